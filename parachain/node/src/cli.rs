@@ -39,6 +39,9 @@ pub enum Subcommand {
 	/// The pallet benchmarking moved to the `pallet` sub-command.
 	#[command(subcommand)]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Dump an account's shadow items at the best block as JSON.
+	ShadowDump(crate::shadow_dump::ShadowDumpCmd),
 }
 
 const AFTER_HELP_EXAMPLE: &str = color_print::cstr!(
@@ -77,6 +80,13 @@ pub struct Cli {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// Expose the Shadow RPC methods that iterate over every account, such as
+	/// `shadow_consentCount`.
+	///
+	/// Their cost grows with chain state, so leave this off on public RPC nodes.
+	#[arg(long)]
+	pub enable_shadow_admin_rpc: bool,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relay_chain_args: Vec<String>,
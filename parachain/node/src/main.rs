@@ -7,8 +7,10 @@ use polkadot_sdk::*;
 mod chain_spec;
 mod cli;
 mod command;
+mod metrics;
 mod rpc;
 mod service;
+mod shadow_dump;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,81 @@
+//! Prometheus gauges mirroring Shadow pallet state.
+//!
+//! Pallets cannot export Prometheus metrics themselves, so the node polls the `ShadowApi`
+//! runtime API on every new best block and writes the results into the registry.
+//!
+//! To verify, run the node with `--prometheus-external`, submit an item and, once the next
+//! block is imported, check `curl -s localhost:9615/metrics | grep substrate_shadow_`.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use pallet_shadow::runtime_api::ShadowApi;
+use parachain_template_runtime::{
+	opaque::{Block, Hash},
+	AccountId, Balance, BlockNumber, Runtime,
+};
+
+use polkadot_sdk::*;
+
+use prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+use sc_client_api::BlockchainEvents;
+use sp_api::ProvideRuntimeApi;
+
+const LOG_TARGET: &str = "shadow-metrics";
+
+/// Gauges for chain-wide Shadow pallet totals.
+pub struct ShadowMetrics {
+	total_items: Gauge<U64>,
+	consents: Gauge<U64>,
+}
+
+impl ShadowMetrics {
+	/// Register the Shadow gauges with `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			total_items: register(
+				Gauge::new("substrate_shadow_total_items", "Shadow items stored across all accounts")?,
+				registry,
+			)?,
+			consents: register(
+				Gauge::new("substrate_shadow_consents", "Accounts holding a consent record")?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// Update `metrics` from the runtime API each time a new best block is imported.
+pub async fn run<C>(client: Arc<C>, metrics: ShadowMetrics)
+where
+	C: ProvideRuntimeApi<Block> + BlockchainEvents<Block>,
+	C::Api: ShadowApi<
+		Block,
+		AccountId,
+		Hash,
+		pallet_shadow::ShadowItem<Runtime>,
+		BlockNumber,
+		pallet_shadow::ConsentAudit<Runtime>,
+		Balance,
+		pallet_shadow::ConsentRecord<Runtime, BlockNumber>,
+	>,
+{
+	let mut imports = client.import_notification_stream();
+
+	while let Some(notification) = imports.next().await {
+		if !notification.is_new_best {
+			continue;
+		}
+
+		let api = client.runtime_api();
+		match (api.total_items(notification.hash), api.consent_count(notification.hash)) {
+			(Ok(total_items), Ok(consents)) => {
+				metrics.total_items.set(total_items.into());
+				metrics.consents.set(consents.into());
+			},
+			(Err(e), _) | (_, Err(e)) => {
+				log::debug!(target: LOG_TARGET, "Failed to query shadow metrics: {e}");
+			},
+		}
+	}
+}
@@ -5,9 +5,15 @@
 
 #![warn(missing_docs)]
 
+pub mod shadow;
+
 use std::sync::Arc;
 
-use parachain_template_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use pallet_shadow::runtime_api::ShadowApi;
+use parachain_template_runtime::{
+	opaque::{Block, Hash},
+	AccountId, Balance, BlockNumber, Nonce, Runtime,
+};
 
 use polkadot_sdk::*;
 
@@ -25,6 +31,8 @@ pub struct FullDeps<C, P> {
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
+	/// Whether to expose the Shadow RPC methods that iterate over every account.
+	pub enable_shadow_admin_rpc: bool,
 }
 
 /// Instantiate all RPC extensions.
@@ -41,15 +49,30 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: ShadowApi<
+		Block,
+		AccountId,
+		Hash,
+		pallet_shadow::ShadowItem<Runtime>,
+		BlockNumber,
+		pallet_shadow::ConsentAudit<Runtime>,
+		Balance,
+		pallet_shadow::ConsentRecord<Runtime, BlockNumber>,
+	>,
 	P: TransactionPool + Sync + Send + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use shadow::{ShadowAdminRpc, ShadowAdminRpcApiServer, ShadowRpc, ShadowRpcApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut module = RpcExtension::new(());
-	let FullDeps { client, pool } = deps;
+	let FullDeps { client, pool, enable_shadow_admin_rpc } = deps;
 
 	module.merge(System::new(client.clone(), pool).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	if enable_shadow_admin_rpc {
+		module.merge(ShadowAdminRpc::new(client.clone()).into_rpc())?;
+	}
+	module.merge(ShadowRpc::new(client).into_rpc())?;
 	Ok(module)
 }
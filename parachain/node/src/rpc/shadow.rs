@@ -0,0 +1,134 @@
+//! `shadow_*` RPC methods, answered through the `ShadowApi` runtime API.
+//!
+//! Per-account queries are always served. Queries that iterate over every account are only
+//! registered when the node runs with `--enable-shadow-admin-rpc`.
+//!
+//! For example, to read an account's items at the best block:
+//!
+//! ```text
+//! curl -s -H 'Content-Type: application/json' localhost:9944 -d '{"id":1,"jsonrpc":"2.0",
+//!     "method":"shadow_getItems","params":["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]}'
+//! ```
+
+use std::sync::Arc;
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObjectOwned};
+use pallet_shadow::runtime_api::ShadowApi;
+use parachain_template_runtime::{
+	opaque::{Block, Hash},
+	AccountId, Balance, BlockNumber, Runtime,
+};
+
+use polkadot_sdk::*;
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(message: &str, e: sp_api::ApiError) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(RUNTIME_ERROR, message, Some(e.to_string()))
+}
+
+/// Shadow pallet queries.
+#[rpc(server)]
+pub trait ShadowRpcApi {
+	/// The items stored by `account` at block `at`, or at the best block if omitted.
+	///
+	/// Each item is an object in the same shape `shadow-dump` prints.
+	#[method(name = "shadow_getItems")]
+	fn get_items(&self, account: AccountId, at: Option<Hash>) -> RpcResult<Vec<serde_json::Value>>;
+
+	/// Number of shadow items stored across all accounts at block `at`, or the best block.
+	#[method(name = "shadow_totalItems")]
+	fn total_items(&self, at: Option<Hash>) -> RpcResult<u32>;
+}
+
+/// Serves [`ShadowRpcApiServer`] from a client's runtime API.
+pub struct ShadowRpc<C> {
+	client: Arc<C>,
+}
+
+impl<C> ShadowRpc<C> {
+	/// Create a handler reading state through `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> ShadowRpcApiServer for ShadowRpc<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ShadowApi<
+		Block,
+		AccountId,
+		Hash,
+		pallet_shadow::ShadowItem<Runtime>,
+		BlockNumber,
+		pallet_shadow::ConsentAudit<Runtime>,
+		Balance,
+		pallet_shadow::ConsentRecord<Runtime, BlockNumber>,
+	>,
+{
+	fn get_items(&self, account: AccountId, at: Option<Hash>) -> RpcResult<Vec<serde_json::Value>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let items = self
+			.client
+			.runtime_api()
+			.shadow_items(at, account)
+			.map_err(|e| runtime_error("Unable to query shadow items", e))?;
+		Ok(items.iter().map(crate::shadow_dump::item_json).collect())
+	}
+
+	fn total_items(&self, at: Option<Hash>) -> RpcResult<u32> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.total_items(at)
+			.map_err(|e| runtime_error("Unable to query total items", e))
+	}
+}
+
+/// Shadow pallet queries whose cost grows with the number of accounts.
+#[rpc(server)]
+pub trait ShadowAdminRpcApi {
+	/// Number of accounts holding a consent record at block `at`, or the best block.
+	#[method(name = "shadow_consentCount")]
+	fn consent_count(&self, at: Option<Hash>) -> RpcResult<u32>;
+}
+
+/// Serves [`ShadowAdminRpcApiServer`] from a client's runtime API.
+pub struct ShadowAdminRpc<C> {
+	client: Arc<C>,
+}
+
+impl<C> ShadowAdminRpc<C> {
+	/// Create a handler reading state through `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> ShadowAdminRpcApiServer for ShadowAdminRpc<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ShadowApi<
+		Block,
+		AccountId,
+		Hash,
+		pallet_shadow::ShadowItem<Runtime>,
+		BlockNumber,
+		pallet_shadow::ConsentAudit<Runtime>,
+		Balance,
+		pallet_shadow::ConsentRecord<Runtime, BlockNumber>,
+	>,
+{
+	fn consent_count(&self, at: Option<Hash>) -> RpcResult<u32> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.consent_count(at)
+			.map_err(|e| runtime_error("Unable to query consent count", e))
+	}
+}
@@ -0,0 +1,93 @@
+//! `shadow-dump` sub-command: print an account's shadow items from the local database.
+//!
+//! Reads `Shadow::ShadowItems` directly from state at the best block, so it works on a stopped
+//! node without RPC. For example:
+//!
+//! ```text
+//! shadow-chain-node shadow-dump --chain dev --base-path /tmp/shadow \
+//!     5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
+//! ```
+//!
+//! prints a JSON array with one object per item (`id`, `cid`, `source`, `timestamp`, ...).
+
+use std::sync::Arc;
+
+use codec::Decode;
+use pallet_shadow::{ShadowItem, ShadowItems, VersionedShadowItem};
+use parachain_template_runtime::{opaque::Block, AccountId, Runtime};
+
+use polkadot_sdk::*;
+
+use sc_cli::{CliConfiguration, SharedParams};
+use sc_client_api::StorageProvider;
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::Ss58Codec, storage::StorageKey};
+
+/// Dump the shadow items stored for an account as JSON.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ShadowDumpCmd {
+	/// SS58 address of the account whose items are dumped.
+	pub account: String,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl ShadowDumpCmd {
+	/// Read the account's items at the best block and print them to stdout.
+	pub fn run<C, B>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: HeaderBackend<Block> + StorageProvider<Block, B>,
+		B: sc_client_api::Backend<Block>,
+	{
+		let account = AccountId::from_ss58check(&self.account)
+			.map_err(|e| sc_cli::Error::Input(format!("Invalid account {}: {e:?}", self.account)))?;
+
+		let best_hash = client.info().best_hash;
+		let key = StorageKey(ShadowItems::<Runtime>::hashed_key_for(&account));
+		let items = match client.storage(best_hash, &key)? {
+			Some(data) => Vec::<VersionedShadowItem<Runtime>>::decode(&mut &data.0[..])
+				.map_err(|e| {
+					sc_cli::Error::Application(format!("Failed to decode shadow items: {e}").into())
+				})?,
+			None => Vec::new(),
+		};
+
+		let dump: Vec<_> = items
+			.into_iter()
+			.map(VersionedShadowItem::current)
+			.map(|item| item_json(&item))
+			.collect();
+
+		let output = serde_json::to_string_pretty(&dump)
+			.map_err(|e| sc_cli::Error::Application(e.into()))?;
+		println!("{output}");
+
+		Ok(())
+	}
+}
+
+/// The JSON object printed for `item`; also returned by the `shadow_getItems` RPC.
+pub fn item_json(item: &ShadowItem<Runtime>) -> serde_json::Value {
+	serde_json::json!({
+		"id": format!("{:?}", item.id),
+		"cid": String::from_utf8_lossy(&item.cid),
+		"source": format!("{:?}", item.source),
+		"timestamp": item.timestamp,
+		"pinned": item.pinned,
+		"expires_at": item.expires_at,
+		"metadata": String::from_utf8_lossy(&item.metadata),
+		"tags": item
+			.tags
+			.iter()
+			.map(|tag| String::from_utf8_lossy(tag).into_owned())
+			.collect::<Vec<_>>(),
+	})
+}
+
+impl CliConfiguration for ShadowDumpCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
@@ -3,110 +3,1241 @@
 
 use super::*;
 use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
-use frame_support::traits::Get;
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use frame_support::{
+	assert_ok,
+	traits::{Currency, EnsureOrigin, Get, Hooks, OnKilledAccount},
+	BoundedVec,
+};
+use polkadot_sdk::{sp_core::crypto::KeyTypeId, sp_io};
+use sp_runtime::Saturating;
+
+/// Fund `who` with enough free balance to grant consent and reserve the deposits of a full
+/// set of items.
+fn fund_for_consent<T: Config>(who: &T::AccountId) {
+	let deposits = T::DepositPerItem::get().saturating_mul(T::MaxItemsPerAccount::get().into());
+	let amount = T::Currency::minimum_balance()
+		.max(T::MinBalanceForConsent::get())
+		.saturating_add(deposits);
+	T::Currency::make_free_balance_be(who, amount);
+}
+
+/// A well-formed base32 CIDv1 of at most `max_len` characters, distinct for each `seed`, so
+/// benchmarks pass `StrictCidValidation`.
+fn benchmark_cid(seed: u32, max_len: u32) -> Vec<u8> {
+	const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+	// Version 1, raw codec, then a sha2-256 multihash sized to fill `max_len`
+	let digest_len = (max_len.saturating_sub(1) * 5 / 8).saturating_sub(4).min(127);
+	let mut bytes = vec![0x01, 0x55, 0x12, digest_len as u8];
+	bytes.extend(seed.to_le_bytes().iter().cycle().take(digest_len as usize));
+
+	let mut cid = vec![b'b'];
+	let (mut buffer, mut bits) = (0u32, 0u32);
+	for byte in bytes {
+		buffer = (buffer << 8) | u32::from(byte);
+		bits += 8;
+		while bits >= 5 {
+			bits -= 5;
+			cid.push(BASE32_ALPHABET[(buffer >> bits) as usize]);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+	if bits > 0 {
+		cid.push(BASE32_ALPHABET[(buffer << (5 - bits)) as usize]);
+	}
+	cid
+}
+
+/// Record a new unexpiring consent for `who` under `message_hash`, as `grant_consent` would,
+/// returning its id.
+///
+/// Written directly since `grant_consent` needs a signature from `who`'s key.
+fn grant_consent_for<T: Config>(who: &T::AccountId, message_hash: &[u8]) -> ConsentId {
+	let bounded_hash = BoundedMessageHash::<T>::try_from(message_hash.to_vec()).unwrap();
+	ConsentByMessage::<T>::mutate(&bounded_hash, |accounts| {
+		if !accounts.contains(who) {
+			let _ = accounts.try_push(who.clone());
+		}
+	});
+	let consent_id = NextConsentId::<T>::mutate(who, |next| {
+		let id = *next;
+		*next += 1;
+		id
+	});
+	let granted_at = frame_system::Pallet::<T>::block_number();
+	ConsentRecords::<T>::insert(
+		who,
+		consent_id,
+		ConsentRecord {
+			granted_at,
+			expires_at: None,
+			message_hash: bounded_hash,
+			scope: Default::default(),
+			needs_resign: false,
+			signature_scheme: Some(crate::signature_scheme::SR25519),
+			cosigned: false,
+			receipt: Pallet::<T>::consent_receipt(who, message_hash, granted_at, None),
+			delegate: None,
+		},
+	);
+	consent_id
+}
+
+/// Fill `message_hash`'s `ConsentByMessage` entry with `n` accounts other than the caller.
+fn fill_consent_index<T: Config>(message_hash: &[u8], n: u32) {
+	let bounded_hash = BoundedMessageHash::<T>::try_from(message_hash.to_vec()).unwrap();
+	ConsentByMessage::<T>::mutate(&bounded_hash, |accounts| {
+		for i in 0..n {
+			let _ = accounts.try_push(account("consenter", i, 0));
+		}
+	});
+}
+
+/// Fund `who` and flag `owner`'s `item_id` from it.
+fn add_flag<T: Config>(who: &T::AccountId, owner: &T::AccountId, item_id: T::Hash) {
+	let amount = T::Currency::minimum_balance().saturating_add(T::FlagDeposit::get());
+	T::Currency::make_free_balance_be(who, amount.saturating_add(amount));
+	let _ = Pallet::<T>::flag_item(RawOrigin::Signed(who.clone()).into(), owner.clone(), item_id, 0);
+}
+
+/// Grant consent for `caller` and store a single item, returning its id.
+fn setup_item<T: Config>(caller: &T::AccountId) -> T::Hash {
+	fund_for_consent::<T>(caller);
+	grant_consent_for::<T>(caller, b"benchmark_consent");
+	let _ = Pallet::<T>::submit_shadow_item(
+		RawOrigin::Signed(caller.clone()).into(),
+		benchmark_cid(0, 46),
+		vec![b'k'; 256],
+		Source::GitHub,
+		vec![b'm'; 128],
+		None,
+	);
+
+	let items = Pallet::<T>::items_of(caller);
+	items[0].id
+}
+
+/// Grant consent for `caller` and store `n` distinct items, clear of the submission rate
+/// limit.
+fn fill_items<T: Config>(caller: &T::AccountId, n: u32) {
+	fund_for_consent::<T>(caller);
+	grant_consent_for::<T>(caller, b"benchmark_consent");
+	for i in 0..n {
+		SubmissionRate::<T>::remove(caller);
+		let _ = Pallet::<T>::submit_shadow_item(
+			RawOrigin::Signed(caller.clone()).into(),
+			benchmark_cid(i, 46),
+			vec![b'k'; 256],
+			Source::GitHub,
+			vec![b'm'; 128],
+			None,
+		);
+	}
+}
 
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn submit_shadow_item() {
+	fn submit_shadow_item(m: Linear<0, { T::MaxMetadataLengthHard::get() }>) {
 		let caller: T::AccountId = whitelisted_caller();
 		
 		// Grant consent first
 		let message_hash = b"benchmark_consent".to_vec();
-		let _ = Pallet::<T>::grant_consent(
-			RawOrigin::Signed(caller.clone()).into(),
-			message_hash,
-			None,
-		);
+		fund_for_consent::<T>(&caller);
+		grant_consent_for::<T>(&caller, &message_hash);
+		// Allow metadata up to the hard limit
+		AccountMetadataLimit::<T>::insert(&caller, T::MaxMetadataLengthHard::get());
 
-		let cid = vec![b'Q'; 46]; // Typical CID length
+		// Fill the account up to one below the cap so `try_mutate` decodes, `try_push`
+		// bounds-checks and re-encodes the largest vector it can still accept. The rate
+		// limit is cleared between submissions so the setup is not throttled.
+		for i in 0..T::MaxItemsPerAccount::get().saturating_sub(1) {
+			SubmissionRate::<T>::remove(&caller);
+			let _ = Pallet::<T>::submit_shadow_item(
+				RawOrigin::Signed(caller.clone()).into(),
+				benchmark_cid(i, 46),
+				vec![b'k'; 256],
+				Source::GitHub,
+				vec![b'm'; 128],
+				None,
+			);
+		}
+
+		let cid = benchmark_cid(u32::MAX, 46); // Typical CID length
 		let encrypted_key = vec![b'k'; 256]; // Typical key length
-		let metadata = vec![b'm'; 128]; // Typical metadata length
+		let metadata = vec![b'm'; m as usize];
 
 		#[extrinsic_call]
 		submit_shadow_item(
 			RawOrigin::Signed(caller.clone()),
 			cid.clone(),
 			encrypted_key,
-			0u8,
+			Source::GitHub,
 			metadata,
+			Some(frame_system::Pallet::<T>::block_number().saturating_add(1_000u32.into())),
 		);
 
 		// Verify
-		let items = ShadowItems::<T>::get(&caller);
-		assert_eq!(items.len(), 1);
-		assert_eq!(items[0].cid, cid);
+		let items = Pallet::<T>::items_of(&caller);
+		assert_eq!(items.len() as u32, T::MaxItemsPerAccount::get());
+		assert_eq!(items[items.len() - 1].cid, cid);
+	}
+
+	#[benchmark]
+	fn delete_shadow_item(n: Linear<1, { T::MaxItemsPerAccount::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		// The first item, so the last one is moved into its slot and reindexed
+		fill_items::<T>(&caller, n);
+		let item_id = Pallet::<T>::items_of(&caller)[0].id;
+
+		#[extrinsic_call]
+		delete_shadow_item(RawOrigin::Signed(caller.clone()), item_id);
+
+		// Verify
+		assert_eq!(Pallet::<T>::items_of(&caller).len() as u32, n - 1);
+	}
+
+	#[benchmark]
+	fn grant_consent() {
+		let caller: T::AccountId = whitelisted_caller();
+		let message_hash = vec![b'h'; 32];
+		let duration = Some(BlockNumberFor::<T>::from(100u32));
+		// ECDSA recovery is the most expensive of the supported schemes
+		let public = sp_io::crypto::ecdsa_generate(KeyTypeId(*b"shdw"), None);
+		let signer =
+			T::BenchmarkHelper::account_for_key(sp_io::hashing::blake2_256(public.as_ref()));
+		fund_for_consent::<T>(&signer);
+
+		// Worst case: the signer holds all but one of its consents and joins an index entry
+		// a slot short of full
+		for _ in 1..T::MaxConsentsPerAccount::get() {
+			grant_consent_for::<T>(&signer, b"benchmark_consent");
+		}
+		fill_consent_index::<T>(&message_hash, T::MaxAccountsPerMessage::get().saturating_sub(1));
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let scope = vec![Source::GitHub, Source::Twitter];
+		let valid_until = frame_system::Pallet::<T>::block_number();
+		let action = ConsentAuthorization::Grant {
+			message_hash: message_hash.clone(),
+			duration,
+			scope: scope.iter().copied().collect::<BTreeSet<_>>().try_into().unwrap(),
+			delegate: Some(delegate.clone()),
+		};
+		let payload = Pallet::<T>::consent_payload(&signer, action, valid_until);
+		let signature = sp_io::crypto::ecdsa_sign(KeyTypeId(*b"shdw"), &public, &payload).unwrap();
+
+		#[extrinsic_call]
+		grant_consent(
+			RawOrigin::Signed(caller),
+			message_hash.clone(),
+			duration,
+			scope,
+			Some(delegate),
+			ConsentSignature {
+				valid_until,
+				scheme: crate::signature_scheme::ECDSA,
+				signature: signature.0.to_vec(),
+			},
+			signer.clone(),
+		);
+
+		// Verify
+		let consent_id = T::MaxConsentsPerAccount::get().saturating_sub(1);
+		let consent = ConsentRecords::<T>::get(&signer, consent_id).unwrap();
+		assert_eq!(consent.message_hash, message_hash);
 	}
 
 	#[benchmark]
-	fn delete_shadow_item() {
+	fn revoke_consent() {
 		let caller: T::AccountId = whitelisted_caller();
 		
-		// Grant consent and add an item first
+		// Grant consent first, alongside the most other consents the caller can hold, so
+		// every one of them is checked before the message hash is unindexed
+		let message_hash = b"revoked_consent".to_vec();
+		fund_for_consent::<T>(&caller);
+		for _ in 1..T::MaxConsentsPerAccount::get() {
+			grant_consent_for::<T>(&caller, b"benchmark_consent");
+		}
+		let consent_id = grant_consent_for::<T>(&caller, &message_hash);
+		fill_consent_index::<T>(&message_hash, T::MaxAccountsPerMessage::get().saturating_sub(1));
+
+		#[extrinsic_call]
+		revoke_consent(RawOrigin::Signed(caller.clone()), consent_id);
+
+		// Verify
+		assert!(ConsentRecords::<T>::get(&caller, consent_id).is_none());
+	}
+
+	#[benchmark]
+	fn set_metadata_limit_for_account() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("premium", 0, 0);
+		let limit = T::MaxMetadataLengthHard::get();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone(), Some(limit));
+
+		// Verify
+		assert_eq!(AccountMetadataLimit::<T>::get(&who), Some(limit));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn authorize_session() {
+		let caller: T::AccountId = whitelisted_caller();
+		let session: T::AccountId = account("session", 0, 0);
+		let expires_at = frame_system::Pallet::<T>::block_number() + T::MaxSessionDuration::get();
+
+		#[extrinsic_call]
+		authorize_session(RawOrigin::Signed(caller.clone()), session.clone(), expires_at);
+
+		// Verify
+		assert_eq!(Sessions::<T>::get(&caller, &session), Some(expires_at));
+	}
+
+	#[benchmark]
+	fn submit_shadow_item_for(m: Linear<0, { T::MaxMetadataLengthHard::get() }>) {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let session: T::AccountId = whitelisted_caller();
+
+		// Grant every consent the owner can hold, none naming the session as delegate, so
+		// all are examined before the session is
 		let message_hash = b"benchmark_consent".to_vec();
-		let _ = Pallet::<T>::grant_consent(
-			RawOrigin::Signed(caller.clone()).into(),
-			message_hash,
-			None,
+		fund_for_consent::<T>(&owner);
+		for _ in 0..T::MaxConsentsPerAccount::get() {
+			grant_consent_for::<T>(&owner, &message_hash);
+		}
+		let expires_at = frame_system::Pallet::<T>::block_number() + T::MaxSessionDuration::get();
+		let _ = Pallet::<T>::authorize_session(
+			RawOrigin::Signed(owner.clone()).into(),
+			session.clone(),
+			expires_at,
 		);
 
-		let cid = vec![b'Q'; 46];
+		AccountMetadataLimit::<T>::insert(&owner, T::MaxMetadataLengthHard::get());
+
+		let cid = benchmark_cid(u32::MAX, 46);
 		let encrypted_key = vec![b'k'; 256];
-		let metadata = vec![b'm'; 128];
+		let metadata = vec![b'm'; m as usize];
 
-		let _ = Pallet::<T>::submit_shadow_item(
-			RawOrigin::Signed(caller.clone()).into(),
-			cid,
+		#[extrinsic_call]
+		submit_shadow_item_for(
+			RawOrigin::Signed(session),
+			owner.clone(),
+			cid.clone(),
 			encrypted_key,
-			0u8,
+			Source::GitHub,
 			metadata,
 		);
 
-		let items = ShadowItems::<T>::get(&caller);
-		let item_id = T::Hash::decode(&mut &items[0].id[..]).unwrap();
+		// Verify
+		let items = Pallet::<T>::items_of(&owner);
+		assert_eq!(items.len(), 1);
+		assert_eq!(items[0].cid, cid);
+	}
+
+	#[benchmark]
+	fn pin_item() {
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = setup_item::<T>(&caller);
 
 		#[extrinsic_call]
-		delete_shadow_item(RawOrigin::Signed(caller.clone()), item_id);
+		pin_item(RawOrigin::Signed(caller.clone()), item_id);
 
 		// Verify
-		let items_after = ShadowItems::<T>::get(&caller);
-		assert_eq!(items_after.len(), 0);
+		assert!(Pallet::<T>::items_of(&caller)[0].pinned);
 	}
 
 	#[benchmark]
-	fn grant_consent() {
+	fn unpin_item() {
 		let caller: T::AccountId = whitelisted_caller();
-		let message_hash = vec![b'h'; 32];
-		let duration = Some(T::BlockNumber::from(100u32));
+		let item_id = setup_item::<T>(&caller);
+		let _ = Pallet::<T>::pin_item(RawOrigin::Signed(caller.clone()).into(), item_id);
 
 		#[extrinsic_call]
-		grant_consent(RawOrigin::Signed(caller.clone()), message_hash.clone(), duration);
+		unpin_item(RawOrigin::Signed(caller.clone()), item_id);
 
 		// Verify
-		let consent = ConsentRecords::<T>::get(&caller).unwrap();
-		assert_eq!(consent.message_hash, message_hash);
+		assert!(!Pallet::<T>::items_of(&caller)[0].pinned);
 	}
 
 	#[benchmark]
-	fn revoke_consent() {
+	fn delete_all_shadow_items(n: Linear<0, { T::MaxItemsPerAccount::get() }>) {
 		let caller: T::AccountId = whitelisted_caller();
-		
-		// Grant consent first
-		let message_hash = b"benchmark_consent".to_vec();
-		let _ = Pallet::<T>::grant_consent(
+		fill_items::<T>(&caller, n);
+
+		#[extrinsic_call]
+		delete_all_shadow_items(RawOrigin::Signed(caller.clone()));
+
+		// Verify
+		assert!(Pallet::<T>::items_of(&caller).is_empty());
+	}
+
+	#[benchmark]
+	fn authorize_agent() {
+		let caller: T::AccountId = whitelisted_caller();
+		// Fill to one below the cap so the membership scan covers the longest list
+		for i in 0..T::MaxAgents::get().saturating_sub(1) {
+			let _ = Pallet::<T>::authorize_agent(
+				RawOrigin::Signed(caller.clone()).into(),
+				account("agent", i, 0),
+			);
+		}
+		let agent: T::AccountId = account("agent", T::MaxAgents::get(), 0);
+
+		#[extrinsic_call]
+		authorize_agent(RawOrigin::Signed(caller.clone()), agent.clone());
+
+		// Verify
+		assert!(AuthorizedAgents::<T>::get(&caller).contains(&agent));
+	}
+
+	#[benchmark]
+	fn revoke_all_agents() {
+		let caller: T::AccountId = whitelisted_caller();
+		for i in 0..T::MaxAgents::get() {
+			let _ = Pallet::<T>::authorize_agent(
+				RawOrigin::Signed(caller.clone()).into(),
+				account("agent", i, 0),
+			);
+		}
+
+		#[extrinsic_call]
+		revoke_all_agents(RawOrigin::Signed(caller.clone()));
+
+		// Verify
+		assert!(AuthorizedAgents::<T>::get(&caller).is_empty());
+	}
+
+	#[benchmark]
+	fn share_item() {
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = setup_item::<T>(&caller);
+		// Fill to one below the cap so the push re-encodes the longest recipient list
+		for i in 0..T::MaxSharesPerItem::get().saturating_sub(1) {
+			let _ = Pallet::<T>::share_item(
+				RawOrigin::Signed(caller.clone()).into(),
+				item_id,
+				account("recipient", i, 0),
+				vec![b'k'; 256],
+			);
+		}
+		let recipient: T::AccountId = account("recipient", T::MaxSharesPerItem::get(), 0);
+
+		#[extrinsic_call]
+		share_item(
+			RawOrigin::Signed(caller.clone()),
+			item_id,
+			recipient.clone(),
+			vec![b'k'; T::MaxKeyLength::get() as usize],
+		);
+
+		// Verify
+		assert!(SharedKeys::<T>::contains_key(&recipient, item_id));
+	}
+
+	#[benchmark]
+	fn revoke_share() {
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = setup_item::<T>(&caller);
+		// Share with the maximum number of recipients so the longest list is searched
+		for i in 0..T::MaxSharesPerItem::get() {
+			let _ = Pallet::<T>::share_item(
+				RawOrigin::Signed(caller.clone()).into(),
+				item_id,
+				account("recipient", i, 0),
+				vec![b'k'; 256],
+			);
+		}
+		let recipient: T::AccountId =
+			account("recipient", T::MaxSharesPerItem::get().saturating_sub(1), 0);
+
+		#[extrinsic_call]
+		revoke_share(RawOrigin::Signed(caller.clone()), item_id, recipient.clone());
+
+		// Verify
+		assert!(!SharedKeys::<T>::contains_key(&recipient, item_id));
+	}
+
+	#[benchmark]
+	fn amend_consent_scope() {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_for_consent::<T>(&caller);
+		let consent_id = grant_consent_for::<T>(&caller, b"benchmark_consent");
+		let max_sources = T::MaxScopeSources::get() as usize;
+
+		#[extrinsic_call]
+		amend_consent_scope(
+			RawOrigin::Signed(caller.clone()),
+			consent_id,
+			vec![Source::GitHub; max_sources],
+			vec![Source::Twitter; max_sources],
+		);
+
+		// Verify
+		assert_eq!(
+			ConsentRecords::<T>::get(&caller, consent_id).unwrap().scope.len(),
+			Source::ALL.len() - 1
+		);
+	}
+
+	#[benchmark]
+	fn register_consent_template() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let message_hash = vec![b'h'; T::MaxMessageHashLength::get() as usize];
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, message_hash.clone());
+
+		// Verify
+		assert!(ConsentTemplates::<T>::contains_key(
+			BoundedMessageHash::<T>::try_from(message_hash).unwrap()
+		));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_cid() {
+		let caller: T::AccountId = whitelisted_caller();
+		// Update the last item, so both the lookup and the duplicate scan cover every item
+		fill_items::<T>(&caller, T::MaxItemsPerAccount::get());
+		let items = Pallet::<T>::items_of(&caller);
+		let item_id = items[items.len() - 1].id;
+		let new_cid = benchmark_cid(u32::MAX, T::MaxCidLength::get());
+
+		#[extrinsic_call]
+		update_cid(RawOrigin::Signed(caller.clone()), item_id, new_cid.clone());
+
+		// Verify
+		let items = Pallet::<T>::items_of(&caller);
+		assert_eq!(items[items.len() - 1].cid, new_cid);
+	}
+
+	#[benchmark]
+	fn update_shadow_item(m: Linear<0, { T::MaxMetadataLengthHard::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		// Update the last item, so both the lookup and the duplicate scan cover every item
+		fill_items::<T>(&caller, T::MaxItemsPerAccount::get());
+		// Allow metadata up to the hard limit
+		AccountMetadataLimit::<T>::insert(&caller, T::MaxMetadataLengthHard::get());
+		let items = Pallet::<T>::items_of(&caller);
+		let item_id = items[items.len() - 1].id;
+		let new_cid = benchmark_cid(u32::MAX, T::MaxCidLength::get());
+		let new_key = vec![b'k'; T::MaxKeyLength::get() as usize];
+		let new_metadata = vec![b'm'; m as usize];
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), item_id, new_cid.clone(), new_key, new_metadata);
+
+		// Verify
+		let items = Pallet::<T>::items_of(&caller);
+		assert_eq!(items[items.len() - 1].cid, new_cid);
+	}
+
+	#[benchmark]
+	fn transfer_consent() {
+		let caller: T::AccountId = whitelisted_caller();
+		let new_account: T::AccountId = account("new_account", 0, 0);
+		fund_for_consent::<T>(&caller);
+		// The caller holds every consent it can and is last in a full index entry, so each
+		// moved consent scans every account
+		fill_consent_index::<T>(
+			b"benchmark_consent",
+			T::MaxAccountsPerMessage::get().saturating_sub(1),
+		);
+		for _ in 0..T::MaxConsentsPerAccount::get() {
+			grant_consent_for::<T>(&caller, b"benchmark_consent");
+		}
+
+		#[extrinsic_call]
+		transfer_consent(RawOrigin::Signed(caller.clone()), new_account.clone());
+
+		// Verify
+		assert!(ConsentRecords::<T>::get(&new_account, 0).unwrap().needs_resign);
+	}
+
+	#[benchmark]
+	fn set_source_meta_template() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let template = vec![b't'; T::MaxMetadataLength::get() as usize];
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Source::GitHub, Some(template.clone()));
+
+		// Verify
+		assert_eq!(SourceMetaTemplate::<T>::get(Source::GitHub).unwrap(), template);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn report_dead_cid() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ReporterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let owner: T::AccountId = account("owner", 0, 0);
+		fill_items::<T>(&owner, T::MaxItemsPerAccount::get());
+		let items = Pallet::<T>::items_of(&owner);
+		let item_id = items[items.len() - 1].id;
+		// The benchmarked report is the one that reaches the threshold and removes the item
+		DeadCidReports::<T>::mutate(item_id, |reporters| {
+			for i in 0..T::DeadCidThreshold::get().saturating_sub(1) {
+				let _ = reporters.try_push(account("reporter", i, 0));
+			}
+		});
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, owner.clone(), item_id);
+
+		// Verify
+		assert_eq!(Pallet::<T>::items_of(&owner).len() as u32, T::MaxItemsPerAccount::get() - 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_delete_item() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let owner: T::AccountId = account("owner", 0, 0);
+		fill_items::<T>(&owner, T::MaxItemsPerAccount::get());
+		let items = Pallet::<T>::items_of(&owner);
+		let item_id = items[items.len() - 1].id;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, owner.clone(), item_id);
+
+		// Verify
+		assert_eq!(Pallet::<T>::items_of(&owner).len() as u32, T::MaxItemsPerAccount::get() - 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn submit_shadow_item_with_encrypted_metadata(
+		m: Linear<0, { T::MaxMetadataLengthHard::get() }>,
+		e: Linear<0, { T::MaxEncMetaLength::get() }>,
+	) {
+		let caller: T::AccountId = whitelisted_caller();
+		fill_items::<T>(&caller, T::MaxItemsPerAccount::get().saturating_sub(1));
+		AccountMetadataLimit::<T>::insert(&caller, T::MaxMetadataLengthHard::get());
+
+		let cid = benchmark_cid(u32::MAX, 46);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			cid.clone(),
+			vec![b'k'; 256],
+			Source::GitHub,
+			vec![b'm'; m as usize],
+			vec![b'e'; e as usize],
+		);
+
+		// Verify
+		let items = Pallet::<T>::items_of(&caller);
+		assert_eq!(items.len() as u32, T::MaxItemsPerAccount::get());
+		assert_eq!(items[items.len() - 1].encrypted_metadata.as_ref().map(|e| e.len()), Some(e as usize));
+	}
+
+	#[benchmark]
+	fn add_trusted() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("trusted", 0, 0);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		// Verify
+		assert!(TrustedSubmitters::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_trusted() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("trusted", 0, 0);
+		TrustedSubmitters::<T>::insert(&who, ());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		// Verify
+		assert!(!TrustedSubmitters::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn flag_item() {
+		let owner: T::AccountId = account("owner", 0, 0);
+		// Flag the last item of a full account, one flag short of the cap
+		fill_items::<T>(&owner, T::MaxItemsPerAccount::get());
+		let items = Pallet::<T>::items_of(&owner);
+		let item_id = items[items.len() - 1].id;
+		for i in 1..T::MaxFlagsPerItem::get() {
+			add_flag::<T>(&account("flagger", i, 0), &owner, item_id);
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = T::Currency::minimum_balance().saturating_add(T::FlagDeposit::get());
+		T::Currency::make_free_balance_be(&caller, amount.saturating_add(amount));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), owner.clone(), item_id, 0u8);
+
+		// Verify
+		assert_eq!(Flags::<T>::get(&owner, item_id).len() as u32, T::MaxFlagsPerItem::get());
+	}
+
+	#[benchmark]
+	fn resolve_flags(n: Linear<1, { T::MaxFlagsPerItem::get() }>) -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let owner: T::AccountId = account("owner", 0, 0);
+		let item_id = setup_item::<T>(&owner);
+		for i in 0..n {
+			add_flag::<T>(&account("flagger", i, 0), &owner, item_id);
+		}
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, owner.clone(), item_id, true);
+
+		// Verify
+		assert!(Flags::<T>::get(&owner, item_id).is_empty());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reserve_slots() {
+		let caller: T::AccountId = whitelisted_caller();
+		let count = T::MaxItemsPerAccount::get();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), count);
+
+		// Verify
+		assert_eq!(ReservedSlots::<T>::get(&caller), count);
+	}
+
+	#[benchmark]
+	fn release_slots() {
+		let caller: T::AccountId = whitelisted_caller();
+		ReservedSlots::<T>::insert(&caller, T::MaxItemsPerAccount::get());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		// Verify
+		assert!(!ReservedSlots::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn prune_empty_accounts(n: Linear<0, 1_000>) -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		// Every examined account is emptied, with each record pruning removes
+		let now = frame_system::Pallet::<T>::block_number();
+		for i in 0..n {
+			let who: T::AccountId = account("emptied", i, 0);
+			ShadowItems::<T>::insert(&who, BoundedVec::default());
+			AccountStats::<T>::insert(&who, Stats::default());
+			AccountBytes::<T>::insert(&who, 0);
+			ItemCounter::<T>::insert(&who, 1);
+			AccountMetadataLimit::<T>::insert(&who, 64);
+			SubmissionRate::<T>::insert(&who, (now, 1));
+		}
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(T::RateLimitWindow::get()));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, n);
+
+		// Verify
+		assert_eq!(ShadowItems::<T>::iter_keys().count(), 0);
+		assert_eq!(AccountStats::<T>::iter_keys().count(), 0);
+		assert_eq!(SubmissionRate::<T>::iter_keys().count(), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn on_finalize(n: Linear<0, { T::MaxAggregatedAccounts::get() }>) {
+		PendingItemsStored::<T>::mutate(|pending| {
+			for i in 0..n {
+				let _ = pending.try_insert(account("submitter", i, 0), 1);
+			}
+		});
+		SubmissionsThisBlock::<T>::put(n.max(1));
+		let now = frame_system::Pallet::<T>::block_number();
+
+		#[block]
+		{
+			Pallet::<T>::on_finalize(now);
+		}
+
+		// Verify
+		assert_eq!(SubmissionsThisBlock::<T>::get(), 0);
+		assert_eq!(TotalSubmissions::<T>::get(), n.max(1) as u64);
+	}
+
+	#[benchmark]
+	fn prune_expired_consents(n: Linear<0, { T::MaxConsentPrunedPerBlock::get() }>) {
+		// Every examined record has expired and is removed
+		for i in 0..n {
+			let who: T::AccountId = account("consenter", i, 0);
+			let consent_id = grant_consent_for::<T>(&who, &i.to_le_bytes());
+			ConsentRecords::<T>::mutate(&who, consent_id, |record| {
+				if let Some(record) = record {
+					record.expires_at = Some(1u32.into());
+				}
+			});
+		}
+		let now: BlockNumberFor<T> = 10u32.into();
+		frame_system::Pallet::<T>::set_block_number(now);
+
+		#[block]
+		{
+			Pallet::<T>::on_initialize(now);
+		}
+
+		// Verify
+		assert_eq!(ConsentRecords::<T>::iter_keys().count(), 0);
+	}
+
+	#[benchmark]
+	fn expire_items(n: Linear<0, { T::MaxAccountsExpiredPerBlock::get() }>) {
+		// Every examined account is full of expired items
+		for i in 0..n {
+			let who: T::AccountId = account("owner", i, 0);
+			fill_items::<T>(&who, T::MaxItemsPerAccount::get());
+			ShadowItems::<T>::mutate(&who, |stored| {
+				for item in stored.iter_mut() {
+					let mut current = item.clone().current();
+					current.expires_at = Some(1u32.into());
+					*item = current.into();
+				}
+			});
+		}
+		let now: BlockNumberFor<T> = 10u32.into();
+		frame_system::Pallet::<T>::set_block_number(now);
+
+		#[block]
+		{
+			Pallet::<T>::on_initialize(now);
+		}
+
+		// Verify
+		assert_eq!(TotalItems::<T>::get(), 0);
+	}
+
+	#[benchmark]
+	fn clear_reaped_item() {
+		// A reaped item with every tag, share, flag and access log entry it can hold
+		let owner: T::AccountId = account("owner", 0, 0);
+		let item_id = setup_item::<T>(&owner);
+		for i in 0..T::MaxTagsPerItem::get() {
+			assert_ok!(Pallet::<T>::add_tag(
+				RawOrigin::Signed(owner.clone()).into(),
+				item_id,
+				i.to_le_bytes().to_vec(),
+			));
+		}
+		for i in 0..T::MaxSharesPerItem::get() {
+			assert_ok!(Pallet::<T>::share_item(
+				RawOrigin::Signed(owner.clone()).into(),
+				item_id,
+				account("recipient", i, 0),
+				vec![b'k'; 256],
+			));
+		}
+		for i in 0..T::MaxFlagsPerItem::get() {
+			add_flag::<T>(&account("flagger", i, 0), &owner, item_id);
+		}
+		let entries = (0..T::MaxAccessLogEntries::get())
+			.map(|_| (owner.clone(), frame_system::Pallet::<T>::block_number()))
+			.collect::<Vec<_>>();
+		AccessLog::<T>::insert(&owner, item_id, BoundedVec::truncate_from(entries));
+		Pallet::<T>::on_killed_account(&owner);
+		let tags = ReapedItems::<T>::get(&owner, item_id).unwrap();
+
+		#[block]
+		{
+			Pallet::<T>::clear_reaped_item(&owner, item_id, &tags);
+		}
+
+		// Verify
+		assert!(!ReapedItems::<T>::contains_key(&owner, item_id));
+		assert!(Flags::<T>::get(&owner, item_id).is_empty());
+		assert!(ShareRecipients::<T>::get(item_id).is_empty());
+	}
+
+	#[benchmark]
+	fn verify_consent_signature() {
+		// ECDSA recovery is the most expensive of the supported schemes
+		let message = b"test_consent".to_vec();
+		let public = sp_io::crypto::ecdsa_generate(KeyTypeId(*b"shdw"), None);
+		let signature =
+			sp_io::crypto::ecdsa_sign(KeyTypeId(*b"shdw"), &public, &message).unwrap();
+		let key = sp_io::hashing::blake2_256(public.as_ref());
+		let valid;
+
+		#[block]
+		{
+			valid = Pallet::<T>::verify_signature(
+				crate::signature_scheme::ECDSA,
+				&key,
+				&message,
+				signature.as_ref(),
+			);
+		}
+
+		// Verify
+		assert_eq!(valid, Ok(true));
+	}
+
+	#[benchmark]
+	fn add_tag() {
+		// Tag the last item of a full account, one tag short of the cap
+		let caller: T::AccountId = whitelisted_caller();
+		fill_items::<T>(&caller, T::MaxItemsPerAccount::get());
+		let items = Pallet::<T>::items_of(&caller);
+		let item_id = items[items.len() - 1].id;
+		for i in 1..T::MaxTagsPerItem::get() {
+			assert_ok!(Pallet::<T>::add_tag(
+				RawOrigin::Signed(caller.clone()).into(),
+				item_id,
+				i.to_le_bytes().to_vec(),
+			));
+		}
+		let tag = vec![b't'; T::MaxTagLength::get() as usize];
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), item_id, tag.clone());
+
+		// Verify
+		let items = Pallet::<T>::items_of(&caller);
+		assert_eq!(items[items.len() - 1].tags.len() as u32, T::MaxTagsPerItem::get());
+	}
+
+	#[benchmark]
+	fn remove_tag() {
+		// Remove the last tag of the last item of a full account
+		let caller: T::AccountId = whitelisted_caller();
+		fill_items::<T>(&caller, T::MaxItemsPerAccount::get());
+		let items = Pallet::<T>::items_of(&caller);
+		let item_id = items[items.len() - 1].id;
+		for i in 0..T::MaxTagsPerItem::get() {
+			assert_ok!(Pallet::<T>::add_tag(
+				RawOrigin::Signed(caller.clone()).into(),
+				item_id,
+				i.to_le_bytes().to_vec(),
+			));
+		}
+		let tag = T::MaxTagsPerItem::get().saturating_sub(1).to_le_bytes().to_vec();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), item_id, tag);
+
+		// Verify
+		let items = Pallet::<T>::items_of(&caller);
+		assert_eq!(
+			items[items.len() - 1].tags.len() as u32,
+			T::MaxTagsPerItem::get().saturating_sub(1)
+		);
+	}
+
+	#[benchmark]
+	fn submit_shadow_items_batch(n: Linear<0, { T::MaxBatchSize::get() }>) {
+		// Fill the account so the batch takes it exactly to the cap, with maximal metadata
+		let caller: T::AccountId = whitelisted_caller();
+		fill_items::<T>(&caller, T::MaxItemsPerAccount::get().saturating_sub(n));
+		AccountMetadataLimit::<T>::insert(&caller, T::MaxMetadataLengthHard::get());
+		let items: Vec<_> = (0..n)
+			.map(|i| {
+				(
+					benchmark_cid(u32::MAX - i, 46),
+					vec![b'k'; 256],
+					Source::GitHub,
+					vec![b'm'; T::MaxMetadataLengthHard::get() as usize],
+				)
+			})
+			.collect();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), items);
+
+		// Verify
+		assert_eq!(Pallet::<T>::items_of(&caller).len() as u32, T::MaxItemsPerAccount::get());
+	}
+
+	#[benchmark]
+	fn opt_out(n: Linear<0, { T::MaxItemsPerAccount::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		// Every consent the caller can hold is revoked, the caller last in a full index entry
+		fill_consent_index::<T>(
+			b"benchmark_consent",
+			T::MaxAccountsPerMessage::get().saturating_sub(1),
+		);
+		fill_items::<T>(&caller, n);
+		for _ in 1..T::MaxConsentsPerAccount::get() {
+			grant_consent_for::<T>(&caller, b"benchmark_consent");
+		}
+
+		#[extrinsic_call]
+		opt_out(RawOrigin::Signed(caller.clone()));
+
+		// Verify
+		assert!(OptedOut::<T>::contains_key(&caller));
+		assert_eq!(ConsentRecords::<T>::iter_prefix(&caller).count(), 0);
+	}
+
+	#[benchmark]
+	fn opt_in() {
+		let caller: T::AccountId = whitelisted_caller();
+		OptedOut::<T>::insert(&caller, ());
+
+		#[extrinsic_call]
+		opt_in(RawOrigin::Signed(caller.clone()));
+
+		// Verify
+		assert!(!OptedOut::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn renew_consent() {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_for_consent::<T>(&caller);
+		let consent_id = grant_consent_for::<T>(&caller, b"benchmark_consent");
+		ConsentRecords::<T>::mutate(&caller, consent_id, |consent| {
+			consent.as_mut().unwrap().expires_at = Some(100u32.into());
+		});
+
+		#[extrinsic_call]
+		renew_consent(RawOrigin::Signed(caller.clone()), consent_id, 50u32.into());
+
+		// Verify
+		assert_eq!(
+			ConsentRecords::<T>::get(&caller, consent_id).unwrap().expires_at,
+			Some(150u32.into())
+		);
+	}
+
+	#[benchmark]
+	fn replace_item(m: Linear<0, { T::MaxMetadataLengthHard::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_for_consent::<T>(&caller);
+		grant_consent_for::<T>(&caller, b"benchmark_consent");
+		AccountMetadataLimit::<T>::insert(&caller, T::MaxMetadataLengthHard::get());
+
+		// A full account, so the whole vector is decoded and re-encoded, clearing the rate
+		// limit between submissions
+		for i in 0..T::MaxItemsPerAccount::get() {
+			SubmissionRate::<T>::remove(&caller);
+			let _ = Pallet::<T>::submit_shadow_item(
+				RawOrigin::Signed(caller.clone()).into(),
+				benchmark_cid(i, 46),
+				vec![b'k'; 256],
+				Source::GitHub,
+				vec![b'm'; 128],
+				None,
+			);
+		}
+		SubmissionRate::<T>::remove(&caller);
+		let old_item_id = Pallet::<T>::items_of(&caller)[0].id;
+		let cid = benchmark_cid(u32::MAX, 46);
+
+		#[extrinsic_call]
+		replace_item(
+			RawOrigin::Signed(caller.clone()),
+			old_item_id,
+			cid.clone(),
+			vec![b'k'; 256],
+			Source::GitHub,
+			vec![b'm'; m as usize],
+		);
+
+		// Verify
+		let items = Pallet::<T>::items_of(&caller);
+		assert_eq!(items.len() as u32, T::MaxItemsPerAccount::get());
+		assert_eq!(items[0].cid, cid);
+	}
+
+	#[benchmark]
+	fn transfer_item_ownership() {
+		let caller: T::AccountId = whitelisted_caller();
+		let new_owner: T::AccountId = account("new_owner", 0, 0);
+		let item_id = setup_item::<T>(&caller);
+
+		#[extrinsic_call]
+		transfer_item_ownership(RawOrigin::Signed(caller.clone()), item_id, new_owner.clone());
+
+		// Verify
+		assert_eq!(PendingTransfers::<T>::get(&caller, item_id), Some(new_owner));
+	}
+
+	#[benchmark]
+	fn delete_items_by_source(n: Linear<0, { T::MaxItemsPerAccount::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		// Every item is from the deleted source, so all of them are removed
+		fill_items::<T>(&caller, n);
+
+		#[extrinsic_call]
+		delete_items_by_source(RawOrigin::Signed(caller.clone()), Source::GitHub);
+
+		// Verify
+		assert!(Pallet::<T>::items_of(&caller).is_empty());
+	}
+
+	#[benchmark]
+	fn access_item() {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = setup_item::<T>(&owner);
+		let _ = Pallet::<T>::share_item(
+			RawOrigin::Signed(owner.clone()).into(),
+			item_id,
+			caller.clone(),
+			vec![b'k'; 256],
+		);
+		// A full log, so the oldest access is dropped
+		let entries = (0..T::MaxAccessLogEntries::get())
+			.map(|_| (owner.clone(), frame_system::Pallet::<T>::block_number()))
+			.collect::<Vec<_>>();
+		AccessLog::<T>::insert(&owner, item_id, BoundedVec::truncate_from(entries));
+
+		#[extrinsic_call]
+		access_item(RawOrigin::Signed(caller.clone()), owner.clone(), item_id);
+
+		// Verify
+		assert_eq!(AccessLog::<T>::get(&owner, item_id).last().map(|(who, _)| who), Some(&caller));
+	}
+
+	#[benchmark]
+	fn register_source() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegisterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let id = u16::from(Source::StackOverflow);
+		let name = vec![b'n'; T::MaxSourceNameLen::get() as usize];
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, id, name.clone());
+
+		// Verify
+		assert_eq!(RegisteredSources::<T>::get(id).unwrap().into_inner(), name);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn accept_item_transfer() {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		// Both accounts hold full vectors, bar the slot the item moves into
+		fill_items::<T>(&owner, T::MaxItemsPerAccount::get());
+		fill_items::<T>(&caller, T::MaxItemsPerAccount::get() - 1);
+		let item_id = Pallet::<T>::items_of(&owner)[0].id;
+		for i in 0..T::MaxTagsPerItem::get() {
+			assert_ok!(Pallet::<T>::add_tag(
+				RawOrigin::Signed(owner.clone()).into(),
+				item_id,
+				i.to_le_bytes().to_vec(),
+			));
+		}
+		add_flag::<T>(&account("flagger", 0, 0), &owner, item_id);
+		let entries = (0..T::MaxAccessLogEntries::get())
+			.map(|_| (owner.clone(), frame_system::Pallet::<T>::block_number()))
+			.collect::<Vec<_>>();
+		AccessLog::<T>::insert(&owner, item_id, BoundedVec::truncate_from(entries));
+		assert_ok!(Pallet::<T>::transfer_item_ownership(
+			RawOrigin::Signed(owner.clone()).into(),
+			item_id,
+			caller.clone(),
+		));
+
+		#[extrinsic_call]
+		accept_item_transfer(RawOrigin::Signed(caller.clone()), owner.clone(), item_id);
+
+		// Verify
+		assert!(Pallet::<T>::items_of(&caller).iter().any(|item| item.id == item_id));
+		assert_eq!(Flags::<T>::get(&caller, item_id).len(), 1);
+		let accesses = AccessLog::<T>::get(&caller, item_id);
+		assert_eq!(accesses.len() as u32, T::MaxAccessLogEntries::get());
+	}
+
+	#[benchmark]
+	fn cancel_item_transfer() {
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = setup_item::<T>(&caller);
+		assert_ok!(Pallet::<T>::transfer_item_ownership(
 			RawOrigin::Signed(caller.clone()).into(),
-			message_hash,
-			None,
+			item_id,
+			account("new_owner", 0, 0),
+		));
+
+		#[extrinsic_call]
+		cancel_item_transfer(RawOrigin::Signed(caller.clone()), item_id);
+
+		// Verify
+		assert!(!PendingTransfers::<T>::contains_key(&caller, item_id));
+	}
+
+	#[benchmark]
+	fn resign_consent() {
+		let message_hash = b"benchmark_consent".to_vec();
+		// ECDSA recovery is the most expensive of the supported schemes
+		let public = sp_io::crypto::ecdsa_generate(KeyTypeId(*b"shdw"), None);
+		let caller =
+			T::BenchmarkHelper::account_for_key(sp_io::hashing::blake2_256(public.as_ref()));
+		fund_for_consent::<T>(&caller);
+		// A transferred consent naming a delegate, so the delegate is signed over too
+		let consent_id = grant_consent_for::<T>(&caller, &message_hash);
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let consent = ConsentRecords::<T>::mutate(&caller, consent_id, |consent| {
+			let consent = consent.as_mut().unwrap();
+			consent.delegate = Some(delegate.clone());
+			consent.needs_resign = true;
+			consent.clone()
+		});
+		let valid_until = frame_system::Pallet::<T>::block_number();
+		let action = ConsentAuthorization::Resign {
+			receipt: consent.receipt,
+			scope: consent.scope,
+			delegate: consent.delegate,
+		};
+		let payload = Pallet::<T>::consent_payload(&caller, action, valid_until);
+		let signature = sp_io::crypto::ecdsa_sign(KeyTypeId(*b"shdw"), &public, &payload).unwrap();
+
+		#[extrinsic_call]
+		resign_consent(
+			RawOrigin::Signed(caller.clone()),
+			consent_id,
+			ConsentSignature {
+				valid_until,
+				scheme: crate::signature_scheme::ECDSA,
+				signature: signature.0.to_vec(),
+			},
+		);
+
+		// Verify
+		assert!(!ConsentRecords::<T>::get(&caller, consent_id).unwrap().needs_resign);
+	}
+
+	#[benchmark]
+	fn unregister_source() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegisterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let id = u16::from(Source::StackOverflow);
+		RegisteredSources::<T>::insert(
+			id,
+			BoundedVec::truncate_from(vec![b'n'; T::MaxSourceNameLen::get() as usize]),
 		);
 
 		#[extrinsic_call]
-		revoke_consent(RawOrigin::Signed(caller.clone()));
+		_(origin as T::RuntimeOrigin, id);
 
 		// Verify
-		assert!(ConsentRecords::<T>::get(&caller).is_none());
+		assert!(!RegisteredSources::<T>::contains_key(id));
+		Ok(())
 	}
 
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
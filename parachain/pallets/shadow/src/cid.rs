@@ -0,0 +1,94 @@
+//! Well-formedness checks for IPFS CIDs in their string form.
+//!
+//! Only the encoding is checked: a CID that passes may still point at content nobody hosts.
+
+use alloc::vec::Vec;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Multihash prefix of a sha2-256 digest, the only kind a CIDv0 can hold.
+const CID_V0_PREFIX: [u8; 2] = [0x12, 0x20];
+
+/// Whether `cid` is a CIDv0 (`Qm...`, 46 characters) or a CIDv1 in base32 (`b...`) or
+/// base58btc (`z...`) multibase.
+pub fn is_well_formed(cid: &[u8]) -> bool {
+	match cid.split_first() {
+		Some((b'Q', _)) => is_cid_v0(cid),
+		Some((b'b', rest)) => decode_base32(rest).is_some_and(|bytes| is_cid_v1(&bytes)),
+		Some((b'z', rest)) => decode_base58(rest).is_some_and(|bytes| is_cid_v1(&bytes)),
+		_ => false,
+	}
+}
+
+/// A base58btc sha2-256 multihash.
+fn is_cid_v0(cid: &[u8]) -> bool {
+	cid.len() == 46 &&
+		cid.starts_with(b"Qm") &&
+		decode_base58(cid)
+			.is_some_and(|bytes| bytes.len() == 34 && bytes.starts_with(&CID_V0_PREFIX))
+}
+
+/// Version 1, a content codec, then a multihash whose digest fills the rest exactly.
+fn is_cid_v1(bytes: &[u8]) -> bool {
+	let mut rest = bytes;
+	if read_varint(&mut rest) != Some(1) {
+		return false;
+	}
+	let (Some(_codec), Some(_hash_code), Some(digest_len)) =
+		(read_varint(&mut rest), read_varint(&mut rest), read_varint(&mut rest))
+	else {
+		return false;
+	};
+	digest_len > 0 && rest.len() as u64 == digest_len
+}
+
+/// Read an unsigned varint of at most 9 bytes from the front of `input`.
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+	let mut value = 0u64;
+	for (i, byte) in input.iter().enumerate().take(9) {
+		value |= u64::from(byte & 0x7f) << (7 * i);
+		if byte & 0x80 == 0 {
+			*input = &input[i + 1..];
+			return Some(value);
+		}
+	}
+	None
+}
+
+fn decode_base58(input: &[u8]) -> Option<Vec<u8>> {
+	// Little-endian while accumulating
+	let mut bytes = Vec::new();
+	for c in input {
+		let mut carry = BASE58_ALPHABET.iter().position(|a| a == c)? as u32;
+		for byte in bytes.iter_mut() {
+			carry += u32::from(*byte) * 58;
+			*byte = carry as u8;
+			carry >>= 8;
+		}
+		while carry > 0 {
+			bytes.push(carry as u8);
+			carry >>= 8;
+		}
+	}
+	// Each leading '1' encodes a leading zero byte
+	bytes.extend(input.iter().take_while(|c| **c == b'1').map(|_| 0));
+	bytes.reverse();
+	Some(bytes)
+}
+
+/// Decode unpadded, lowercase RFC 4648 base32.
+fn decode_base32(input: &[u8]) -> Option<Vec<u8>> {
+	let mut bytes = Vec::with_capacity(input.len() * 5 / 8);
+	let (mut buffer, mut bits) = (0u32, 0u32);
+	for c in input {
+		buffer = (buffer << 5) | BASE32_ALPHABET.iter().position(|a| a == c)? as u32;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			bytes.push((buffer >> bits) as u8);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+	Some(bytes)
+}
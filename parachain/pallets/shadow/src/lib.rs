@@ -1,6 +1,14 @@
 //! # Shadow Pallet
 //!
 //! A pallet for storing encrypted Web2 activity metadata on-chain.
+//!
+//! ## Weight refunds
+//!
+//! Calls charged for a worst case that depends on how many items they touch
+//! (`delete_shadow_item`, `delete_all_shadow_items` and `delete_items_by_source`) return
+//! `DispatchResultWithPostInfo` and refund the fee down to the weight of the items actually
+//! handled. Every other call, including `force_delete_item`, is charged a weight fixed by its
+//! arguments and returns `DispatchResult`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -15,19 +23,192 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod cid;
+pub mod migrations;
+pub mod runtime_api;
 pub mod weights;
 pub use weights::WeightInfo;
 
 // Import Vec from alloc for no_std compatibility
 extern crate alloc;
-use alloc::vec::Vec;
+use alloc::{
+	collections::{BTreeMap, BTreeSet},
+	vec::Vec,
+};
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// The Web2 platform a shadow item was collected from.
+///
+/// Encodes as a single byte equal to the `u8` source ids used before this type existed
+/// (0 = GitHub, 1 = Twitter).
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	serde::Serialize,
+	serde::Deserialize,
+)]
+pub enum Source {
+	#[codec(index = 0)]
+	GitHub,
+	#[codec(index = 1)]
+	Twitter,
+	#[codec(index = 2)]
+	GitLab,
+	#[codec(index = 3)]
+	Reddit,
+	#[codec(index = 4)]
+	StackOverflow,
+}
+
+impl TryFrom<u8> for Source {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Self::GitHub),
+			1 => Ok(Self::Twitter),
+			2 => Ok(Self::GitLab),
+			3 => Ok(Self::Reddit),
+			4 => Ok(Self::StackOverflow),
+			_ => Err(()),
+		}
+	}
+}
+
+impl TryFrom<u16> for Source {
+	type Error = ();
+
+	fn try_from(value: u16) -> Result<Self, Self::Error> {
+		u8::try_from(value).map_err(|_| ()).and_then(Self::try_from)
+	}
+}
+
+impl Source {
+	/// Every source, in id order.
+	pub const ALL: [Source; 5] =
+		[Self::GitHub, Self::Twitter, Self::GitLab, Self::Reddit, Self::StackOverflow];
+
+	/// The source's display name, as registered at genesis.
+	pub fn name(self) -> &'static [u8] {
+		match self {
+			Self::GitHub => b"GitHub",
+			Self::Twitter => b"Twitter",
+			Self::GitLab => b"GitLab",
+			Self::Reddit => b"Reddit",
+			Self::StackOverflow => b"StackOverflow",
+		}
+	}
+}
+
+impl From<Source> for u8 {
+	fn from(source: Source) -> Self {
+		source as u8
+	}
+}
+
+impl From<Source> for u16 {
+	/// The id the source is registered under in `RegisteredSources`.
+	fn from(source: Source) -> Self {
+		u8::from(source).into()
+	}
+}
+
+/// Per-source rules for which CIDs are acceptable.
+pub trait CidValidator {
+	/// Whether `cid` is a valid content identifier for `source`.
+	fn validate(source: Source, cid: &[u8]) -> bool;
+}
+
+/// Accepts every CID for every source.
+impl CidValidator for () {
+	fn validate(_source: Source, _cid: &[u8]) -> bool {
+		true
+	}
+}
+
+/// Per-source maximum CID lengths.
+pub trait CidLimitForSource {
+	/// The maximum CID length for `source`, or `None` to use `MaxCidLength`.
+	fn max_cid_length(source: Source) -> Option<u32>;
+}
+
+/// Uses `MaxCidLength` for every source.
+impl CidLimitForSource for () {
+	fn max_cid_length(_source: Source) -> Option<u32> {
+		None
+	}
+}
+
+/// Notified whenever an account loses a consent record.
+pub trait OnConsentRevoked<AccountId> {
+	/// Called once for each consent `who` loses: revoked, dropped by `opt_out` or account
+	/// reaping, or pruned after expiry.
+	fn on_consent_revoked(who: &AccountId);
+}
+
+/// Ignores revocations.
+impl<AccountId> OnConsentRevoked<AccountId> for () {
+	fn on_consent_revoked(_who: &AccountId) {}
+}
+
+/// Benchmark support for calls that check an account's signature.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId> {
+	/// An account whose `AccountPublicKey` is `public`: an sr25519 public key, or the
+	/// blake2-256 hash of a compressed ECDSA one.
+	fn account_for_key(public: [u8; 32]) -> AccountId;
+}
+
+/// Signature schemes accepted for consent signatures.
+pub mod signature_scheme {
+	/// sr25519 over the signed bytes.
+	pub const SR25519: u8 = 0;
+	/// ed25519 over the signed bytes.
+	pub const ED25519: u8 = 1;
+	/// Recoverable secp256k1 ECDSA over the blake2-256 hash of the signed bytes.
+	pub const ECDSA: u8 = 2;
+}
 
 #[frame::pallet]
 pub mod pallet {
-	use frame::prelude::*;
-	use super::{WeightInfo, Vec};
+	use codec::DecodeWithMemTracking;
+	use frame::{
+		prelude::*,
+		traits::{Currency, ReservableCurrency},
+	};
+	use polkadot_sdk::{
+		frame_support::{traits::OnKilledAccount, weights::WeightMeter},
+		sp_core::{ed25519, sr25519},
+		sp_io::{crypto, hashing::blake2_256},
+		sp_runtime::traits::{Convert, Saturating, Zero},
+	};
+	use super::{
+		signature_scheme, BTreeMap, BTreeSet, CidLimitForSource, CidValidator, OnConsentRevoked,
+		Source, WeightInfo, Vec,
+	};
+
+	/// Balance type of the configured currency.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 	
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(18);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// The pallet's configuration trait.
@@ -42,6 +223,19 @@ pub mod pallet {
 		/// Maximum number of shadow items per account
 		#[pallet::constant]
 		type MaxItemsPerAccount: Get<u32>;
+
+		/// Maximum number of items in one `submit_shadow_items_batch` call
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+
+		/// Maximum number of items an account may receive within one `RateLimitWindow`,
+		/// unless sent by a trusted submitter
+		#[pallet::constant]
+		type MaxSubmissionsPerWindow: Get<u32>;
+
+		/// Length in blocks of the window submissions are counted over
+		#[pallet::constant]
+		type RateLimitWindow: Get<BlockNumberFor<Self>>;
 		
 		/// Maximum length for IPFS CID
 		#[pallet::constant]
@@ -51,42 +245,518 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxKeyLength: Get<u32>;
 
-		/// Maximum length for source identifier
-		#[pallet::constant]
-		type MaxSourceLength: Get<u32>;
-		
-		/// Maximum length for metadata
+		/// Default maximum length for metadata, used when an account has no override
 		#[pallet::constant]
 		type MaxMetadataLength: Get<u32>;
+
+		/// Absolute maximum length for metadata, bounding any per-account override
+		#[pallet::constant]
+		type MaxMetadataLengthHard: Get<u32>;
 		
 		/// Maximum length for message hash
 		#[pallet::constant]
 		type MaxMessageHashLength: Get<u32>;
+
+		/// Origin allowed to perform administrative actions such as setting metadata limits.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin allowed to register sources items may be submitted from
+		type RegisterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum length of a registered source's name
+		#[pallet::constant]
+		type MaxSourceNameLen: Get<u32>;
+
+		/// Maximum number of blocks a session key authorization may last
+		#[pallet::constant]
+		type MaxSessionDuration: Get<BlockNumberFor<Self>>;
+
+		/// Maximum number of accounts whose consent check is cached within a block
+		#[pallet::constant]
+		type MaxConsentCacheEntries: Get<u32>;
+
+		/// Maximum number of long-lived agents an account may authorize
+		#[pallet::constant]
+		type MaxAgents: Get<u32>;
+
+		/// Currency used for balance checks and deposits.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Minimum free balance an account must hold to grant consent
+		#[pallet::constant]
+		type MinBalanceForConsent: Get<BalanceOf<Self>>;
+
+		/// Per-source CID validation rules. Use `()` to accept any CID.
+		type CidValidator: CidValidator;
+
+		/// Per-source CID length limits, capped by `MaxCidLength`. Use `()` to apply
+		/// `MaxCidLength` to every source.
+		type CidLimitForSource: CidLimitForSource;
+
+		/// Whether every CID must be a well-formed IPFS CIDv0 or CIDv1. Disable on chains
+		/// that store references other than IPFS CIDs.
+		#[pallet::constant]
+		type StrictCidValidation: Get<bool>;
+
+		/// Maximum number of recipients a single item may be shared with
+		#[pallet::constant]
+		type MaxSharesPerItem: Get<u32>;
+
+		/// Maximum number of accesses kept in each item's `AccessLog`; older ones are dropped
+		#[pallet::constant]
+		type MaxAccessLogEntries: Get<u32>;
+
+		/// Maximum number of sources a consent scope may list
+		#[pallet::constant]
+		type MaxScopeSources: Get<u32>;
+
+		/// Maximum number of tags on a shadow item
+		#[pallet::constant]
+		type MaxTagsPerItem: Get<u32>;
+
+		/// Maximum length of a single tag
+		#[pallet::constant]
+		type MaxTagLength: Get<u32>;
+
+		/// Maximum number of an account's items indexed under a single tag
+		#[pallet::constant]
+		type MaxItemsPerTag: Get<u32>;
+
+		/// Whether same-block item submissions by one account are summarised into a single
+		/// `ItemsStored` event in `on_finalize` instead of one `ShadowItemStored` each
+		#[pallet::constant]
+		type AggregateEvents: Get<bool>;
+
+		/// Maximum number of accounts whose submissions are summarised within a block
+		#[pallet::constant]
+		type MaxAggregatedAccounts: Get<u32>;
+
+		/// Whether `grant_consent` only accepts message hashes registered in
+		/// `ConsentTemplates`
+		#[pallet::constant]
+		type EnforceTemplates: Get<bool>;
+
+		/// Maximum number of accounts that may hold consent for the same message hash
+		#[pallet::constant]
+		type MaxAccountsPerMessage: Get<u32>;
+
+		/// Whether consent is still valid at its `expires_at` block. If `false`, consent
+		/// expires at that block rather than after it
+		#[pallet::constant]
+		type ConsentExpiryInclusive: Get<bool>;
+
+		/// Origin of the trusted oracles that report unretrievable CIDs, yielding the
+		/// reporting account.
+		type ReporterOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+		/// Number of distinct reporters needed before a dead-CID item is removed
+		#[pallet::constant]
+		type DeadCidThreshold: Get<u32>;
+
+		/// Maximum length for encrypted metadata
+		#[pallet::constant]
+		type MaxEncMetaLength: Get<u32>;
+
+		/// Maximum number of consent history entries kept per account; the oldest entry is
+		/// dropped once the log is full
+		#[pallet::constant]
+		type MaxConsentHistory: Get<u32>;
+
+		/// Deposit reserved from an account flagging an item, slashed if the flag is
+		/// judged frivolous
+		#[pallet::constant]
+		type FlagDeposit: Get<BalanceOf<Self>>;
+
+		/// Number of flags at which an item is raised for review
+		#[pallet::constant]
+		type FlagReviewThreshold: Get<u32>;
+
+		/// Maximum number of flags an item may accumulate before they are resolved
+		#[pallet::constant]
+		type MaxFlagsPerItem: Get<u32>;
+
+		/// Whether `grant_consent` stores `T::Hashing` of the supplied message hash rather
+		/// than the bytes as sent, so raw messages and client-side hashes are stored alike
+		#[pallet::constant]
+		type NormalizeMessageHash: Get<bool>;
+
+		/// How submissions treat a consent record that is present but expired
+		#[pallet::constant]
+		type ExpiredConsentPolicy: Get<ExpiredConsentPolicy>;
+
+		/// The 32 bytes identifying an account's signing key: the sr25519 or ed25519 public
+		/// key, or the blake2-256 hash of the compressed ECDSA public key
+		type AccountPublicKey: Convert<Self::AccountId, [u8; 32]>;
+
+		/// Maximum combined length of an item's CID, encrypted key and metadata. Set to the
+		/// sum of the individual maxima to leave only the per-field limits in effect
+		#[pallet::constant]
+		type MaxItemTotalBytes: Get<u32>;
+
+		/// Maximum total encoded size of an account's stored items, independent of their
+		/// number
+		#[pallet::constant]
+		type MaxBytesPerAccount: Get<u32>;
+
+		/// The compliance account whose key must co-sign `grant_consent_cosigned`, or `None`
+		/// if co-signed grants are not available
+		type ComplianceKey: Get<Option<Self::AccountId>>;
+
+		/// Whether submitting requires a consent co-signed by the `ComplianceKey` account
+		#[pallet::constant]
+		type RequireCosignedConsent: Get<bool>;
+
+		/// Whether an item's new owner must hold a valid consent covering the item's source
+		/// for `accept_item_transfer` to succeed
+		#[pallet::constant]
+		type RequireRecipientConsent: Get<bool>;
+
+		/// Maximum number of consent records examined for expiry at the start of each block
+		#[pallet::constant]
+		type MaxConsentPrunedPerBlock: Get<u32>;
+
+		/// Maximum number of accounts whose items are examined for expiry at the start of
+		/// each block
+		#[pallet::constant]
+		type MaxAccountsExpiredPerBlock: Get<u32>;
+
+		/// Maximum number of consents an account can hold at once
+		#[pallet::constant]
+		type MaxConsentsPerAccount: Get<u32>;
+
+		/// Whether `opt_out` also deletes the account's shadow items
+		#[pallet::constant]
+		type ClearItemsOnOptOut: Get<bool>;
+
+		/// Deposit reserved from the owner for each stored shadow item and returned when the
+		/// item is removed
+		#[pallet::constant]
+		type DepositPerItem: Get<BalanceOf<Self>>;
+
+		/// Hook run for every consent an account loses. Use `()` to ignore revocations.
+		type OnConsentRevoked: OnConsentRevoked<Self::AccountId>;
+
+		/// Maps benchmark-generated keys to accounts
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: crate::BenchmarkHelper<Self::AccountId>;
 	}
 
 	/// Type aliases for bounded vectors
 	pub type BoundedCid<T> = BoundedVec<u8, <T as Config>::MaxCidLength>;
 	pub type BoundedKey<T> = BoundedVec<u8, <T as Config>::MaxKeyLength>;
-	pub type BoundedSource<T> = BoundedVec<u8, <T as Config>::MaxSourceLength>;
-	pub type BoundedMetadata<T> = BoundedVec<u8, <T as Config>::MaxMetadataLength>;
+	pub type BoundedMetadata<T> = BoundedVec<u8, <T as Config>::MaxMetadataLengthHard>;
+	pub type BoundedEncryptedMetadata<T> = BoundedVec<u8, <T as Config>::MaxEncMetaLength>;
 	pub type BoundedMessageHash<T> = BoundedVec<u8, <T as Config>::MaxMessageHashLength>;
+	pub type BoundedTag<T> = BoundedVec<u8, <T as Config>::MaxTagLength>;
+	pub type ItemTags<T> = BoundedVec<BoundedTag<T>, <T as Config>::MaxTagsPerItem>;
+	pub type ConsentScope<T> = BoundedBTreeSet<Source, <T as Config>::MaxScopeSources>;
+
+	/// An item submitted in a batch, as `(cid, encrypted_key, source, metadata)`.
+	pub type BatchItem = (Vec<u8>, Vec<u8>, Source, Vec<u8>);
+	/// The result of `items_diff`, as `(added, removed)`.
+	pub type ItemsDiff<T> = (Vec<ShadowItem<T>>, Vec<<T as frame_system::Config>::Hash>);
+	/// A consent seeded at genesis, as `(account, granted_at, expires_at, message_hash)`.
+	pub type GenesisConsent<T> = (
+		<T as frame_system::Config>::AccountId,
+		BlockNumberFor<T>,
+		Option<BlockNumberFor<T>>,
+		Vec<u8>,
+	);
+	/// An item seeded at genesis, as `(owner, cid, encrypted_key, source, metadata)`.
+	pub type GenesisItem<T> =
+		(<T as frame_system::Config>::AccountId, Vec<u8>, Vec<u8>, Source, Vec<u8>);
 
 	/// A shadow item stored on-chain.
 	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 	#[scale_info(skip_type_params(T))]
 	pub struct ShadowItem<T: Config> {
 		/// Unique identifier for the item.
-		pub id: [u8; 32],  // Fixed size for hash output
+		pub id: T::Hash,
 		/// IPFS CID where encrypted content is stored.
 		pub cid: BoundedCid<T>,
 		/// Encrypted symmetric key (encrypted with user's public key).
 		pub encrypted_key: BoundedKey<T>,
 		/// Timestamp when the item was stored.
 		pub timestamp: u64,
-		/// Source of the content (e.g., "GitHub", "Twitter").
-		pub source: BoundedSource<T>,
+		/// Platform the content was collected from.
+		pub source: Source,
 		/// Additional metadata.
 		pub metadata: BoundedMetadata<T>,
+		/// Whether the item is exempt from automatic expiry.
+		pub pinned: bool,
+		/// Optional metadata encrypted with the item's symmetric key.
+		pub encrypted_metadata: Option<BoundedEncryptedMetadata<T>>,
+		/// Labels the owner attached to the item, in the order they were added.
+		pub tags: ItemTags<T>,
+		/// Deposit reserved from the owner while the item is stored.
+		pub deposit: BalanceOf<T>,
+		/// Last block the item is kept for, `None` if it never expires.
+		pub expires_at: Option<BlockNumberFor<T>>,
+	}
+
+	impl<T: Config> ShadowItem<T> {
+		/// The item's id as the `T::Hash` that calls such as `delete_shadow_item` take.
+		///
+		/// Ids are stored as `T::Hash`, so this cannot fail; prefer it over converting
+		/// `id` by hand.
+		pub fn hash_id(&self) -> T::Hash {
+			self.id
+		}
+	}
+
+	/// Structured item metadata, stored SCALE-encoded in [`ShadowItem::metadata`] by
+	/// `submit_structured_item`.
+	#[derive(
+		Encode,
+		Decode,
+		DecodeWithMemTracking,
+		CloneNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ItemMetadata<T: Config> {
+		/// Human-readable title of the content.
+		pub title: BoundedMetadata<T>,
+		/// Labels describing the content.
+		pub tags: ItemTags<T>,
+		/// When the content was created on its source platform, in milliseconds since the
+		/// Unix epoch.
+		pub created_at: u64,
+	}
+
+	/// A shadow item in the layout stored as [`VersionedShadowItem::V1`], before tags.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ShadowItemV1<T: Config> {
+		pub id: T::Hash,
+		pub cid: BoundedCid<T>,
+		pub encrypted_key: BoundedKey<T>,
+		pub timestamp: u64,
+		pub source: Source,
+		pub metadata: BoundedMetadata<T>,
+		pub pinned: bool,
+		pub encrypted_metadata: Option<BoundedEncryptedMetadata<T>>,
+	}
+
+	/// A shadow item in the layout stored as [`VersionedShadowItem::V2`], before deposits.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ShadowItemV2<T: Config> {
+		pub id: T::Hash,
+		pub cid: BoundedCid<T>,
+		pub encrypted_key: BoundedKey<T>,
+		pub timestamp: u64,
+		pub source: Source,
+		pub metadata: BoundedMetadata<T>,
+		pub pinned: bool,
+		pub encrypted_metadata: Option<BoundedEncryptedMetadata<T>>,
+		pub tags: ItemTags<T>,
+	}
+
+	/// A shadow item in the layout stored as [`VersionedShadowItem::V3`], before item expiry.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ShadowItemV3<T: Config> {
+		pub id: T::Hash,
+		pub cid: BoundedCid<T>,
+		pub encrypted_key: BoundedKey<T>,
+		pub timestamp: u64,
+		pub source: Source,
+		pub metadata: BoundedMetadata<T>,
+		pub pinned: bool,
+		pub encrypted_metadata: Option<BoundedEncryptedMetadata<T>>,
+		pub tags: ItemTags<T>,
+		pub deposit: BalanceOf<T>,
+	}
+
+	/// A shadow item as stored, tagged with the layout it was written in.
+	///
+	/// Items written in an older layout keep decoding after `ShadowItem` changes, so a layout
+	/// change no longer needs a storage migration. To change `ShadowItem`, freeze its current
+	/// shape as `ShadowItemVn` for the latest variant, add a variant holding the new
+	/// `ShadowItem`, and translate the old one in [`Self::current`]. Variants are append-only.
+	///
+	/// A change that keeps the encoding of every valid item, such as narrowing a field to an
+	/// enum with matching indices, may instead keep the variant and drop the items that no
+	/// longer decode with a migration (see `migrations::v8`).
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub enum VersionedShadowItem<T: Config> {
+		/// The layout introduced with storage version 6.
+		#[codec(index = 0)]
+		V1(ShadowItemV1<T>),
+		/// `V1` with the item's tags.
+		#[codec(index = 1)]
+		V2(ShadowItemV2<T>),
+		/// `V2` with the deposit reserved for the item.
+		#[codec(index = 2)]
+		V3(ShadowItemV3<T>),
+		/// `V3` with the item's expiry.
+		#[codec(index = 3)]
+		V4(ShadowItem<T>),
+	}
+
+	impl<T: Config> VersionedShadowItem<T> {
+		/// The item in the latest `ShadowItem` layout.
+		///
+		/// Items stored before deposits were introduced hold no deposit, and items stored
+		/// before item expiry never expire.
+		pub fn current(self) -> ShadowItem<T> {
+			match self {
+				Self::V1(item) => ShadowItem {
+					id: item.id,
+					cid: item.cid,
+					encrypted_key: item.encrypted_key,
+					timestamp: item.timestamp,
+					source: item.source,
+					metadata: item.metadata,
+					pinned: item.pinned,
+					encrypted_metadata: item.encrypted_metadata,
+					tags: Default::default(),
+					deposit: Zero::zero(),
+					expires_at: None,
+				},
+				Self::V2(item) => ShadowItem {
+					id: item.id,
+					cid: item.cid,
+					encrypted_key: item.encrypted_key,
+					timestamp: item.timestamp,
+					source: item.source,
+					metadata: item.metadata,
+					pinned: item.pinned,
+					encrypted_metadata: item.encrypted_metadata,
+					tags: item.tags,
+					deposit: Zero::zero(),
+					expires_at: None,
+				},
+				Self::V3(item) => ShadowItem {
+					id: item.id,
+					cid: item.cid,
+					encrypted_key: item.encrypted_key,
+					timestamp: item.timestamp,
+					source: item.source,
+					metadata: item.metadata,
+					pinned: item.pinned,
+					encrypted_metadata: item.encrypted_metadata,
+					tags: item.tags,
+					deposit: item.deposit,
+					expires_at: None,
+				},
+				Self::V4(item) => item,
+			}
+		}
+	}
+
+	impl<T: Config> From<ShadowItem<T>> for VersionedShadowItem<T> {
+		fn from(item: ShadowItem<T>) -> Self {
+			Self::V4(item)
+		}
+	}
+
+	/// Why a shadow item was removed.
+	#[derive(
+		Encode,
+		Decode,
+		DecodeWithMemTracking,
+		Clone,
+		Copy,
+		PartialEq,
+		Eq,
+		RuntimeDebug,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	pub enum RemovalReason {
+		/// The owner deleted the item.
+		UserDeleted,
+		/// An admin deleted the item.
+		ForceDeleted,
+		/// The item outlived its retention period.
+		Expired,
+		/// Oracles reported the item's CID as unretrievable.
+		DeadCid,
+		/// The owner cleared the item along with the rest of their items.
+		Cleared,
+		/// The item moved to another account.
+		Transferred,
+		/// A storage migration found the item's source did not name a known platform.
+		UnknownSource,
+	}
+
+	/// Why a consent was revoked.
+	#[derive(
+		Encode,
+		Decode,
+		DecodeWithMemTracking,
+		Clone,
+		Copy,
+		PartialEq,
+		Eq,
+		RuntimeDebug,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	pub enum RevocationReason {
+		/// The account revoked the consent.
+		UserRevoked,
+		/// The account opted out of the pallet.
+		OptedOut,
+		/// The account was reaped.
+		AccountReaped,
+		/// Every source the consent was scoped to is unknown.
+		UnknownSource,
+	}
+
+	/// An account's signature authorizing a consent grant.
+	#[derive(
+		Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo,
+	)]
+	pub struct ConsentSignature<BlockNumber> {
+		/// The last block the signature may be used in.
+		pub valid_until: BlockNumber,
+		/// The [`signature_scheme`] the signature was made with.
+		pub scheme: u8,
+		/// The signature over [`Pallet::consent_payload`].
+		pub signature: Vec<u8>,
+	}
+
+	/// Prefixed to every encoded [`ConsentPayload`] before signing, so that a consent
+	/// signature is never valid as a signature over anything else.
+	pub const CONSENT_SIGNING_CONTEXT: &[u8] = b"shadow/consent";
+
+	/// What a consent signature authorizes.
+	#[derive(Encode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub enum ConsentAuthorization<T: Config> {
+		/// A grant through `grant_consent` or `submit_and_grant`, with its arguments.
+		Grant {
+			message_hash: Vec<u8>,
+			duration: Option<BlockNumberFor<T>>,
+			scope: ConsentScope<T>,
+			delegate: Option<T::AccountId>,
+		},
+		/// The `ComplianceKey` account co-signing a `grant_consent_cosigned` grant of
+		/// `message_hash`.
+		Cosign { message_hash: Vec<u8> },
+		/// Taking on, through `resign_consent`, the consent with `receipt` moved by
+		/// `transfer_consent`, as scoped and delegated when it was moved.
+		Resign { receipt: T::Hash, scope: ConsentScope<T>, delegate: Option<T::AccountId> },
+	}
+
+	/// The payload a consent signature covers; see [`Pallet::consent_payload`].
+	#[derive(Encode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct ConsentPayload<T: Config> {
+		/// The account the consent is recorded for.
+		pub who: T::AccountId,
+		/// What the signature authorizes.
+		pub action: ConsentAuthorization<T>,
+		/// The last block the signature may be used in.
+		pub valid_until: BlockNumberFor<T>,
+		/// `who`'s `ConsentNonce` when the signature is used.
+		pub nonce: u32,
 	}
 
 	/// A consent record stored on-chain.
@@ -99,219 +769,3885 @@ pub mod pallet {
 		pub expires_at: Option<BlockNumber>,
 		/// Hash of the consent message.
 		pub message_hash: BoundedMessageHash<T>,
+		/// Sources the consent covers; empty means all sources.
+		pub scope: ConsentScope<T>,
+		/// Whether the consent was transferred and must be re-granted before use.
+		pub needs_resign: bool,
+		/// The [`signature_scheme`] the grant was signed with, `None` if unsigned.
+		pub signature_scheme: Option<u8>,
+		/// Whether the `ComplianceKey` account co-signed the grant.
+		pub cosigned: bool,
+		/// Identifies this grant; see [`Pallet::consent_receipt`].
+		pub receipt: T::Hash,
+		/// The account allowed to submit for the consenting account through
+		/// `submit_shadow_item_for`, if any.
+		pub delegate: Option<T::AccountId>,
+	}
+
+	/// How an expired consent record is handled when an account submits.
+	#[derive(
+		Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub enum ExpiredConsentPolicy {
+		/// Fail with `ConsentExpired`; the account must grant consent again explicitly.
+		#[default]
+		Reject,
+		/// Treat the record as absent, failing with `NoConsent` so that `submit_and_grant`
+		/// re-grants it.
+		TreatAsNone,
+	}
+
+	/// A change to an account's consent.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum ConsentAction {
+		/// Consent was granted, or re-granted over an existing record.
+		Granted,
+		/// Consent was revoked.
+		Revoked,
+		/// An unexpired consent's expiry was extended.
+		Renewed,
+	}
+
+	/// An entry in an account's consent history log.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ConsentHistoryEntry<T: Config, BlockNumber> {
+		/// What happened.
+		pub action: ConsentAction,
+		/// Block at which it happened.
+		pub at: BlockNumber,
+		/// Hash of the consent message the action applied to.
+		pub message_hash: BoundedMessageHash<T>,
+		/// Expiry of the consent after the action, `None` if permanent or revoked.
+		pub expires_at: Option<BlockNumber>,
+	}
+
+	/// A flag raised against an item.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct FlagRecord<AccountId, Balance> {
+		/// The account that raised the flag.
+		pub flagger: AccountId,
+		/// Caller-defined reason code.
+		pub reason: u8,
+		/// The deposit reserved from `flagger`.
+		pub deposit: Balance,
+	}
+
+	/// An account's full consent trail, for compliance tooling.
+	///
+	/// Assembled on demand by [`Pallet::consent_audit`]; it is not stored and is only meant to
+	/// be fetched off-chain through the runtime API.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ConsentAudit<T: Config> {
+		/// The account's current consent records by id.
+		pub records: Vec<(ConsentId, ConsentRecord<T, BlockNumberFor<T>>)>,
+		/// Past grants and revocations, oldest first.
+		pub history: Vec<ConsentHistoryEntry<T, BlockNumberFor<T>>>,
+	}
+
+	/// Everything a client needs to know before submitting an item.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct SubmitReadiness<BlockNumber> {
+		/// Whether the account currently holds an unexpired consent.
+		pub consent_valid: bool,
+		/// Blocks left until the last of its valid consents expires, `None` if one is
+		/// permanent or none is valid.
+		pub consent_remaining: Option<BlockNumber>,
+		/// How many more items the account can store.
+		pub slots_remaining: u32,
+	}
+
+	/// The state of an account's consent, for display.
+	///
+	/// Only expiry is considered; a consent awaiting re-signing or a cosignature still
+	/// counts as `Permanent` or `Active`.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub enum ConsentStatus<BlockNumber> {
+		/// The account holds no consent record.
+		None,
+		/// A consent that never expires.
+		Permanent { granted_at: BlockNumber },
+		/// The unexpired consent lasting longest, `remaining` blocks from now.
+		Active { granted_at: BlockNumber, expires_at: BlockNumber, remaining: BlockNumber },
+		/// Every consent has expired, the last of them at `expired_at`.
+		Expired { expired_at: BlockNumber },
+	}
+
+	/// Running submission counters for an account.
+	#[derive(
+		Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct Stats {
+		/// Items ever stored, including those since deleted.
+		pub lifetime_submitted: u64,
+		/// Items ever deleted, by the owner or otherwise.
+		pub lifetime_deleted: u64,
+		/// Items currently stored.
+		pub current: u32,
 	}
 
 	/// Storage map for shadow items by account.
+	///
+	/// Deleting an item moves the account's last item into its place, so items are not kept
+	/// in submission order.
 	#[pallet::storage]
 	pub type ShadowItems<T: Config> = StorageMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
-		BoundedVec<ShadowItem<T>, T::MaxItemsPerAccount>,
+		BoundedVec<VersionedShadowItem<T>, T::MaxItemsPerAccount>,
 		ValueQuery,
 	>;
 
-	/// Storage map for consent records by account.
+	/// Identifies one of an account's consents, assigned in grant order.
+	pub type ConsentId = u32;
+
+	/// Consent records by account and consent id.
+	///
+	/// An account may hold up to `MaxConsentsPerAccount` consents at once, e.g. one per
+	/// backend; any one of them that is valid authorizes a submission.
 	#[pallet::storage]
-	pub type ConsentRecords<T: Config> = StorageMap<
+	pub type ConsentRecords<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
+		Twox64Concat,
+		ConsentId,
 		ConsentRecord<T, BlockNumberFor<T>>,
 		OptionQuery,
 	>;
 
-	/// Events emitted by the pallet.
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// A shadow item was stored. [who, item_id, cid]
-		ShadowItemStored { who: T::AccountId, item_id: T::Hash, cid: Vec<u8> },
-		/// A shadow item was deleted. [who, item_id]
-		ShadowItemDeleted { who: T::AccountId, item_id: T::Hash },
-		/// Consent was granted. [who, message_hash]
-		ConsentGranted { who: T::AccountId, message_hash: Vec<u8> },
-		/// Consent was revoked. [who]
-		ConsentRevoked { who: T::AccountId },
-	}
+	/// The id the next consent granted by each account receives.
+	#[pallet::storage]
+	pub type NextConsentId<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ConsentId, ValueQuery>;
 
-	/// Errors that can occur in the pallet.
-	#[pallet::error]
-	pub enum Error<T> {
-		/// The CID is too long.
-		CidTooLong,
-		/// The encrypted key is too long.
-		KeyTooLong,
-		/// The source is too long.
-		SourceTooLong,
-		/// The metadata is too long.
-		MetadataTooLong,
-		/// The account has too many items.
-		TooManyItems,
-		/// Item not found.
-		ItemNotFound,
-		/// Invalid source value.
-		InvalidSource,
-		/// No consent record found.
-		NoConsent,
-		/// Consent has expired.
-		ConsentExpired,
-	}
+	/// How many consent signatures each account has had accepted.
+	///
+	/// Signed into the next [`Pallet::consent_payload`], so no signature is accepted twice.
+	#[pallet::storage]
+	pub type ConsentNonce<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	/// Per-account metadata length overrides, e.g. for premium accounts.
+	///
+	/// Removed by `prune_empty_accounts` once the account holds no items.
+	#[pallet::storage]
+	pub type AccountMetadataLimit<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, OptionQuery>;
 
-	/// Dispatchable calls that can be made to the pallet.
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Submit a new shadow item to the chain.
-		///
-		/// - `cid`: The IPFS CID where encrypted content is stored.
-		/// - `encrypted_key`: The encrypted symmetric key.
-		/// - `source`: The source of the content (e.g., "GitHub", "Twitter").
-		/// - `metadata`: Additional metadata about the item.
-		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::submit_shadow_item())]
-		pub fn submit_shadow_item(
-			origin: OriginFor<T>,
-			cid: Vec<u8>,
-			encrypted_key: Vec<u8>,
-			source: Vec<u8>,
-			metadata: Vec<u8>,
-		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+	/// Accounts already found in the current block to hold a valid consent covering every
+	/// source.
+	///
+	/// Whitelisted and cleared in `on_finalize`, so once an account is cached its further
+	/// consent checks in the block read no trie node: N same-account submissions perform one
+	/// `ConsentRecords` read instead of N. Submission weights still charge the first read.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type ConsentCheckCache<T: Config> =
+		StorageValue<_, BoundedBTreeSet<T::AccountId, T::MaxConsentCacheEntries>, ValueQuery>;
 
-			// Check consent
-			Self::ensure_valid_consent(&who)?;
+	/// Items stored per account in the current block, pending their `ItemsStored` summary.
+	///
+	/// Only used when `AggregateEvents` is set. Whitelisted and drained in `on_finalize`.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type PendingItemsStored<T: Config> =
+		StorageValue<_, BoundedBTreeMap<T::AccountId, u32, T::MaxAggregatedAccounts>, ValueQuery>;
 
-			// Validate inputs - convert lengths to u32 for comparison
-			ensure!(cid.len() as u32 <= T::MaxCidLength::get(), Error::<T>::CidTooLong);
-			ensure!(encrypted_key.len() as u32 <= T::MaxKeyLength::get(), Error::<T>::KeyTooLong);
-			ensure!(source.len() as u32 <= T::MaxSourceLength::get(), Error::<T>::SourceTooLong);
-			ensure!(metadata.len() as u32 <= T::MaxMetadataLength::get(), Error::<T>::MetadataTooLong);
+	/// Items submitted in the current block.
+	///
+	/// Whitelisted and folded into `TotalSubmissions` in `on_finalize`.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type SubmissionsThisBlock<T: Config> = StorageValue<_, u32, ValueQuery>;
 
-			// Generate unique ID for this item
-			let nonce = frame_system::Pallet::<T>::account_nonce(&who);
-			let item_id = T::Hashing::hash_of(&(&who, &nonce, &cid));
-			
-			// Convert vecs to bounded vecs
-			let bounded_cid = BoundedCid::<T>::try_from(cid.clone())
-				.map_err(|_| Error::<T>::CidTooLong)?;
-			let bounded_key = BoundedKey::<T>::try_from(encrypted_key)
-				.map_err(|_| Error::<T>::KeyTooLong)?;
-			let bounded_source = BoundedSource::<T>::try_from(source)
-				.map_err(|_| Error::<T>::SourceTooLong)?;
-			let bounded_metadata = BoundedMetadata::<T>::try_from(metadata)
-				.map_err(|_| Error::<T>::MetadataTooLong)?;
+	/// Items submitted over the chain's lifetime, up to the end of the previous block.
+	#[pallet::storage]
+	pub type TotalSubmissions<T: Config> = StorageValue<_, u64, ValueQuery>;
 
-			// Create the shadow item
-			let item = ShadowItem {
-				id: item_id.as_ref().try_into().map_err(|_| Error::<T>::InvalidSource)?,
-				cid: bounded_cid,
-				encrypted_key: bounded_key,
-				timestamp: frame_system::Pallet::<T>::block_number().saturated_into::<u64>(),
-				source: bounded_source,
-				metadata: bounded_metadata,
-			};
+	/// Long-lived agents allowed to submit on behalf of an account.
+	#[pallet::storage]
+	pub type AuthorizedAgents<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxAgents>,
+		ValueQuery,
+	>;
 
-			// Store the item
-			<ShadowItems<T>>::try_mutate(&who, |items| -> DispatchResult {
-				items.try_push(item).map_err(|_| Error::<T>::TooManyItems)?;
-				Ok(())
-			})?;
+	/// Short-lived session keys allowed to submit on behalf of an account, by expiry block.
+	#[pallet::storage]
+	pub type Sessions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
 
-			// Emit event
-			Self::deposit_event(Event::ShadowItemStored { who, item_id, cid: cid.clone() });
+	/// Item keys re-wrapped for a recipient, by recipient and item id. Removed along with
+	/// the item.
+	#[pallet::storage]
+	pub type SharedKeys<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		BoundedKey<T>,
+		OptionQuery,
+	>;
 
-			Ok(())
+	/// The most recent accesses to each item, oldest first, by owner and item id.
+	#[pallet::storage]
+	pub type AccessLog<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		BoundedVec<(T::AccountId, BlockNumberFor<T>), T::MaxAccessLogEntries>,
+		ValueQuery,
+	>;
+
+	/// Recipients each item has been shared with; the reverse index of `SharedKeys`.
+	#[pallet::storage]
+	pub type ShareRecipients<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::Hash,
+		BoundedVec<T::AccountId, T::MaxSharesPerItem>,
+		ValueQuery,
+	>;
+
+	#[pallet::type_value]
+	pub fn DefaultEnabled() -> bool {
+		true
+	}
+
+	/// Whether the pallet accepts mutating calls. Only set at genesis.
+	///
+	/// Whitelisted as every call reads it.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type Enabled<T: Config> = StorageValue<_, bool, ValueQuery, DefaultEnabled>;
+
+	/// Accounts whose current consent was granted with each message hash.
+	#[pallet::storage]
+	pub type ConsentByMessage<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BoundedMessageHash<T>,
+		BoundedVec<T::AccountId, T::MaxAccountsPerMessage>,
+		ValueQuery,
+	>;
+
+	/// Distinct oracles that reported an item's CID as unretrievable.
+	#[pallet::storage]
+	pub type DeadCidReports<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::Hash,
+		BoundedVec<T::AccountId, T::DeadCidThreshold>,
+		ValueQuery,
+	>;
+
+	/// Names of the sources items may be submitted from, by source id.
+	///
+	/// Ids are the encoded index of the matching [`Source`]; only ids with a variant can be
+	/// registered. Items are keyed by [`Source`], so this is not an open registry: it names
+	/// the platforms the runtime knows and enables or disables them, while a new platform
+	/// still needs a runtime upgrade adding its variant.
+	#[pallet::storage]
+	pub type RegisteredSources<T: Config> =
+		StorageMap<_, Blake2_128Concat, u16, BoundedVec<u8, T::MaxSourceNameLen>, OptionQuery>;
+
+	/// Default metadata stored for a source's items when they are submitted without any.
+	#[pallet::storage]
+	pub type SourceMetaTemplate<T: Config> =
+		StorageMap<_, Blake2_128Concat, Source, BoundedMetadata<T>, OptionQuery>;
+
+	/// Bounded log of each account's consent grants and revocations, oldest first.
+	#[pallet::storage]
+	pub type ConsentHistory<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<ConsentHistoryEntry<T, BlockNumberFor<T>>, T::MaxConsentHistory>,
+		ValueQuery,
+	>;
+
+	/// Trusted accounts, such as our own backend, exempt from submission throttling.
+	#[pallet::storage]
+	pub type TrustedSubmitters<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Start of each account's current submission window and the items submitted in it.
+	#[pallet::storage]
+	pub type SubmissionRate<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (BlockNumberFor<T>, u32), ValueQuery>;
+
+	/// Outstanding flags raised against each item, keyed by owner and item id. Removing the
+	/// item returns their deposits.
+	#[pallet::storage]
+	pub type Flags<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Identity,
+		T::Hash,
+		BoundedVec<FlagRecord<T::AccountId, BalanceOf<T>>, T::MaxFlagsPerItem>,
+		ValueQuery,
+	>;
+
+	/// Item slots each account has held back for its own submissions.
+	///
+	/// Agents and sessions submitting on the account's behalf cannot fill these slots.
+	#[pallet::storage]
+	pub type ReservedSlots<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Block at which each account last revoked its consent, cleared by a fresh grant.
+	#[pallet::storage]
+	pub type RevokedConsents<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+	/// The last account examined by `prune_empty_accounts`; the next call resumes after it.
+	#[pallet::storage]
+	pub type PruneCursor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// The last consent record examined for expiry; the next block resumes after it.
+	#[pallet::storage]
+	pub type ConsentPruneCursor<T: Config> =
+		StorageValue<_, (T::AccountId, ConsentId), OptionQuery>;
+
+	/// The last account whose items were examined for expiry; the next block resumes after it.
+	#[pallet::storage]
+	pub type ItemExpiryCursor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// Message hashes of the consent templates clients are expected to sign.
+	#[pallet::storage]
+	pub type ConsentTemplates<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedMessageHash<T>, (), OptionQuery>;
+
+	/// Number of items each account has stored, used to derive unique item ids.
+	///
+	/// Removed by `prune_empty_accounts` once the account holds no items.
+	#[pallet::storage]
+	pub type ItemCounter<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	/// Submission statistics for each account that has stored an item.
+	#[pallet::storage]
+	pub type AccountStats<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Stats, ValueQuery>;
+
+	/// Total encoded size of each account's stored items, as tracked against
+	/// `MaxBytesPerAccount`.
+	#[pallet::storage]
+	pub type AccountBytes<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Number of shadow items stored across all accounts.
+	#[pallet::storage]
+	pub type TotalItems<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Ids of each account's items carrying a tag, by account and tag.
+	#[pallet::storage]
+	pub type TagIndex<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		BoundedTag<T>,
+		BoundedVec<T::Hash, T::MaxItemsPerTag>,
+		ValueQuery,
+	>;
+
+	/// Position of each item in its owner's `ShadowItems` vector, by owner and item id.
+	///
+	/// Lets an item be removed without searching the vector for its id.
+	#[pallet::storage]
+	pub type ItemIndex<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::Hash, u32>;
+
+	/// Accounts that opted out; nothing can be submitted under them until they opt back in.
+	#[pallet::storage]
+	pub type OptedOut<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Item transfers offered with `transfer_item_ownership` and not yet accepted, by owner
+	/// and item id, to the account the item is offered to.
+	#[pallet::storage]
+	pub type PendingTransfers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		T::AccountId,
+	>;
+
+	/// Items of reaped accounts still to be cleaned up, by former owner and item id, with the
+	/// item's tags.
+	///
+	/// `on_killed_account` queues them and `on_idle` drops their index entries and item
+	/// records a few at a time.
+	#[pallet::storage]
+	pub type ReapedItems<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		ItemTags<T>,
+	>;
+
+	/// Reaped accounts whose queued items, received keys and sessions `on_idle` has yet to
+	/// clear.
+	#[pallet::storage]
+	pub type ReapedAccounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Events emitted by the pallet.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A shadow item was stored. [who, item_id, cid]
+		ShadowItemStored { who: T::AccountId, item_id: T::Hash, cid: Vec<u8> },
+		/// A shadow item was removed. [who, item_id, reason]
+		ShadowItemRemoved { who: T::AccountId, item_id: T::Hash, reason: RemovalReason },
+		/// Consent was granted. [who, consent_id, message_hash, granted_at, expires_at, receipt]
+		ConsentGranted {
+			who: T::AccountId,
+			consent_id: ConsentId,
+			message_hash: Vec<u8>,
+			granted_at: BlockNumberFor<T>,
+			expires_at: Option<BlockNumberFor<T>>,
+			receipt: T::Hash,
+		},
+		/// Consent was revoked. [who, consent_id, reason]
+		ConsentRevoked { who: T::AccountId, consent_id: ConsentId, reason: RevocationReason },
+		/// An account's metadata limit override was set or cleared. [who, limit]
+		MetadataLimitSet { who: T::AccountId, limit: Option<u32> },
+		/// A session key was authorized to submit for an account. [who, session, expires_at]
+		SessionAuthorized { who: T::AccountId, session: T::AccountId, expires_at: BlockNumberFor<T> },
+		/// A shadow item was pinned. [who, item_id]
+		ItemPinned { who: T::AccountId, item_id: T::Hash },
+		/// A shadow item was unpinned. [who, item_id]
+		ItemUnpinned { who: T::AccountId, item_id: T::Hash },
+		/// All of an account's shadow items were deleted. [who, count]
+		AllShadowItemsDeleted { who: T::AccountId, count: u32 },
+		/// An agent was authorized to submit for an account. [who, agent]
+		AgentAuthorized { who: T::AccountId, agent: T::AccountId },
+		/// All of an account's agents were revoked. [who, count]
+		AllAgentsRevoked { who: T::AccountId, count: u32 },
+		/// An item was shared with a new recipient. [who, item_id, recipient]
+		ItemShared { who: T::AccountId, item_id: T::Hash, recipient: T::AccountId },
+		/// The key shared with an existing recipient was replaced. [who, item_id, recipient]
+		ItemShareUpdated { who: T::AccountId, item_id: T::Hash, recipient: T::AccountId },
+		/// A recipient's access to an item's key was revoked. [who, item_id, recipient]
+		ItemShareRevoked { who: T::AccountId, item_id: T::Hash, recipient: T::AccountId },
+		/// The sources covered by one of an account's consents changed. [who, consent_id, scope]
+		ConsentScopeAmended { who: T::AccountId, consent_id: ConsentId, scope: Vec<Source> },
+		/// Summary of the items an account stored in this block. [who, count]
+		ItemsStored { who: T::AccountId, count: u32 },
+		/// A consent template was registered. [message_hash]
+		ConsentTemplateRegistered { message_hash: Vec<u8> },
+		/// An account was added to the trusted submitters. [who]
+		TrustedAdded { who: T::AccountId },
+		/// An account was removed from the trusted submitters. [who]
+		TrustedRemoved { who: T::AccountId },
+		/// A reaped account's consent and items were removed. [who, items_removed]
+		AccountReaped { who: T::AccountId, items_removed: u32 },
+		/// An item was flagged. [who, item_id, flagger, reason]
+		ItemFlagged { who: T::AccountId, item_id: T::Hash, flagger: T::AccountId, reason: u8 },
+		/// An item reached the flag review threshold. [who, item_id, flags]
+		ItemNeedsReview { who: T::AccountId, item_id: T::Hash, flags: u32 },
+		/// An item's flags were resolved and their deposits slashed or returned.
+		/// [who, item_id, frivolous]
+		FlagsResolved { who: T::AccountId, item_id: T::Hash, frivolous: bool },
+		/// An account reserved item slots. [who, count, reserved]
+		SlotsReserved { who: T::AccountId, count: u32, reserved: u32 },
+		/// An account released its unused reserved slots. [who, count]
+		SlotsReleased { who: T::AccountId, count: u32 },
+		/// Empty `ShadowItems` entries were removed. [count]
+		EmptyAccountsPruned { count: u32 },
+		/// A migration dropped an account's oldest items to fit `MaxItemsPerAccount`.
+		/// [who, dropped]
+		MigrationTruncated { who: T::AccountId, dropped: u32 },
+		/// A shadow item's CID, key and metadata were replaced. [who, item_id, cid]
+		ShadowItemUpdated { who: T::AccountId, item_id: T::Hash, cid: Vec<u8> },
+		/// A shadow item's CID was replaced. [who, item_id, new_cid]
+		CidUpdated { who: T::AccountId, item_id: T::Hash, new_cid: Vec<u8> },
+		/// Consent was moved to another account. [from, to]
+		ConsentTransferred { from: T::AccountId, to: T::AccountId },
+		/// A source's default metadata was set or cleared. [source, template]
+		SourceMetaTemplateSet { source: Source, template: Option<Vec<u8>> },
+		/// A tag was added to a shadow item. [who, item_id, tag]
+		ItemTagged { who: T::AccountId, item_id: T::Hash, tag: Vec<u8> },
+		/// A tag was removed from a shadow item. [who, item_id, tag]
+		ItemUntagged { who: T::AccountId, item_id: T::Hash, tag: Vec<u8> },
+		/// An oracle reported an item's CID as unretrievable. [who, item_id, reporter, reports]
+		DeadCidReported {
+			who: T::AccountId,
+			item_id: T::Hash,
+			reporter: T::AccountId,
+			reports: u32,
+		},
+		/// An expired consent record was removed from storage. [who, consent_id]
+		ConsentExpiredPruned { who: T::AccountId, consent_id: ConsentId },
+		/// A shadow item passed its expiry block and was removed. [who, item_id]
+		ShadowItemExpired { who: T::AccountId, item_id: T::Hash },
+		/// An account opted out of the pallet. [who]
+		OptedOut { who: T::AccountId },
+		/// An account opted back in to the pallet. [who]
+		OptedIn { who: T::AccountId },
+		/// A consent's expiry was extended. [who, consent_id, new_expires_at]
+		ConsentRenewed {
+			who: T::AccountId,
+			consent_id: ConsentId,
+			new_expires_at: BlockNumberFor<T>,
+		},
+		/// An item was replaced by a new one. [who, old_item_id, new_item_id]
+		ShadowItemReplaced { who: T::AccountId, old_item_id: T::Hash, new_item_id: T::Hash },
+		/// An item moved to another account. [from, to, item_id]
+		ItemOwnershipTransferred { from: T::AccountId, to: T::AccountId, item_id: T::Hash },
+		/// An account's shadow items from one source were deleted. [who, source, count]
+		ItemsDeletedBySource { who: T::AccountId, source: Source, count: u32 },
+		/// An item's access was logged. [accessor, owner, item_id]
+		ItemAccessed { accessor: T::AccountId, owner: T::AccountId, item_id: T::Hash },
+		/// A source was registered or renamed. [id, name]
+		SourceRegistered { id: u16, name: Vec<u8> },
+		/// A source was unregistered. [id]
+		SourceUnregistered { id: u16 },
+		/// An item was offered to another account. [from, to, item_id]
+		ItemTransferOffered { from: T::AccountId, to: T::AccountId, item_id: T::Hash },
+		/// An item transfer offer was withdrawn. [from, item_id]
+		ItemTransferCancelled { from: T::AccountId, item_id: T::Hash },
+		/// A transferred consent was re-signed by its new holder. [who, consent_id]
+		ConsentResigned { who: T::AccountId, consent_id: ConsentId },
+	}
+
+	/// Errors that can occur in the pallet.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The CID is too long.
+		CidTooLong,
+		/// The encrypted key is too long.
+		KeyTooLong,
+		/// The metadata is too long.
+		MetadataTooLong,
+		/// The account has too many items.
+		TooManyItems,
+		/// Item not found.
+		ItemNotFound,
+		/// Invalid source value.
+		InvalidSource,
+		/// No consent record found.
+		NoConsent,
+		/// Consent has expired.
+		ConsentExpired,
+		/// The account revoked its consent and has not granted it again.
+		ConsentRevoked,
+		/// The metadata limit exceeds `MaxMetadataLengthHard`.
+		MetadataLimitTooHigh,
+		/// The session expiry is in the past or beyond `MaxSessionDuration`.
+		InvalidSessionExpiry,
+		/// The submitter is neither an agent nor holds a session for the account.
+		NotAuthorizedSubmitter,
+		/// The account named a consent delegate, and the submitter is not it.
+		NotAuthorizedDelegate,
+		/// The session has expired.
+		SessionExpired,
+		/// The account already has `MaxAgents` agents.
+		TooManyAgents,
+		/// The account's free balance is below `MinBalanceForConsent`.
+		InsufficientBalanceForConsent,
+		/// The CID is not valid for the item's source, or is not a well-formed IPFS CID
+		/// under `StrictCidValidation`.
+		InvalidCid,
+		/// The item is already shared with `MaxSharesPerItem` recipients.
+		TooManyShares,
+		/// The consent scope would exceed `MaxScopeSources`.
+		ScopeTooLarge,
+		/// The message hash does not match a registered consent template.
+		UnknownTemplate,
+		/// Another of the account's items already uses this CID.
+		DuplicateCid,
+		/// The pallet was disabled at genesis.
+		PalletDisabled,
+		/// The signature scheme byte is not one of [`signature_scheme`].
+		UnsupportedSignatureScheme,
+		/// The consent signature is malformed or was not made by the granting account.
+		InvalidConsentSignature,
+		/// The consent signature's `valid_until` block has passed.
+		ConsentSignatureExpired,
+		/// `MaxAccountsPerMessage` accounts already consented with this message hash.
+		TooManyAccountsForMessage,
+		/// The target account already holds a consent record.
+		ConsentAlreadyExists,
+		/// The consent was transferred and must be re-signed with `resign_consent` before use.
+		ConsentNeedsResign,
+		/// The reporter already reported this item.
+		AlreadyReported,
+		/// The encrypted metadata is too long.
+		EncryptedMetadataTooLong,
+		/// The caller already flagged this item.
+		AlreadyFlagged,
+		/// The item has too many unresolved flags.
+		TooManyFlags,
+		/// The item has no flags to resolve.
+		NoFlags,
+		/// Not enough free item slots to reserve.
+		InsufficientCapacity,
+		/// The account has no reserved slots.
+		NoReservedSlots,
+		/// The item's CID, encrypted key and metadata together exceed `MaxItemTotalBytes`.
+		ItemTooLarge,
+		/// The consent lacks a valid co-signature from the `ComplianceKey` account.
+		MissingCosignature,
+		/// The item already has `MaxTagsPerItem` tags.
+		TooManyTags,
+		/// The tag is longer than `MaxTagLength`.
+		TagTooLong,
+		/// The batch holds more than `MaxBatchSize` items.
+		BatchTooLarge,
+		/// The account already holds `MaxConsentsPerAccount` consents.
+		TooManyConsents,
+		/// The account opted out and accepts no submissions until it opts back in.
+		AccountOptedOut,
+		/// None of the account's valid consents covers the item's source.
+		SourceNotConsented,
+		/// The account cannot reserve the deposit for the items it is storing.
+		InsufficientDepositBalance,
+		/// The consent never expires, so it cannot be renewed.
+		ConsentAlreadyPermanent,
+		/// The item is not shared with the recipient.
+		ShareNotFound,
+		/// The account already has `MaxItemsPerTag` items with this tag.
+		TooManyItemsWithTag,
+		/// An item's expiry block is not after the current block.
+		InvalidItemExpiry,
+		/// The account received `MaxSubmissionsPerWindow` items in the current window.
+		RateLimited,
+		/// The items would take the account's stored bytes past `MaxBytesPerAccount`.
+		AccountStorageFull,
+		/// The caller neither owns the item nor holds a key shared for it.
+		AccessDenied,
+		/// The source name is longer than `MaxSourceNameLen`.
+		SourceNameTooLong,
+		/// The scope change would leave the consent scope empty, covering all sources.
+		EmptyScope,
+		/// The item is not offered to the caller.
+		NoPendingTransfer,
+		/// The consent was not transferred, so there is nothing to re-sign.
+		ConsentNotTransferred,
+		/// More ids were passed to `items_diff` than an account can hold items.
+		TooManyKnownIds,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Whether the pallet accepts mutating calls.
+		pub enabled: bool,
+		/// Consents to seed.
+		pub consents: Vec<GenesisConsent<T>>,
+		/// Items to seed. Each owner needs a seeded consent covering the item's source, and
+		/// the source must be seeded too.
+		pub items: Vec<GenesisItem<T>>,
+		/// Sources to register, as `(id, name)`. Defaults to every [`Source`].
+		pub sources: Vec<(u16, Vec<u8>)>,
+		#[serde(skip)]
+		pub _config: core::marker::PhantomData<T>,
+	}
+
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				enabled: true,
+				consents: Vec::new(),
+				items: Vec::new(),
+				sources: Source::ALL
+					.into_iter()
+					.map(|source| (source.into(), source.name().to_vec()))
+					.collect(),
+				_config: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			<Enabled<T>>::put(self.enabled);
+
+			for (id, name) in &self.sources {
+				assert!(Source::try_from(*id).is_ok(), "genesis source id has no `Source` variant");
+				let name =
+					BoundedVec::try_from(name.clone()).expect("genesis source name is too long");
+				<RegisteredSources<T>>::insert(id, name);
+			}
+
+			// Seed data is validated as the extrinsics would, failing the build if invalid
+			for (who, granted_at, expires_at, message_hash) in &self.consents {
+				Pallet::<T>::insert_consent(
+					who.clone(),
+					message_hash.clone(),
+					*granted_at,
+					*expires_at,
+					Default::default(),
+					None,
+					false,
+					None,
+				)
+				.expect("genesis consent is invalid");
+			}
+			for (who, cid, encrypted_key, source, metadata) in &self.items {
+				Pallet::<T>::do_submit_shadow_item(
+					who.clone(),
+					cid.clone(),
+					encrypted_key.clone(),
+					*source,
+					metadata.clone(),
+					None,
+					false,
+					Default::default(),
+					None,
+				)
+				.expect("genesis item is invalid");
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			Self::prune_expired_consents(n);
+			Self::expire_items(n);
+
+			// Accounts for `on_finalize`, with a full summary buffer when aggregating
+			let pending =
+				if T::AggregateEvents::get() { T::MaxAggregatedAccounts::get() } else { 0 };
+			T::WeightInfo::on_finalize(pending)
+				.saturating_add(T::WeightInfo::prune_expired_consents(
+					T::MaxConsentPrunedPerBlock::get(),
+				))
+				.saturating_add(T::WeightInfo::expire_items(T::MaxAccountsExpiredPerBlock::get()))
+		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			<ConsentCheckCache<T>>::kill();
+
+			if T::AggregateEvents::get() {
+				for (who, count) in <PendingItemsStored<T>>::take() {
+					Self::deposit_event(Event::ItemsStored { who, count });
+				}
+			}
+
+			let submitted = <SubmissionsThisBlock<T>>::take();
+			if submitted > 0 {
+				<TotalSubmissions<T>>::mutate(|total| {
+					*total = total.saturating_add(submitted.into());
+				});
+			}
+		}
+
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let used = Self::prune_expired_sessions(now, remaining_weight);
+			used.saturating_add(Self::clear_reaped_accounts(remaining_weight.saturating_sub(used)))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(
+			_n: BlockNumberFor<T>,
+		) -> Result<(), polkadot_sdk::sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
+
+	/// Dispatchable calls that can be made to the pallet.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Submit a new shadow item to the chain.
+		///
+		/// - `cid`: The IPFS CID where encrypted content is stored.
+		/// - `encrypted_key`: The encrypted symmetric key.
+		/// - `source`: The platform the content was collected from.
+		/// - `metadata`: Additional metadata about the item.
+		/// - `expires_at`: Optional last block to keep the item for; unpinned items are removed
+		///   automatically once it has passed.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::submit_shadow_item(metadata.len() as u32))]
+		pub fn submit_shadow_item(
+			origin: OriginFor<T>,
+			cid: Vec<u8>,
+			encrypted_key: Vec<u8>,
+			source: Source,
+			metadata: Vec<u8>,
+			expires_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			Self::ensure_within_rate_limit(&who, &who, 1)?;
+			Self::do_submit_shadow_item(
+				who,
+				cid,
+				encrypted_key,
+				source,
+				metadata,
+				None,
+				false,
+				Default::default(),
+				expires_at,
+			)
 		}
 
 		/// Delete a shadow item.
 		///
 		/// - `item_id`: The ID of the item to delete.
+		///
+		/// Fails with `ItemNotFound` if the caller holds no item with this id, so
+		/// `ShadowItemRemoved` is only emitted for an item that was actually removed.
+		///
+		/// The whole item vector is decoded, so the fee is refunded down to the weight of the
+		/// caller's item count.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::delete_shadow_item())]
-		pub fn delete_shadow_item(origin: OriginFor<T>, item_id: T::Hash) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::delete_shadow_item(T::MaxItemsPerAccount::get()))]
+		#[allow(clippy::useless_conversion)]
+		pub fn delete_shadow_item(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
 
-			// Remove the item if it exists
-			<ShadowItems<T>>::mutate(&who, |items| {
-				items.retain(|item| {
-					// Compare the hash directly with the stored id
-					let stored_id = T::Hash::decode(&mut &item.id[..]).unwrap_or_default();
-					stored_id != item_id
-				});
-			});
+			let (removed, count) = Self::try_mutate_items(&who, |items| {
+				let count = items.len() as u32;
+				Self::take_item(&who, items, item_id)
+					.map(|removed| (removed, count))
+					.ok_or(Error::<T>::ItemNotFound)
+			})?;
+			Self::note_items_deleted(&who, &[removed]);
 
 			// Emit event
-			Self::deposit_event(Event::ShadowItemDeleted { who, item_id });
+			Self::deposit_event(Event::ShadowItemRemoved {
+				who,
+				item_id,
+				reason: RemovalReason::UserDeleted,
+			});
 
-			Ok(())
+			Ok(Some(T::WeightInfo::delete_shadow_item(count)).into())
 		}
 
 		/// Grant consent for the backend to submit shadow items on behalf of the user.
 		///
+		/// The consent is recorded for `signer`, so the call may be sent by the user or
+		/// relayed by the backend. Each grant adds a new consent alongside any the account
+		/// already holds; its id is reported in `ConsentGranted`.
+		///
 		/// - `message_hash`: Hash of the consent message.
 		/// - `duration`: Optional duration in blocks for consent validity.
+		/// - `scope`: The sources the consent covers; empty covers all sources.
+		/// - `delegate`: The account allowed to submit for `signer` through
+		///   `submit_shadow_item_for`, if any.
+		/// - `signature`: `signer`'s signature over [`Self::consent_payload`] for a
+		///   [`ConsentAuthorization::Grant`] of the other arguments; its scheme is recorded on the
+		///   consent.
+		/// - `signer`: The account granting consent.
 		#[pallet::call_index(2)]
 		#[pallet::weight(T::WeightInfo::grant_consent())]
 		pub fn grant_consent(
 			origin: OriginFor<T>,
 			message_hash: Vec<u8>,
 			duration: Option<BlockNumberFor<T>>,
+			scope: Vec<Source>,
+			delegate: Option<T::AccountId>,
+			signature: ConsentSignature<BlockNumberFor<T>>,
+			signer: T::AccountId,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-
-			let current_block = frame_system::Pallet::<T>::block_number();
-			let expires_at = duration.map(|d| current_block + d);
-
-			// Convert message hash to bounded vec
-			let bounded_hash = BoundedMessageHash::<T>::try_from(message_hash.clone())
-				.map_err(|_| DispatchError::Other("Message hash too long"))?;
-
-			// Store consent record
-			<ConsentRecords<T>>::insert(
-				&who,
-				ConsentRecord {
-					granted_at: current_block,
-					expires_at,
-					message_hash: bounded_hash,
-				},
-			);
+			ensure_signed(origin)?;
+			Self::ensure_enabled()?;
 
-			// Emit event
-			Self::deposit_event(Event::ConsentGranted { who, message_hash });
+			let scope = ConsentScope::<T>::try_from(scope.into_iter().collect::<BTreeSet<_>>())
+				.map_err(|_| Error::<T>::ScopeTooLarge)?;
+			let action = ConsentAuthorization::Grant {
+				message_hash: message_hash.clone(),
+				duration,
+				scope: scope.clone(),
+				delegate: delegate.clone(),
+			};
+			Self::ensure_consent_signature(&signer, action, &signature)?;
 
+			Self::do_grant_consent(
+				signer,
+				message_hash,
+				duration,
+				scope,
+				Some(signature.scheme),
+				false,
+				delegate,
+			)?;
 			Ok(())
 		}
 
-		/// Revoke consent for the backend to submit shadow items.
+		/// Revoke one of the caller's consents.
+		///
+		/// - `consent_id`: The consent to revoke; the caller's other consents are unaffected.
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::revoke_consent())]
-		pub fn revoke_consent(origin: OriginFor<T>) -> DispatchResult {
+		pub fn revoke_consent(origin: OriginFor<T>, consent_id: ConsentId) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
 
 			// Remove consent record
-			<ConsentRecords<T>>::remove(&who);
+			let consent =
+				<ConsentRecords<T>>::take(&who, consent_id).ok_or(Error::<T>::NoConsent)?;
+			Self::unindex_consent_message(&who, &consent.message_hash);
+			Self::record_consent_history(&who, ConsentAction::Revoked, consent.message_hash, None);
+			<RevokedConsents<T>>::insert(&who, frame_system::Pallet::<T>::block_number());
+			Self::invalidate_consent_cache(&who);
+			T::OnConsentRevoked::on_consent_revoked(&who);
 
 			// Emit event
-			Self::deposit_event(Event::ConsentRevoked { who });
+			Self::deposit_event(Event::ConsentRevoked {
+				who,
+				consent_id,
+				reason: RevocationReason::UserRevoked,
+			});
 
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		/// Check if an account has valid consent.
-		pub fn ensure_valid_consent(account: &T::AccountId) -> DispatchResult {
-			let consent = <ConsentRecords<T>>::get(account).ok_or(Error::<T>::NoConsent)?;
+		/// Set or clear an account's metadata length override.
+		///
+		/// - `who`: The account to configure.
+		/// - `limit`: The new limit, or `None` to fall back to `MaxMetadataLength`.
+		///
+		/// The override is dropped by `prune_empty_accounts` while `who` holds no items.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::set_metadata_limit_for_account())]
+		pub fn set_metadata_limit_for_account(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			limit: Option<u32>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
 
-			if let Some(expires_at) = consent.expires_at {
-				let current_block = frame_system::Pallet::<T>::block_number();
-				ensure!(current_block <= expires_at, Error::<T>::ConsentExpired);
+			match limit {
+				Some(limit) => {
+					ensure!(limit <= T::MaxMetadataLengthHard::get(), Error::<T>::MetadataLimitTooHigh);
+					<AccountMetadataLimit<T>>::insert(&who, limit);
+				},
+				None => <AccountMetadataLimit<T>>::remove(&who),
 			}
 
+			Self::deposit_event(Event::MetadataLimitSet { who, limit });
+
 			Ok(())
 		}
+
+		/// Authorize a short-lived session key to submit shadow items for the caller.
+		///
+		/// - `session_pubkey`: The session account allowed to call `submit_shadow_item_for`.
+		/// - `expires_at`: Last block at which the session is valid, at most
+		///   `MaxSessionDuration` blocks from now.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::authorize_session())]
+		pub fn authorize_session(
+			origin: OriginFor<T>,
+			session_pubkey: T::AccountId,
+			expires_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				expires_at >= current_block &&
+					expires_at <= current_block.saturating_add(T::MaxSessionDuration::get()),
+				Error::<T>::InvalidSessionExpiry
+			);
+
+			<Sessions<T>>::insert(&who, &session_pubkey, expires_at);
+
+			Self::deposit_event(Event::SessionAuthorized { who, session: session_pubkey, expires_at });
+
+			Ok(())
+		}
+
+		/// Submit a shadow item on behalf of another account.
+		///
+		/// The caller must be an agent of `owner`, the delegate named in one of `owner`'s
+		/// valid consents, or hold an unexpired session for it, and `owner` must have valid
+		/// consent. The item is stored under `owner`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::submit_shadow_item_for(metadata.len() as u32))]
+		pub fn submit_shadow_item_for(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			cid: Vec<u8>,
+			encrypted_key: Vec<u8>,
+			source: Source,
+			metadata: Vec<u8>,
+		) -> DispatchResult {
+			let submitter = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			Self::ensure_can_submit_for(&submitter, &owner)?;
+
+			Self::ensure_within_rate_limit(&submitter, &owner, 1)?;
+			Self::do_submit_shadow_item(
+				owner,
+				cid,
+				encrypted_key,
+				source,
+				metadata,
+				None,
+				true,
+				Default::default(),
+				None,
+			)
+		}
+
+		/// Pin a shadow item so it is exempt from automatic expiry.
+		///
+		/// - `item_id`: The ID of the item to pin.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::pin_item())]
+		pub fn pin_item(origin: OriginFor<T>, item_id: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			Self::set_pinned(&who, item_id, true)?;
+
+			Self::deposit_event(Event::ItemPinned { who, item_id });
+
+			Ok(())
+		}
+
+		/// Unpin a shadow item so it expires normally again.
+		///
+		/// - `item_id`: The ID of the item to unpin.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::unpin_item())]
+		pub fn unpin_item(origin: OriginFor<T>, item_id: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			Self::set_pinned(&who, item_id, false)?;
+
+			Self::deposit_event(Event::ItemUnpinned { who, item_id });
+
+			Ok(())
+		}
+
+		/// Delete all of the caller's shadow items, e.g. to erase its data after revoking
+		/// consent.
+		///
+		/// Emits a single aggregate `AllShadowItemsDeleted` event. No per-item
+		/// `ShadowItemRemoved` events are emitted; indexers should treat it as deleting every
+		/// item the account held before the call. The fee is refunded down to the weight of
+		/// the items actually deleted.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::delete_all_shadow_items(T::MaxItemsPerAccount::get()))]
+		#[allow(clippy::useless_conversion)]
+		pub fn delete_all_shadow_items(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			let removed = Self::current_items(<ShadowItems<T>>::take(&who));
+			let count = removed.len() as u32;
+			<AccountBytes<T>>::remove(&who);
+			let _ = <ItemIndex<T>>::clear_prefix(&who, u32::MAX, None);
+			Self::note_items_deleted(&who, &removed);
+
+			Self::deposit_event(Event::AllShadowItemsDeleted { who, count });
+
+			Ok(Some(T::WeightInfo::delete_all_shadow_items(count)).into())
+		}
+
+		/// Authorize a long-lived agent to submit shadow items for the caller.
+		///
+		/// Authorizing an existing agent again is a no-op.
+		///
+		/// - `agent`: The account allowed to call `submit_shadow_item_for`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::authorize_agent())]
+		pub fn authorize_agent(origin: OriginFor<T>, agent: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			<AuthorizedAgents<T>>::try_mutate(&who, |agents| -> DispatchResult {
+				if !agents.contains(&agent) {
+					agents.try_push(agent.clone()).map_err(|_| Error::<T>::TooManyAgents)?;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AgentAuthorized { who, agent });
+
+			Ok(())
+		}
+
+		/// Revoke every agent authorized by the caller.
+		///
+		/// Succeeds with a count of zero when the caller has no agents.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::revoke_all_agents())]
+		pub fn revoke_all_agents(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			let count = <AuthorizedAgents<T>>::take(&who).len() as u32;
+
+			Self::deposit_event(Event::AllAgentsRevoked { who, count });
+
+			Ok(())
+		}
+
+		/// Grant consent if needed and submit a shadow item in a single call.
+		///
+		/// Consent is granted only when the caller has no valid consent; an existing valid
+		/// consent is left untouched. An expired consent is only re-granted under the
+		/// `TreatAsNone` expiry policy, and a valid consent scoped to other sources is never
+		/// widened. Consents awaiting a re-sign after `transfer_consent` are replaced by the
+		/// fresh grant. Both steps succeed or neither takes effect.
+		///
+		/// - `message_hash`, `duration`: As for `grant_consent`.
+		/// - `signature`: As for `grant_consent`, signed by the caller over a grant covering
+		///   all sources without a delegate. It is checked whenever consent is granted.
+		/// - `cid`, `encrypted_key`, `source`, `metadata`: As for `submit_shadow_item`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(
+			T::WeightInfo::grant_consent()
+				.saturating_add(T::WeightInfo::submit_shadow_item(metadata.len() as u32))
+		)]
+		#[allow(clippy::too_many_arguments)]
+		pub fn submit_and_grant(
+			origin: OriginFor<T>,
+			message_hash: Vec<u8>,
+			duration: Option<BlockNumberFor<T>>,
+			signature: ConsentSignature<BlockNumberFor<T>>,
+			cid: Vec<u8>,
+			encrypted_key: Vec<u8>,
+			source: Source,
+			metadata: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			match Self::ensure_valid_consent(&who, source) {
+				Ok(()) => {},
+				Err(e)
+					if e == Error::<T>::ConsentExpired.into() ||
+						e == Error::<T>::SourceNotConsented.into() =>
+					return Err(e),
+				Err(_) => {
+					let action = ConsentAuthorization::Grant {
+						message_hash: message_hash.clone(),
+						duration,
+						scope: Default::default(),
+						delegate: None,
+					};
+					Self::ensure_consent_signature(&who, action, &signature)?;
+					Self::do_grant_consent(
+						who.clone(),
+						message_hash,
+						duration,
+						Default::default(),
+						Some(signature.scheme),
+						false,
+						None,
+					)?;
+				},
+			}
+
+			Self::ensure_within_rate_limit(&who, &who, 1)?;
+			Self::do_submit_shadow_item(
+				who,
+				cid,
+				encrypted_key,
+				source,
+				metadata,
+				None,
+				false,
+				Default::default(),
+				None,
+			)
+		}
+
+		/// Share one of the caller's items with `recipient`.
+		///
+		/// - `item_id`: The item to share; must belong to the caller.
+		/// - `recipient`: The account granted access.
+		/// - `recipient_encrypted_key`: The item key wrapped for `recipient`.
+		///
+		/// Re-sharing with an existing recipient replaces their key and does not count
+		/// against `MaxSharesPerItem`.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::share_item())]
+		pub fn share_item(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+			recipient: T::AccountId,
+			recipient_encrypted_key: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			ensure!(
+				Self::items_of(&who)
+					.iter()
+					.any(|item| item.id == item_id),
+				Error::<T>::ItemNotFound
+			);
+			let bounded_key = BoundedKey::<T>::try_from(recipient_encrypted_key)
+				.map_err(|_| Error::<T>::KeyTooLong)?;
+
+			let updated = <SharedKeys<T>>::contains_key(&recipient, item_id);
+			if !updated {
+				<ShareRecipients<T>>::try_mutate(item_id, |recipients| {
+					recipients.try_push(recipient.clone()).map_err(|_| Error::<T>::TooManyShares)
+				})?;
+			}
+			<SharedKeys<T>>::insert(&recipient, item_id, bounded_key);
+
+			if updated {
+				Self::deposit_event(Event::ItemShareUpdated { who, item_id, recipient });
+			} else {
+				Self::deposit_event(Event::ItemShared { who, item_id, recipient });
+			}
+
+			Ok(())
+		}
+
+		/// Add or remove sources from one of the caller's consent scopes without re-granting.
+		///
+		/// - `consent_id`: The consent whose scope changes.
+		/// - `add`: Sources to include in the scope.
+		/// - `remove`: Sources to drop from the scope, applied after `add`.
+		///
+		/// Adding a source already in scope, or removing one that is not, is a no-op. An empty
+		/// scope covers all sources, so adding to it changes nothing, while removing from it
+		/// leaves every other source in scope. A removal that would leave the scope empty, and
+		/// so widen it back to all sources, fails with `EmptyScope`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::amend_consent_scope())]
+		pub fn amend_consent_scope(
+			origin: OriginFor<T>,
+			consent_id: ConsentId,
+			add: Vec<Source>,
+			remove: Vec<Source>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			let max_sources = T::MaxScopeSources::get() as usize;
+			ensure!(
+				add.len() <= max_sources && remove.len() <= max_sources,
+				Error::<T>::ScopeTooLarge
+			);
+
+			let scope = <ConsentRecords<T>>::try_mutate(
+				&who,
+				consent_id,
+				|maybe_consent| -> Result<Vec<Source>, DispatchError> {
+					let consent = maybe_consent.as_mut().ok_or(Error::<T>::NoConsent)?;
+					if consent.scope.is_empty() && remove.is_empty() {
+						return Ok(Vec::new());
+					}
+
+					let mut scope = if consent.scope.is_empty() {
+						Source::ALL.into_iter().collect()
+					} else {
+						consent.scope.clone().into_inner()
+					};
+					scope.extend(add);
+					for source in remove {
+						scope.remove(&source);
+					}
+					ensure!(!scope.is_empty(), Error::<T>::EmptyScope);
+
+					consent.scope =
+						ConsentScope::<T>::try_from(scope).map_err(|_| Error::<T>::ScopeTooLarge)?;
+					Ok(consent.scope.iter().cloned().collect())
+				},
+			)?;
+			// A check cached under the old scope must not outlive it
+			Self::invalidate_consent_cache(&who);
+
+			Self::deposit_event(Event::ConsentScopeAmended { who, consent_id, scope });
+
+			Ok(())
+		}
+
+		/// Register a consent template's message hash.
+		///
+		/// Only consulted by `grant_consent` when `EnforceTemplates` is set.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::register_consent_template())]
+		pub fn register_consent_template(
+			origin: OriginFor<T>,
+			message_hash: Vec<u8>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			let bounded_hash = BoundedMessageHash::<T>::try_from(message_hash.clone())
+				.map_err(|_| DispatchError::Other("Message hash too long"))?;
+			<ConsentTemplates<T>>::insert(bounded_hash, ());
+
+			Self::deposit_event(Event::ConsentTemplateRegistered { message_hash });
+
+			Ok(())
+		}
+
+		/// Point an existing item at a new CID, keeping its id and every other field.
+		///
+		/// - `item_id`: The ID of the item to update.
+		/// - `new_cid`: The CID the content is now stored under.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::update_cid())]
+		pub fn update_cid(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+			new_cid: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			let bounded_cid =
+				BoundedCid::<T>::try_from(new_cid.clone()).map_err(|_| Error::<T>::CidTooLong)?;
+
+			Self::try_mutate_items(&who, |items| -> DispatchResult {
+				let index = items
+					.iter()
+					.position(|item| item.id == item_id)
+					.ok_or(Error::<T>::ItemNotFound)?;
+				Self::ensure_valid_consent(&who, items[index].source)?;
+				ensure!(
+					items.iter().enumerate().all(|(i, item)| i == index || item.cid != bounded_cid),
+					Error::<T>::DuplicateCid
+				);
+				Self::validate_cid(items[index].source, &new_cid)?;
+				items[index].cid = bounded_cid;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::CidUpdated { who, item_id, new_cid });
+
+			Ok(())
+		}
+
+		/// Move the caller's consents to `new_account`, e.g. after a key migration.
+		///
+		/// The moved consents keep their ids, expiry and scope but are marked `needs_resign`,
+		/// so `new_account` must call `resign_consent` for each before it can submit.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::transfer_consent())]
+		pub fn transfer_consent(origin: OriginFor<T>, new_account: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			ensure!(
+				<ConsentRecords<T>>::iter_key_prefix(&new_account).next().is_none(),
+				Error::<T>::ConsentAlreadyExists
+			);
+			let consents: Vec<_> = <ConsentRecords<T>>::drain_prefix(&who).collect();
+			ensure!(!consents.is_empty(), Error::<T>::NoConsent);
+
+			for (consent_id, mut consent) in consents {
+				consent.needs_resign = true;
+				// The new account takes the old one's slot, so the entry cannot overflow
+				<ConsentByMessage<T>>::mutate(&consent.message_hash, |accounts| {
+					if let Some(account) = accounts.iter_mut().find(|account| **account == who) {
+						*account = new_account.clone();
+					}
+				});
+				<ConsentRecords<T>>::insert(&new_account, consent_id, consent);
+			}
+			// Keep later grants by the new account from reusing a moved id
+			let next_id = <NextConsentId<T>>::get(&who);
+			<NextConsentId<T>>::mutate(&new_account, |id| *id = (*id).max(next_id));
+			Self::invalidate_consent_cache(&who);
+
+			Self::deposit_event(Event::ConsentTransferred { from: who, to: new_account });
+
+			Ok(())
+		}
+
+		/// Set or clear the default metadata for a source's items.
+		///
+		/// - `source`: The source the template applies to.
+		/// - `template`: Metadata stored for items submitted with empty metadata, or `None`
+		///   to clear. Limited to `MaxMetadataLength` so it fits every account's limit.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::set_source_meta_template())]
+		pub fn set_source_meta_template(
+			origin: OriginFor<T>,
+			source: Source,
+			template: Option<Vec<u8>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			match &template {
+				Some(template) => {
+					ensure!(
+						template.len() as u32 <= T::MaxMetadataLength::get(),
+						Error::<T>::MetadataTooLong
+					);
+					let bounded_template = BoundedMetadata::<T>::try_from(template.clone())
+						.map_err(|_| Error::<T>::MetadataTooLong)?;
+					<SourceMetaTemplate<T>>::insert(source, bounded_template);
+				},
+				None => <SourceMetaTemplate<T>>::remove(source),
+			}
+
+			Self::deposit_event(Event::SourceMetaTemplateSet { source, template });
+
+			Ok(())
+		}
+
+		/// Report that an item's CID can no longer be retrieved.
+		///
+		/// - `who`: The owner of the item.
+		/// - `item_id`: The ID of the item.
+		///
+		/// Once `DeadCidThreshold` distinct reporters agree, the item is removed.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::report_dead_cid())]
+		pub fn report_dead_cid(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			item_id: T::Hash,
+		) -> DispatchResult {
+			let reporter = T::ReporterOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			ensure!(
+				Self::items_of(&who)
+					.iter()
+					.any(|item| item.id == item_id),
+				Error::<T>::ItemNotFound
+			);
+
+			let reports = <DeadCidReports<T>>::try_mutate(
+				item_id,
+				|reporters| -> Result<u32, DispatchError> {
+					ensure!(!reporters.contains(&reporter), Error::<T>::AlreadyReported);
+					// Cannot overflow: reports are cleared once the threshold is reached
+					reporters
+						.try_push(reporter.clone())
+						.map_err(|_| Error::<T>::AlreadyReported)?;
+					Ok(reporters.len() as u32)
+				},
+			)?;
+
+			if reports >= T::DeadCidThreshold::get() {
+				let removed =
+					Self::shrink_items(&who, |items| Self::take_item(&who, items, item_id));
+				Self::note_items_deleted(&who, removed.as_slice());
+				<DeadCidReports<T>>::remove(item_id);
+
+				Self::deposit_event(Event::ShadowItemRemoved {
+					who,
+					item_id,
+					reason: RemovalReason::DeadCid,
+				});
+			} else {
+				Self::deposit_event(Event::DeadCidReported { who, item_id, reporter, reports });
+			}
+
+			Ok(())
+		}
+
+		/// Delete any account's shadow item.
+		///
+		/// - `who`: The owner of the item.
+		/// - `item_id`: The ID of the item to delete.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::force_delete_item())]
+		pub fn force_delete_item(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			item_id: T::Hash,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			let removed = Self::try_mutate_items(&who, |items| {
+				Self::take_item(&who, items, item_id).ok_or(Error::<T>::ItemNotFound)
+			})?;
+			Self::note_items_deleted(&who, &[removed]);
+
+			Self::deposit_event(Event::ShadowItemRemoved {
+				who,
+				item_id,
+				reason: RemovalReason::ForceDeleted,
+			});
+
+			Ok(())
+		}
+
+		/// Submit a new shadow item carrying encrypted metadata alongside the plaintext.
+		///
+		/// `encrypted_metadata` is decryptable with the same key as the content, for private
+		/// fields; `metadata` stays plaintext so it can be indexed. Otherwise identical to
+		/// [`Pallet::submit_shadow_item`].
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::submit_shadow_item_with_encrypted_metadata(
+			metadata.len() as u32,
+			encrypted_metadata.len() as u32,
+		))]
+		pub fn submit_shadow_item_with_encrypted_metadata(
+			origin: OriginFor<T>,
+			cid: Vec<u8>,
+			encrypted_key: Vec<u8>,
+			source: Source,
+			metadata: Vec<u8>,
+			encrypted_metadata: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			Self::ensure_within_rate_limit(&who, &who, 1)?;
+			Self::do_submit_shadow_item(
+				who,
+				cid,
+				encrypted_key,
+				source,
+				metadata,
+				Some(encrypted_metadata),
+				false,
+				Default::default(),
+				None,
+			)
+		}
+
+		/// Exempt `who` from submission throttling.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::add_trusted())]
+		pub fn add_trusted(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			<TrustedSubmitters<T>>::insert(&who, ());
+
+			Self::deposit_event(Event::TrustedAdded { who });
+
+			Ok(())
+		}
+
+		/// Subject `who` to submission throttling again.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::remove_trusted())]
+		pub fn remove_trusted(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			<TrustedSubmitters<T>>::remove(&who);
+
+			Self::deposit_event(Event::TrustedRemoved { who });
+
+			Ok(())
+		}
+
+		/// Flag one of `who`'s items for review, reserving `FlagDeposit` from the caller.
+		///
+		/// Once the item has `FlagReviewThreshold` flags, `ItemNeedsReview` is emitted; the item
+		/// is never removed automatically.
+		///
+		/// - `who`: The owner of the item.
+		/// - `item_id`: The ID of the item to flag.
+		/// - `reason`: Caller-defined reason code.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::flag_item())]
+		pub fn flag_item(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			item_id: T::Hash,
+			reason: u8,
+		) -> DispatchResult {
+			let flagger = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			ensure!(
+				Self::items_of(&who).iter().any(|item| item.id == item_id),
+				Error::<T>::ItemNotFound
+			);
+
+			let flags = <Flags<T>>::try_mutate(
+				&who,
+				item_id,
+				|flags| -> Result<u32, DispatchError> {
+					ensure!(
+						!flags.iter().any(|flag| flag.flagger == flagger),
+						Error::<T>::AlreadyFlagged
+					);
+					let deposit = T::FlagDeposit::get();
+					flags
+						.try_push(FlagRecord { flagger: flagger.clone(), reason, deposit })
+						.map_err(|_| Error::<T>::TooManyFlags)?;
+					T::Currency::reserve(&flagger, deposit)?;
+					Ok(flags.len() as u32)
+				},
+			)?;
+
+			Self::deposit_event(Event::ItemFlagged {
+				who: who.clone(),
+				item_id,
+				flagger,
+				reason,
+			});
+			if flags >= T::FlagReviewThreshold::get() {
+				Self::deposit_event(Event::ItemNeedsReview { who, item_id, flags });
+			}
+
+			Ok(())
+		}
+
+		/// Resolve the flags on one of `who`'s items, clearing them.
+		///
+		/// Deposits are slashed if the flags are `frivolous`, and returned otherwise.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::resolve_flags(T::MaxFlagsPerItem::get()))]
+		pub fn resolve_flags(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			item_id: T::Hash,
+			frivolous: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			let flags = <Flags<T>>::take(&who, item_id);
+			ensure!(!flags.is_empty(), Error::<T>::NoFlags);
+
+			for flag in flags {
+				if frivolous {
+					let _ = T::Currency::slash_reserved(&flag.flagger, flag.deposit);
+				} else {
+					T::Currency::unreserve(&flag.flagger, flag.deposit);
+				}
+			}
+
+			Self::deposit_event(Event::FlagsResolved { who, item_id, frivolous });
+
+			Ok(())
+		}
+
+		/// Hold back `count` of the caller's free item slots for its own submissions.
+		///
+		/// Items the caller submits itself consume reserved slots first; agents and sessions
+		/// submitting through `submit_shadow_item_for` can only use unreserved slots.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::reserve_slots())]
+		pub fn reserve_slots(origin: OriginFor<T>, count: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			let stored = <ShadowItems<T>>::decode_len(&who).unwrap_or(0) as u32;
+			let reserved = <ReservedSlots<T>>::get(&who).saturating_add(count);
+			ensure!(
+				stored.saturating_add(reserved) <= T::MaxItemsPerAccount::get(),
+				Error::<T>::InsufficientCapacity
+			);
+			<ReservedSlots<T>>::insert(&who, reserved);
+
+			Self::deposit_event(Event::SlotsReserved { who, count, reserved });
+
+			Ok(())
+		}
+
+		/// Release all of the caller's unused reserved slots.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::release_slots())]
+		pub fn release_slots(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			let count = <ReservedSlots<T>>::take(&who);
+			ensure!(count > 0, Error::<T>::NoReservedSlots);
+
+			Self::deposit_event(Event::SlotsReleased { who, count });
+
+			Ok(())
+		}
+
+		/// Remove the records left behind by accounts whose items were all deleted.
+		///
+		/// For each account holding no items, its `ShadowItems`, `AccountStats`,
+		/// `AccountBytes`, `ItemCounter` and `AccountMetadataLimit` entries are removed, and
+		/// its `SubmissionRate` once the window has ended, so pruning never lifts a rate limit
+		/// early. `EmptyAccountsPruned` counts the accounts cleared.
+		///
+		/// Examines at most `max` accounts with statistics, resuming where the previous call
+		/// stopped and starting over once every account has been examined.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::prune_empty_accounts(*max))]
+		pub fn prune_empty_accounts(origin: OriginFor<T>, max: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			let keys = match <PruneCursor<T>>::get() {
+				Some(cursor) =>
+					<AccountStats<T>>::iter_keys_from(<AccountStats<T>>::hashed_key_for(cursor)),
+				None => <AccountStats<T>>::iter_keys(),
+			};
+			let examined: Vec<T::AccountId> = keys.take(max as usize).collect();
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut count = 0u32;
+			for who in &examined {
+				if <ShadowItems<T>>::decode_len(who).unwrap_or_default() > 0 {
+					continue;
+				}
+				<ShadowItems<T>>::remove(who);
+				<AccountStats<T>>::remove(who);
+				<AccountBytes<T>>::remove(who);
+				<ItemCounter<T>>::remove(who);
+				<AccountMetadataLimit<T>>::remove(who);
+				let (window_start, _) = <SubmissionRate<T>>::get(who);
+				if now >= window_start.saturating_add(T::RateLimitWindow::get()) {
+					<SubmissionRate<T>>::remove(who);
+				}
+				count.saturating_inc();
+			}
+
+			// Start over from the beginning once the end of the map is reached
+			match examined.last() {
+				Some(last) if examined.len() == max as usize => <PruneCursor<T>>::put(last),
+				_ => <PruneCursor<T>>::kill(),
+			}
+
+			Self::deposit_event(Event::EmptyAccountsPruned { count });
+
+			Ok(())
+		}
+
+		/// Grant consent signed by both the caller and the `ComplianceKey` account.
+		///
+		/// The consent is recorded as co-signed, which `RequireCosignedConsent` deployments
+		/// need before the caller can submit.
+		///
+		/// - `message_hash`, `duration`: As for `grant_consent`.
+		/// - `user_signature`: The caller's signature, as for `grant_consent`, over a grant
+		///   covering all sources without a delegate.
+		/// - `admin_signature`: The compliance account's signature over
+		///   [`Self::consent_payload`] for a [`ConsentAuthorization::Cosign`] of the caller's
+		///   grant. It is checked against the same `ConsentNonce` as `user_signature`, which
+		///   the grant uses up, so neither can be replayed.
+		#[pallet::call_index(31)]
+		#[pallet::weight(
+			T::WeightInfo::grant_consent()
+				.saturating_add(T::WeightInfo::verify_consent_signature())
+		)]
+		pub fn grant_consent_cosigned(
+			origin: OriginFor<T>,
+			message_hash: Vec<u8>,
+			duration: Option<BlockNumberFor<T>>,
+			user_signature: ConsentSignature<BlockNumberFor<T>>,
+			admin_signature: ConsentSignature<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			// Checked first, while the nonce is the one the user's signature uses up
+			let compliance = T::ComplianceKey::get().ok_or(Error::<T>::MissingCosignature)?;
+			let cosign = ConsentAuthorization::Cosign { message_hash: message_hash.clone() };
+			ensure!(
+				Self::check_consent_signature(&compliance, &who, cosign, &admin_signature)?,
+				Error::<T>::MissingCosignature
+			);
+			let action = ConsentAuthorization::Grant {
+				message_hash: message_hash.clone(),
+				duration,
+				scope: Default::default(),
+				delegate: None,
+			};
+			Self::ensure_consent_signature(&who, action, &user_signature)?;
+
+			Self::do_grant_consent(
+				who,
+				message_hash,
+				duration,
+				Default::default(),
+				Some(user_signature.scheme),
+				true,
+				None,
+			)?;
+			Ok(())
+		}
+
+		/// Replace an item's CID, encrypted key and metadata in place.
+		///
+		/// The item keeps its id, source and pin state; its timestamp is set to now.
+		///
+		/// - `item_id`: The ID of the item to update.
+		/// - `new_cid`, `new_encrypted_key`, `new_metadata`: The replacement fields, bounded
+		///   as for `submit_shadow_item`.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::update_shadow_item(new_metadata.len() as u32))]
+		pub fn update_shadow_item(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+			new_cid: Vec<u8>,
+			new_encrypted_key: Vec<u8>,
+			new_metadata: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			ensure!(
+				new_metadata.len() as u32 <= Self::metadata_limit(&who),
+				Error::<T>::MetadataTooLong
+			);
+			Self::ensure_item_size(&new_cid, &new_encrypted_key, &new_metadata)?;
+			let bounded_cid =
+				BoundedCid::<T>::try_from(new_cid.clone()).map_err(|_| Error::<T>::CidTooLong)?;
+			let bounded_key = BoundedKey::<T>::try_from(new_encrypted_key)
+				.map_err(|_| Error::<T>::KeyTooLong)?;
+			let bounded_metadata = BoundedMetadata::<T>::try_from(new_metadata)
+				.map_err(|_| Error::<T>::MetadataTooLong)?;
+
+			Self::try_mutate_items(&who, |items| -> DispatchResult {
+				let index = items
+					.iter()
+					.position(|item| item.id == item_id)
+					.ok_or(Error::<T>::ItemNotFound)?;
+				Self::ensure_valid_consent(&who, items[index].source)?;
+				ensure!(
+					items.iter().enumerate().all(|(i, item)| i == index || item.cid != bounded_cid),
+					Error::<T>::DuplicateCid
+				);
+				Self::validate_cid(items[index].source, &new_cid)?;
+
+				let item = &mut items[index];
+				item.cid = bounded_cid;
+				item.encrypted_key = bounded_key;
+				item.metadata = bounded_metadata;
+				item.timestamp = Self::current_timestamp();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ShadowItemUpdated { who, item_id, cid: new_cid });
+
+			Ok(())
+		}
+
+		/// Add a tag to one of the caller's items.
+		///
+		/// Adding a tag the item already has is a no-op.
+		///
+		/// - `item_id`: The ID of the item to tag.
+		/// - `tag`: The tag, at most `MaxTagLength` bytes.
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::add_tag())]
+		pub fn add_tag(origin: OriginFor<T>, item_id: T::Hash, tag: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			let bounded_tag =
+				BoundedTag::<T>::try_from(tag.clone()).map_err(|_| Error::<T>::TagTooLong)?;
+			Self::try_mutate_items(&who, |items| -> DispatchResult {
+				let item = items
+					.iter_mut()
+					.find(|item| item.id == item_id)
+					.ok_or(Error::<T>::ItemNotFound)?;
+				if !item.tags.contains(&bounded_tag) {
+					item.tags
+						.try_push(bounded_tag.clone())
+						.map_err(|_| Error::<T>::TooManyTags)?;
+					Self::index_item_tags(&who, item_id, &[bounded_tag])?;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ItemTagged { who, item_id, tag });
+
+			Ok(())
+		}
+
+		/// Remove a tag from one of the caller's items.
+		///
+		/// Removing a tag the item does not have is a no-op.
+		///
+		/// - `item_id`: The ID of the item to untag.
+		/// - `tag`: The tag to remove.
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::remove_tag())]
+		pub fn remove_tag(origin: OriginFor<T>, item_id: T::Hash, tag: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			Self::try_mutate_items(&who, |items| -> DispatchResult {
+				let item = items
+					.iter_mut()
+					.find(|item| item.id == item_id)
+					.ok_or(Error::<T>::ItemNotFound)?;
+				let before = item.tags.len();
+				item.tags.retain(|existing| existing.as_slice() != tag.as_slice());
+				if item.tags.len() < before {
+					if let Ok(bounded_tag) = BoundedTag::<T>::try_from(tag.clone()) {
+						Self::unindex_item_tags(&who, item_id, &[bounded_tag]);
+					}
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ItemUntagged { who, item_id, tag });
+
+			Ok(())
+		}
+
+		/// Submit several shadow items at once, checking consent a single time.
+		///
+		/// - `items`: `(cid, encrypted_key, source, metadata)` for each item, at most
+		///   `MaxBatchSize` of them.
+		///
+		/// Every item is validated as by `submit_shadow_item`, and none is stored unless all
+		/// of them fit within `MaxItemsPerAccount`.
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::submit_shadow_items_batch(items.len() as u32))]
+		pub fn submit_shadow_items_batch(
+			origin: OriginFor<T>,
+			items: Vec<BatchItem>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(items.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+			Self::ensure_within_rate_limit(&who, &who, items.len() as u32)?;
+
+			Self::ensure_not_opted_out(&who)?;
+			let sources: BTreeSet<_> = items.iter().map(|(_, _, source, _)| *source).collect();
+			for source in sources {
+				Self::ensure_valid_consent(&who, source)?;
+			}
+
+			let new_items = items
+				.into_iter()
+				.map(|(cid, encrypted_key, source, metadata)| {
+					let item_id = Self::next_item_id(&who, &cid);
+					Self::build_item(&who, item_id, cid, encrypted_key, source, metadata, None)
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+			let count = new_items.len() as u32;
+			Self::ensure_bytes_available(&who, Self::items_size(&new_items))?;
+			let deposit = new_items
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |total, item| total.saturating_add(item.deposit));
+			Self::reserve_item_deposit(&who, deposit)?;
+			let stored: Vec<_> =
+				new_items.iter().map(|item| (item.id, item.cid.to_vec())).collect();
+
+			Self::try_mutate_items(&who, |items| -> DispatchResult {
+				for item in new_items {
+					Self::push_item(&who, items, item)?;
+				}
+				Ok(())
+			})?;
+			Self::note_items_stored(&who, count);
+			let reserved = <ReservedSlots<T>>::get(&who);
+			if reserved > 0 {
+				match reserved.saturating_sub(count) {
+					0 => <ReservedSlots<T>>::remove(&who),
+					left => <ReservedSlots<T>>::insert(&who, left),
+				}
+			}
+
+			<SubmissionsThisBlock<T>>::mutate(|submissions| {
+				*submissions = submissions.saturating_add(count)
+			});
+
+			for (item_id, cid) in stored {
+				if !(T::AggregateEvents::get() && Self::defer_stored_event(&who)) {
+					Self::deposit_event(Event::ShadowItemStored { who: who.clone(), item_id, cid });
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Stop anything from being submitted under the caller's account, including by its
+		/// agents and sessions, until it calls `opt_in`.
+		///
+		/// Every consent the caller holds is revoked. If `ClearItemsOnOptOut` is set, its
+		/// items are deleted as by `delete_all_shadow_items`.
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::opt_out(T::MaxItemsPerAccount::get()))]
+		pub fn opt_out(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			<OptedOut<T>>::insert(&who, ());
+
+			let consents: Vec<_> = <ConsentRecords<T>>::drain_prefix(&who).collect();
+			if !consents.is_empty() {
+				for (consent_id, consent) in consents {
+					Self::unindex_consent_message(&who, &consent.message_hash);
+					Self::record_consent_history(
+						&who,
+						ConsentAction::Revoked,
+						consent.message_hash,
+						None,
+					);
+					T::OnConsentRevoked::on_consent_revoked(&who);
+					Self::deposit_event(Event::ConsentRevoked {
+						who: who.clone(),
+						consent_id,
+						reason: RevocationReason::OptedOut,
+					});
+				}
+				<RevokedConsents<T>>::insert(&who, frame_system::Pallet::<T>::block_number());
+				Self::invalidate_consent_cache(&who);
+			}
+
+			if T::ClearItemsOnOptOut::get() {
+				let removed = Self::current_items(<ShadowItems<T>>::take(&who));
+				let count = removed.len() as u32;
+				<AccountBytes<T>>::remove(&who);
+				let _ = <ItemIndex<T>>::clear_prefix(&who, u32::MAX, None);
+				Self::note_items_deleted(&who, &removed);
+				Self::deposit_event(Event::AllShadowItemsDeleted { who: who.clone(), count });
+			}
+
+			Self::deposit_event(Event::OptedOut { who });
+
+			Ok(())
+		}
+
+		/// Accept submissions under the caller's account again after `opt_out`.
+		///
+		/// Consent revoked by opting out is not restored and must be granted again.
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::opt_in())]
+		pub fn opt_in(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			<OptedOut<T>>::remove(&who);
+
+			Self::deposit_event(Event::OptedIn { who });
+
+			Ok(())
+		}
+
+		/// Push back the expiry of one of the caller's unexpired consents.
+		///
+		/// - `consent_id`: The consent to renew.
+		/// - `additional_duration`: Blocks added to the consent's current `expires_at`.
+		///
+		/// Unlike granting again, `granted_at` and the receipt of the original grant are kept.
+		#[pallet::call_index(37)]
+		#[pallet::weight(T::WeightInfo::renew_consent())]
+		pub fn renew_consent(
+			origin: OriginFor<T>,
+			consent_id: ConsentId,
+			additional_duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			let (message_hash, new_expires_at) = <ConsentRecords<T>>::try_mutate(
+				&who,
+				consent_id,
+				|maybe_consent| -> Result<_, DispatchError> {
+					let consent = maybe_consent.as_mut().ok_or(Error::<T>::NoConsent)?;
+					let expires_at =
+						consent.expires_at.ok_or(Error::<T>::ConsentAlreadyPermanent)?;
+					ensure!(
+						!Self::is_expired(expires_at, current_block),
+						Error::<T>::ConsentExpired
+					);
+					let new_expires_at = expires_at.saturating_add(additional_duration);
+					consent.expires_at = Some(new_expires_at);
+					Ok((consent.message_hash.clone(), new_expires_at))
+				},
+			)?;
+			Self::record_consent_history(
+				&who,
+				ConsentAction::Renewed,
+				message_hash,
+				Some(new_expires_at),
+			);
+
+			Self::deposit_event(Event::ConsentRenewed { who, consent_id, new_expires_at });
+
+			Ok(())
+		}
+
+		/// Replace one of the caller's items with a new one in a single step.
+		///
+		/// - `old_item_id`: The item to remove.
+		/// - `new_cid`, `new_key`, `new_source`, `new_metadata`: The new item, as for
+		///   `submit_shadow_item`.
+		///
+		/// The new item takes the old one's position, so this succeeds even when the account
+		/// is at `MaxItemsPerAccount`. The old item's deposit is returned and the new one's
+		/// reserved. The new item counts against the caller's submission rate limit.
+		#[pallet::call_index(38)]
+		#[pallet::weight(T::WeightInfo::replace_item(new_metadata.len() as u32))]
+		pub fn replace_item(
+			origin: OriginFor<T>,
+			old_item_id: T::Hash,
+			new_cid: Vec<u8>,
+			new_key: Vec<u8>,
+			new_source: Source,
+			new_metadata: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			Self::ensure_not_opted_out(&who)?;
+			Self::ensure_valid_consent(&who, new_source)?;
+			Self::ensure_within_rate_limit(&who, &who, 1)?;
+
+			let new_item_id = Self::next_item_id(&who, &new_cid);
+			let item = Self::build_item(
+				&who,
+				new_item_id,
+				new_cid,
+				new_key,
+				new_source,
+				new_metadata,
+				None,
+			)?;
+			let deposit = item.deposit;
+
+			let removed = Self::try_mutate_items(&who, |items| -> Result<_, DispatchError> {
+				let index = Self::item_position(&who, items, old_item_id)
+					.ok_or(Error::<T>::ItemNotFound)?;
+				<ItemIndex<T>>::remove(&who, old_item_id);
+				<ItemIndex<T>>::insert(&who, new_item_id, index as u32);
+				Ok(core::mem::replace(&mut items[index], item))
+			})?;
+			Self::note_items_deleted(&who, &[removed]);
+			Self::reserve_item_deposit(&who, deposit)?;
+			Self::note_items_stored(&who, 1);
+			<SubmissionsThisBlock<T>>::mutate(|count| count.saturating_inc());
+
+			Self::deposit_event(Event::ShadowItemReplaced { who, old_item_id, new_item_id });
+
+			Ok(())
+		}
+
+		/// Withdraw a recipient's copy of one of the caller's item keys.
+		///
+		/// - `item_id`: The shared item, which the caller must own.
+		/// - `recipient`: The account whose shared key is removed.
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::revoke_share())]
+		pub fn revoke_share(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+			recipient: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			ensure!(
+				Self::items_of(&who).iter().any(|item| item.id == item_id),
+				Error::<T>::ItemNotFound
+			);
+			<SharedKeys<T>>::take(&recipient, item_id).ok_or(Error::<T>::ShareNotFound)?;
+			<ShareRecipients<T>>::mutate_exists(item_id, |maybe_recipients| {
+				if let Some(recipients) = maybe_recipients {
+					recipients.retain(|account| *account != recipient);
+					if recipients.is_empty() {
+						*maybe_recipients = None;
+					}
+				}
+			});
+
+			Self::deposit_event(Event::ItemShareRevoked { who, item_id, recipient });
+
+			Ok(())
+		}
+
+		/// Submit a new shadow item with structured metadata.
+		///
+		/// `metadata` is stored SCALE-encoded in the item's `metadata` field, where
+		/// [`Pallet::decode_metadata`] reads it back, and counts against the same length
+		/// limits. Its tags also become the item's tags, indexed for
+		/// [`Pallet::items_with_tag`]. Otherwise identical to [`Pallet::submit_shadow_item`].
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::submit_shadow_item(metadata.encoded_size() as u32))]
+		pub fn submit_structured_item(
+			origin: OriginFor<T>,
+			cid: Vec<u8>,
+			encrypted_key: Vec<u8>,
+			source: Source,
+			metadata: ItemMetadata<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			Self::ensure_within_rate_limit(&who, &who, 1)?;
+			let tags = metadata.tags.clone();
+			Self::do_submit_shadow_item(
+				who,
+				cid,
+				encrypted_key,
+				source,
+				metadata.encode(),
+				None,
+				false,
+				tags,
+				None,
+			)
+		}
+
+		/// Offer one of the caller's items to `new_owner`, e.g. after an identity migration.
+		///
+		/// - `item_id`: The item to offer, which the caller must own.
+		/// - `new_owner`: The account the item is offered to.
+		///
+		/// The item only moves once `new_owner` calls `accept_item_transfer`. A new offer for
+		/// the same item replaces the previous one.
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::transfer_item_ownership())]
+		pub fn transfer_item_ownership(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(<ItemIndex<T>>::contains_key(&who, item_id), Error::<T>::ItemNotFound);
+
+			<PendingTransfers<T>>::insert(&who, item_id, &new_owner);
+
+			Self::deposit_event(Event::ItemTransferOffered { from: who, to: new_owner, item_id });
+
+			Ok(())
+		}
+
+		/// Delete all of the caller's shadow items from `source`, e.g. after disconnecting
+		/// that integration.
+		///
+		/// Emits a single aggregate `ItemsDeletedBySource` event, also when the caller holds
+		/// no items from `source`. Every item is examined, so the fee is refunded down to the
+		/// weight of the caller's item count.
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::delete_items_by_source(T::MaxItemsPerAccount::get()))]
+		#[allow(clippy::useless_conversion)]
+		pub fn delete_items_by_source(
+			origin: OriginFor<T>,
+			source: Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			// Bail out with the scanned count when nothing matches, so nothing is written
+			let (removed, scanned) = Self::try_shrink_items(&who, |items| {
+				let scanned = items.len() as u32;
+				let removed: Vec<_> =
+					items.iter().filter(|item| item.source == source).cloned().collect();
+				if removed.is_empty() {
+					return Err(scanned);
+				}
+				items.retain(|item| item.source != source);
+				// Survivors shift down, so every remaining position is rewritten
+				for item in &removed {
+					<ItemIndex<T>>::remove(&who, item.id);
+				}
+				for (index, item) in items.iter().enumerate() {
+					<ItemIndex<T>>::insert(&who, item.id, index as u32);
+				}
+				Ok((removed, scanned))
+			})
+			.unwrap_or_else(|scanned| (Vec::new(), scanned));
+			let count = removed.len() as u32;
+			Self::note_items_deleted(&who, &removed);
+
+			Self::deposit_event(Event::ItemsDeletedBySource { who, source, count });
+
+			Ok(Some(T::WeightInfo::delete_items_by_source(scanned)).into())
+		}
+
+		/// Record in `owner`'s `AccessLog` that the caller accessed one of its items.
+		///
+		/// The caller must be `owner` or hold a key shared for the item. Once the log holds
+		/// `MaxAccessLogEntries` accesses, the oldest is dropped to make room.
+		///
+		/// - `owner`: The account holding the item.
+		/// - `item_id`: The item accessed.
+		#[pallet::call_index(43)]
+		#[pallet::weight(T::WeightInfo::access_item())]
+		pub fn access_item(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			item_id: T::Hash,
+		) -> DispatchResult {
+			let accessor = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			ensure!(<ItemIndex<T>>::contains_key(&owner, item_id), Error::<T>::ItemNotFound);
+			ensure!(
+				accessor == owner || <SharedKeys<T>>::contains_key(&accessor, item_id),
+				Error::<T>::AccessDenied
+			);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			<AccessLog<T>>::mutate(&owner, item_id, |log| {
+				if log.is_full() {
+					log.remove(0);
+				}
+				// Cannot fail: a slot was freed above if the log was full
+				let _ = log.try_push((accessor.clone(), now));
+			});
+
+			Self::deposit_event(Event::ItemAccessed { accessor, owner, item_id });
+
+			Ok(())
+		}
+
+		/// Register a source items may be submitted from, or rename a registered one.
+		///
+		/// - `id`: The source id, the encoded index of the matching [`Source`].
+		/// - `name`: The source's display name.
+		///
+		/// Fails with `InvalidSource` if `id` has no [`Source`] variant, as nothing could be
+		/// submitted from it. A new platform needs a runtime upgrade adding its variant.
+		#[pallet::call_index(44)]
+		#[pallet::weight(T::WeightInfo::register_source())]
+		pub fn register_source(origin: OriginFor<T>, id: u16, name: Vec<u8>) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(Source::try_from(id).is_ok(), Error::<T>::InvalidSource);
+
+			let bounded_name = BoundedVec::<u8, T::MaxSourceNameLen>::try_from(name.clone())
+				.map_err(|_| Error::<T>::SourceNameTooLong)?;
+			<RegisteredSources<T>>::insert(id, bounded_name);
+
+			Self::deposit_event(Event::SourceRegistered { id, name });
+
+			Ok(())
+		}
+
+		/// Accept an item `from` offered to the caller with `transfer_item_ownership`.
+		///
+		/// - `from`: The item's current owner.
+		/// - `item_id`: The offered item.
+		///
+		/// The item keeps its id, tags, outstanding flags, access log and shared keys. Its
+		/// deposit is returned to `from` and reserved from the caller. The item must fit in
+		/// the caller's `MaxBytesPerAccount`, cannot take one of the caller's reserved slots,
+		/// and the caller must hold a consent covering the item's source when
+		/// `RequireRecipientConsent` is set.
+		#[pallet::call_index(45)]
+		#[pallet::weight(T::WeightInfo::accept_item_transfer())]
+		pub fn accept_item_transfer(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			item_id: T::Hash,
+		) -> DispatchResult {
+			let new_owner = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(
+				<PendingTransfers<T>>::take(&from, item_id).as_ref() == Some(&new_owner),
+				Error::<T>::NoPendingTransfer
+			);
+			Self::ensure_not_opted_out(&new_owner)?;
+
+			let item = Self::try_mutate_items(&from, |items| {
+				Self::take_item(&from, items, item_id).ok_or(Error::<T>::ItemNotFound)
+			})?;
+			if T::RequireRecipientConsent::get() {
+				Self::ensure_valid_consent(&new_owner, item.source)?;
+			}
+			Self::ensure_bytes_available(&new_owner, Self::stored_size(&item))?;
+
+			T::Currency::unreserve(&from, item.deposit);
+			Self::reserve_item_deposit(&new_owner, item.deposit)?;
+			Self::unindex_item_tags(&from, item_id, &item.tags);
+			Self::index_item_tags(&new_owner, item_id, &item.tags)?;
+			let reserved = <ReservedSlots<T>>::get(&new_owner);
+			Self::try_mutate_items(&new_owner, |items| -> DispatchResult {
+				ensure!(
+					(items.len() as u32).saturating_add(reserved) < T::MaxItemsPerAccount::get(),
+					Error::<T>::TooManyItems
+				);
+				Self::push_item(&new_owner, items, item)
+			})?;
+			<AccountStats<T>>::mutate(&from, |stats| stats.current.saturating_dec());
+			<AccountStats<T>>::mutate(&new_owner, |stats| stats.current.saturating_inc());
+			// Shares are keyed by item id alone, so they follow the item as is
+			let flags = <Flags<T>>::take(&from, item_id);
+			if !flags.is_empty() {
+				<Flags<T>>::insert(&new_owner, item_id, flags);
+			}
+			let accesses = <AccessLog<T>>::take(&from, item_id);
+			if !accesses.is_empty() {
+				<AccessLog<T>>::insert(&new_owner, item_id, accesses);
+			}
+
+			Self::deposit_event(Event::ItemOwnershipTransferred { from, to: new_owner, item_id });
+
+			Ok(())
+		}
+
+		/// Withdraw the caller's outstanding offer of `item_id`.
+		#[pallet::call_index(46)]
+		#[pallet::weight(T::WeightInfo::cancel_item_transfer())]
+		pub fn cancel_item_transfer(origin: OriginFor<T>, item_id: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+			ensure!(
+				<PendingTransfers<T>>::take(&who, item_id).is_some(),
+				Error::<T>::NoPendingTransfer
+			);
+
+			Self::deposit_event(Event::ItemTransferCancelled { from: who, item_id });
+
+			Ok(())
+		}
+
+		/// Re-sign a consent moved to the caller by `transfer_consent`.
+		///
+		/// - `consent_id`: The transferred consent.
+		/// - `signature`: The caller's signature, as for `grant_consent`, over a
+		///   [`ConsentAuthorization::Resign`] of the consent.
+		///
+		/// Expiry, scope and the receipt of the original grant are kept.
+		#[pallet::call_index(47)]
+		#[pallet::weight(T::WeightInfo::resign_consent())]
+		pub fn resign_consent(
+			origin: OriginFor<T>,
+			consent_id: ConsentId,
+			signature: ConsentSignature<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_enabled()?;
+
+			<ConsentRecords<T>>::try_mutate(&who, consent_id, |maybe_consent| -> DispatchResult {
+				let consent = maybe_consent.as_mut().ok_or(Error::<T>::NoConsent)?;
+				ensure!(consent.needs_resign, Error::<T>::ConsentNotTransferred);
+				let action = ConsentAuthorization::Resign {
+					receipt: consent.receipt,
+					scope: consent.scope.clone(),
+					delegate: consent.delegate.clone(),
+				};
+				Self::ensure_consent_signature(&who, action, &signature)?;
+				consent.needs_resign = false;
+				consent.signature_scheme = Some(signature.scheme);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ConsentResigned { who, consent_id });
+
+			Ok(())
+		}
+
+		/// Unregister a source, rejecting further submissions from it.
+		///
+		/// - `id`: The registered source id.
+		///
+		/// Items already submitted from the source are kept. Fails with `InvalidSource` if
+		/// `id` is not registered.
+		#[pallet::call_index(48)]
+		#[pallet::weight(T::WeightInfo::unregister_source())]
+		pub fn unregister_source(origin: OriginFor<T>, id: u16) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			Self::ensure_enabled()?;
+
+			<RegisteredSources<T>>::take(id).ok_or(Error::<T>::InvalidSource)?;
+
+			Self::deposit_event(Event::SourceUnregistered { id });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Ensure the pallet was not disabled at genesis.
+		fn ensure_enabled() -> DispatchResult {
+			ensure!(<Enabled<T>>::get(), Error::<T>::PalletDisabled);
+			Ok(())
+		}
+
+		/// Ensure `account` has not opted out of the pallet.
+		fn ensure_not_opted_out(account: &T::AccountId) -> DispatchResult {
+			ensure!(!<OptedOut<T>>::contains_key(account), Error::<T>::AccountOptedOut);
+			Ok(())
+		}
+
+		/// Check if an account has valid consent covering `source`.
+		///
+		/// Any one of the account's consents being valid and covering `source` suffices. If
+		/// none is, the error is `SourceNotConsented` when a valid consent exists for other
+		/// sources only, and otherwise that of its most recently granted consent.
+		pub fn ensure_valid_consent(account: &T::AccountId, source: Source) -> DispatchResult {
+			if <ConsentCheckCache<T>>::get().contains(account) {
+				return Ok(());
+			}
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			let mut latest_error: Option<(ConsentId, DispatchError)> = None;
+			let mut out_of_scope = false;
+			for (consent_id, consent) in <ConsentRecords<T>>::iter_prefix(account) {
+				match Self::check_consent(&consent, current_block) {
+					Ok(()) if consent.scope.is_empty() => {
+						// A full cache only costs later checks their read, so ignore overflow
+						<ConsentCheckCache<T>>::mutate(|cache| {
+							let _ = cache.try_insert(account.clone());
+						});
+						return Ok(());
+					},
+					Ok(()) if consent.scope.contains(&source) => return Ok(()),
+					Ok(()) => out_of_scope = true,
+					Err(e) if latest_error.as_ref().is_none_or(|(id, _)| consent_id > *id) =>
+						latest_error = Some((consent_id, e)),
+					Err(_) => {},
+				}
+			}
+
+			if out_of_scope {
+				return Err(Error::<T>::SourceNotConsented.into());
+			}
+			Err(latest_error.map(|(_, e)| e).unwrap_or_else(|| {
+				if <RevokedConsents<T>>::contains_key(account) {
+					Error::<T>::ConsentRevoked.into()
+				} else {
+					Error::<T>::NoConsent.into()
+				}
+			}))
+		}
+
+		/// Check that `consent` can authorize a submission at block `now`.
+		fn check_consent(
+			consent: &ConsentRecord<T, BlockNumberFor<T>>,
+			now: BlockNumberFor<T>,
+		) -> DispatchResult {
+			ensure!(!consent.needs_resign, Error::<T>::ConsentNeedsResign);
+			ensure!(
+				consent.cosigned || !T::RequireCosignedConsent::get(),
+				Error::<T>::MissingCosignature
+			);
+
+			if let Some(expires_at) = consent.expires_at {
+				if Self::is_expired(expires_at, now) {
+					return Err(match T::ExpiredConsentPolicy::get() {
+						ExpiredConsentPolicy::Reject => Error::<T>::ConsentExpired,
+						ExpiredConsentPolicy::TreatAsNone => Error::<T>::NoConsent,
+					}
+					.into());
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Whether a consent expiring at `expires_at` has expired by `now`.
+		///
+		/// Honours `ConsentExpiryInclusive` at the boundary block.
+		fn is_expired(expires_at: BlockNumberFor<T>, now: BlockNumberFor<T>) -> bool {
+			if T::ConsentExpiryInclusive::get() {
+				now > expires_at
+			} else {
+				now >= expires_at
+			}
+		}
+
+		/// The timestamp recorded on newly stored items.
+		///
+		/// This is currently the block number rather than wall-clock time; switching to
+		/// `pallet_timestamp` only needs to change this function.
+		pub fn current_timestamp() -> u64 {
+			frame_system::Pallet::<T>::block_number().saturated_into::<u64>()
+		}
+
+		/// Drop an account from the in-block consent cache after its consent changes.
+		fn invalidate_consent_cache(account: &T::AccountId) {
+			<ConsentCheckCache<T>>::mutate(|cache| {
+				cache.remove(account);
+			});
+		}
+
+		/// Check that `submitter` may submit shadow items on behalf of `owner`.
+		pub fn ensure_can_submit_for(
+			submitter: &T::AccountId,
+			owner: &T::AccountId,
+		) -> DispatchResult {
+			if <AuthorizedAgents<T>>::get(owner).contains(submitter) {
+				return Ok(());
+			}
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			let mut delegated = false;
+			for consent in <ConsentRecords<T>>::iter_prefix_values(owner) {
+				let Some(delegate) = &consent.delegate else { continue };
+				if delegate == submitter && Self::check_consent(&consent, current_block).is_ok() {
+					return Ok(());
+				}
+				delegated = true;
+			}
+
+			match <Sessions<T>>::get(owner, submitter) {
+				Some(expires_at) => {
+					ensure!(current_block <= expires_at, Error::<T>::SessionExpired);
+					Ok(())
+				},
+				// The owner named who may submit for it, and it is not the caller
+				None if delegated => Err(Error::<T>::NotAuthorizedDelegate.into()),
+				None => Err(Error::<T>::NotAuthorizedSubmitter.into()),
+			}
+		}
+
+		/// Remove the expired records among the next `MaxConsentPrunedPerBlock` consent
+		/// records, resuming after `ConsentPruneCursor`.
+		///
+		/// Starts over from the beginning once every record has been examined.
+		fn prune_expired_consents(now: BlockNumberFor<T>) {
+			let max = T::MaxConsentPrunedPerBlock::get() as usize;
+			if max == 0 {
+				return;
+			}
+
+			let records = match <ConsentPruneCursor<T>>::get() {
+				Some((who, consent_id)) => <ConsentRecords<T>>::iter_from(
+					<ConsentRecords<T>>::hashed_key_for(who, consent_id),
+				),
+				None => <ConsentRecords<T>>::iter(),
+			};
+			let examined: Vec<_> = records.take(max).collect();
+
+			for (who, consent_id, consent) in &examined {
+				let expired = consent
+					.expires_at
+					.is_some_and(|expires_at| Self::is_expired(expires_at, now));
+				if expired {
+					<ConsentRecords<T>>::remove(who, consent_id);
+					Self::unindex_consent_message(who, &consent.message_hash);
+					Self::invalidate_consent_cache(who);
+					T::OnConsentRevoked::on_consent_revoked(who);
+					Self::deposit_event(Event::ConsentExpiredPruned {
+						who: who.clone(),
+						consent_id: *consent_id,
+					});
+				}
+			}
+
+			match examined.last() {
+				Some((who, consent_id, _)) if examined.len() == max =>
+					<ConsentPruneCursor<T>>::put((who, consent_id)),
+				_ => <ConsentPruneCursor<T>>::kill(),
+			}
+		}
+
+		/// Remove the expired, unpinned items of the next `MaxAccountsExpiredPerBlock`
+		/// accounts, resuming after `ItemExpiryCursor`.
+		///
+		/// Starts over from the beginning once every account has been examined.
+		fn expire_items(now: BlockNumberFor<T>) {
+			let max = T::MaxAccountsExpiredPerBlock::get() as usize;
+			if max == 0 {
+				return;
+			}
+
+			let accounts = match <ItemExpiryCursor<T>>::get() {
+				Some(cursor) =>
+					<ShadowItems<T>>::iter_keys_from(<ShadowItems<T>>::hashed_key_for(cursor)),
+				None => <ShadowItems<T>>::iter_keys(),
+			};
+			let examined: Vec<T::AccountId> = accounts.take(max).collect();
+
+			let is_expired = |item: &ShadowItem<T>| {
+				!item.pinned && item.expires_at.is_some_and(|expires_at| expires_at < now)
+			};
+			for who in &examined {
+				if !Self::items_of(who).iter().any(is_expired) {
+					continue;
+				}
+				let expired: Vec<ShadowItem<T>> = Self::shrink_items(who, |items| {
+					let expired: Vec<_> =
+						items.iter().filter(|item| is_expired(item)).cloned().collect();
+					items.retain(|item| !is_expired(item));
+					// Survivors shift down, so every remaining position is rewritten
+					for item in &expired {
+						<ItemIndex<T>>::remove(who, item.id);
+					}
+					for (index, item) in items.iter().enumerate() {
+						<ItemIndex<T>>::insert(who, item.id, index as u32);
+					}
+					expired
+				});
+				Self::note_items_deleted(who, &expired);
+				for item in expired {
+					Self::deposit_event(Event::ShadowItemExpired {
+						who: who.clone(),
+						item_id: item.id,
+					});
+				}
+			}
+
+			match examined.last() {
+				Some(last) if examined.len() == max => <ItemExpiryCursor<T>>::put(last),
+				_ => <ItemExpiryCursor<T>>::kill(),
+			}
+		}
+
+		/// Remove expired sessions while weight remains, returning the weight used.
+		fn prune_expired_sessions(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let read = T::DbWeight::get().reads(1);
+			let write = T::DbWeight::get().writes(1);
+			let mut used = Weight::zero();
+			let mut expired = Vec::new();
+
+			for (owner, session, expires_at) in <Sessions<T>>::iter() {
+				// Reserve room for the removal so every collected entry can be deleted
+				if used.saturating_add(read).saturating_add(write).any_gt(remaining_weight) {
+					break;
+				}
+				used = used.saturating_add(read);
+				if now > expires_at {
+					used = used.saturating_add(write);
+					expired.push((owner, session));
+				}
+			}
+
+			for (owner, session) in expired {
+				<Sessions<T>>::remove(&owner, &session);
+			}
+
+			used
+		}
+
+		/// Clear what `on_killed_account` queued for reaped accounts, within
+		/// `remaining_weight`.
+		fn clear_reaped_accounts(remaining_weight: Weight) -> Weight {
+			// Looking up the account and the end of each of its queues
+			let lookup = T::DbWeight::get().reads(4);
+			let mut meter = WeightMeter::with_limit(remaining_weight);
+
+			while meter.try_consume(lookup).is_ok() {
+				let Some(who) = <ReapedAccounts<T>>::iter_keys().next() else { break };
+				if !Self::clear_reaped_account(&who, &mut meter) {
+					break;
+				}
+			}
+
+			meter.consumed()
+		}
+
+		/// Clear `who`'s queued items, then the keys shared with it and its sessions, as far as
+		/// `meter` allows, returning whether `who` is done and was dequeued.
+		///
+		/// An account that has been recreated since keeps the keys and sessions, as they can no
+		/// longer be told apart from ones it was given afterwards.
+		fn clear_reaped_account(who: &T::AccountId, meter: &mut WeightMeter) -> bool {
+			let db = T::DbWeight::get();
+
+			while let Some((item_id, tags)) = <ReapedItems<T>>::iter_prefix(who).next() {
+				if meter.try_consume(T::WeightInfo::clear_reaped_item()).is_err() {
+					return false;
+				}
+				Self::clear_reaped_item(who, item_id, &tags);
+			}
+
+			if !frame_system::Pallet::<T>::account_exists(who) {
+				while let Some(item_id) = <SharedKeys<T>>::iter_key_prefix(who).next() {
+					if meter.try_consume(db.reads_writes(2, 2)).is_err() {
+						return false;
+					}
+					<SharedKeys<T>>::remove(who, item_id);
+					<ShareRecipients<T>>::mutate(item_id, |recipients| {
+						recipients.retain(|recipient| recipient != who)
+					});
+				}
+				while let Some(session) = <Sessions<T>>::iter_key_prefix(who).next() {
+					if meter.try_consume(db.reads_writes(1, 1)).is_err() {
+						return false;
+					}
+					<Sessions<T>>::remove(who, session);
+				}
+			}
+
+			if meter.try_consume(db.writes(1)).is_err() {
+				return false;
+			}
+			<ReapedAccounts<T>>::remove(who);
+			true
+		}
+
+		/// Drop reaped `who`'s queued item `item_id` with its `ItemIndex` and `TagIndex`
+		/// entries and item records.
+		pub(crate) fn clear_reaped_item(
+			who: &T::AccountId,
+			item_id: T::Hash,
+			tags: &[BoundedTag<T>],
+		) {
+			<ReapedItems<T>>::remove(who, item_id);
+			<ItemIndex<T>>::remove(who, item_id);
+			Self::unindex_item_tags(who, item_id, tags);
+			Self::clear_item_records(who, item_id);
+		}
+
+		/// Set the `pinned` flag on one of `who`'s items.
+		fn set_pinned(who: &T::AccountId, item_id: T::Hash, pinned: bool) -> DispatchResult {
+			Self::try_mutate_items(who, |items| -> DispatchResult {
+				let item = items
+					.iter_mut()
+					.find(|item| item.id == item_id)
+					.ok_or(Error::<T>::ItemNotFound)?;
+				item.pinned = pinned;
+				Ok(())
+			})
+		}
+
+		/// The bytes a consent signature by `signer` authorizing `action` covers:
+		/// [`CONSENT_SIGNING_CONTEXT`] followed by the SCALE-encoded [`ConsentPayload`] with
+		/// `signer`'s current `ConsentNonce`.
+		///
+		/// The nonce moves on with every accepted signature, so none can be replayed, and
+		/// `valid_until` bounds how long an unused one stays good.
+		pub fn consent_payload(
+			signer: &T::AccountId,
+			action: ConsentAuthorization<T>,
+			valid_until: BlockNumberFor<T>,
+		) -> Vec<u8> {
+			let payload = ConsentPayload::<T> {
+				who: signer.clone(),
+				action,
+				valid_until,
+				nonce: <ConsentNonce<T>>::get(signer),
+			};
+			let mut message = CONSENT_SIGNING_CONTEXT.to_vec();
+			payload.encode_to(&mut message);
+			message
+		}
+
+		/// Check `signature` is `signer`'s signature over [`Self::consent_payload`] for
+		/// `action` and is not past its `valid_until`, then use up `signer`'s nonce.
+		fn ensure_consent_signature(
+			signer: &T::AccountId,
+			action: ConsentAuthorization<T>,
+			signature: &ConsentSignature<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			ensure!(
+				Self::check_consent_signature(signer, signer, action, signature)?,
+				Error::<T>::InvalidConsentSignature
+			);
+			<ConsentNonce<T>>::mutate(signer, |nonce| nonce.saturating_inc());
+			Ok(())
+		}
+
+		/// Whether `signature` is `key_holder`'s signature over [`Self::consent_payload`] for
+		/// `who` and `action`. Fails with `ConsentSignatureExpired` past its `valid_until`.
+		fn check_consent_signature(
+			key_holder: &T::AccountId,
+			who: &T::AccountId,
+			action: ConsentAuthorization<T>,
+			signature: &ConsentSignature<BlockNumberFor<T>>,
+		) -> Result<bool, DispatchError> {
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= signature.valid_until,
+				Error::<T>::ConsentSignatureExpired
+			);
+			let key = T::AccountPublicKey::convert(key_holder.clone());
+			let payload = Self::consent_payload(who, action, signature.valid_until);
+			Self::verify_signature(signature.scheme, &key, &payload, &signature.signature)
+		}
+
+		/// Whether `signature` is a valid `scheme` signature of `message` by `key`.
+		///
+		/// `key` is as produced by `AccountPublicKey`. Fails with `UnsupportedSignatureScheme`
+		/// for unknown schemes.
+		pub fn verify_signature(
+			scheme: u8,
+			key: &[u8; 32],
+			message: &[u8],
+			signature: &[u8],
+		) -> Result<bool, DispatchError> {
+			Ok(match scheme {
+				signature_scheme::SR25519 => sr25519::Signature::try_from(signature)
+					.is_ok_and(|signature| {
+						crypto::sr25519_verify(&signature, message, &sr25519::Public::from_raw(*key))
+					}),
+				signature_scheme::ED25519 => ed25519::Signature::try_from(signature)
+					.is_ok_and(|signature| {
+						crypto::ed25519_verify(&signature, message, &ed25519::Public::from_raw(*key))
+					}),
+				signature_scheme::ECDSA => <[u8; 65]>::try_from(signature).is_ok_and(|signature| {
+					crypto::secp256k1_ecdsa_recover_compressed(&signature, &blake2_256(message))
+						.is_ok_and(|public| blake2_256(&public) == *key)
+				}),
+				_ => return Err(Error::<T>::UnsupportedSignatureScheme.into()),
+			})
+		}
+
+		/// Validate and store a new consent record for `who`, returning its id.
+		///
+		/// `signature_scheme` is recorded on the consent for grants whose signature was
+		/// checked by the caller, and `cosigned` for grants whose compliance co-signature was.
+		fn do_grant_consent(
+			who: T::AccountId,
+			message_hash: Vec<u8>,
+			duration: Option<BlockNumberFor<T>>,
+			scope: ConsentScope<T>,
+			signature_scheme: Option<u8>,
+			cosigned: bool,
+			delegate: Option<T::AccountId>,
+		) -> Result<ConsentId, DispatchError> {
+			let current_block = frame_system::Pallet::<T>::block_number();
+			let expires_at = duration.map(|d| current_block + d);
+			Self::insert_consent(
+				who,
+				message_hash,
+				current_block,
+				expires_at,
+				scope,
+				signature_scheme,
+				cosigned,
+				delegate,
+			)
+		}
+
+		/// [`Self::do_grant_consent`] for a consent granted at `granted_at` and expiring at
+		/// `expires_at`, as seeded at genesis.
+		#[allow(clippy::too_many_arguments)]
+		fn insert_consent(
+			who: T::AccountId,
+			message_hash: Vec<u8>,
+			granted_at: BlockNumberFor<T>,
+			expires_at: Option<BlockNumberFor<T>>,
+			scope: ConsentScope<T>,
+			signature_scheme: Option<u8>,
+			cosigned: bool,
+			delegate: Option<T::AccountId>,
+		) -> Result<ConsentId, DispatchError> {
+			ensure!(
+				T::Currency::free_balance(&who) >= T::MinBalanceForConsent::get(),
+				Error::<T>::InsufficientBalanceForConsent
+			);
+			ensure!(
+				(<ConsentRecords<T>>::iter_key_prefix(&who).count() as u32) <
+					T::MaxConsentsPerAccount::get(),
+				Error::<T>::TooManyConsents
+			);
+
+			let message_hash = if T::NormalizeMessageHash::get() {
+				T::Hashing::hash(&message_hash).as_ref().to_vec()
+			} else {
+				message_hash
+			};
+
+			// Convert message hash to bounded vec
+			let bounded_hash = BoundedMessageHash::<T>::try_from(message_hash.clone())
+				.map_err(|_| DispatchError::Other("Message hash too long"))?;
+			ensure!(
+				!T::EnforceTemplates::get() || <ConsentTemplates<T>>::contains_key(&bounded_hash),
+				Error::<T>::UnknownTemplate
+			);
+
+			Self::index_consent_message(&who, &bounded_hash)?;
+
+			Self::record_consent_history(
+				&who,
+				ConsentAction::Granted,
+				bounded_hash.clone(),
+				expires_at,
+			);
+
+			<RevokedConsents<T>>::remove(&who);
+
+			// Store consent record
+			let consent_id = <NextConsentId<T>>::mutate(&who, |next| {
+				let id = *next;
+				*next = next.saturating_add(1);
+				id
+			});
+			let receipt = Self::consent_receipt(&who, &bounded_hash, granted_at, expires_at);
+			<ConsentRecords<T>>::insert(
+				&who,
+				consent_id,
+				ConsentRecord {
+					granted_at,
+					expires_at,
+					message_hash: bounded_hash,
+					scope,
+					needs_resign: false,
+					signature_scheme,
+					cosigned,
+					receipt,
+					delegate,
+				},
+			);
+
+			// Emit event
+			Self::deposit_event(Event::ConsentGranted {
+				who,
+				consent_id,
+				message_hash,
+				granted_at,
+				expires_at,
+				receipt,
+			});
+
+			Ok(consent_id)
+		}
+
+		/// Identifier of the grant of `message_hash` by `who` at `granted_at`.
+		///
+		/// Clients holding the grant's details can recompute it to check a stored receipt.
+		pub fn consent_receipt(
+			who: &T::AccountId,
+			message_hash: &[u8],
+			granted_at: BlockNumberFor<T>,
+			expires_at: Option<BlockNumberFor<T>>,
+		) -> T::Hash {
+			T::Hashing::hash_of(&(who, message_hash, granted_at, expires_at))
+		}
+
+		/// Append an entry to `who`'s consent history, dropping the oldest if the log is full.
+		fn record_consent_history(
+			who: &T::AccountId,
+			action: ConsentAction,
+			message_hash: BoundedMessageHash<T>,
+			expires_at: Option<BlockNumberFor<T>>,
+		) {
+			let entry = ConsentHistoryEntry {
+				action,
+				at: frame_system::Pallet::<T>::block_number(),
+				message_hash,
+				expires_at,
+			};
+			<ConsentHistory<T>>::mutate(who, |history| {
+				if history.is_full() {
+					history.remove(0);
+				}
+				// Cannot fail: a slot was freed above if the log was full
+				let _ = history.try_push(entry);
+			});
+		}
+
+		/// Record `who` under `message_hash` in `ConsentByMessage`.
+		fn index_consent_message(
+			who: &T::AccountId,
+			message_hash: &BoundedMessageHash<T>,
+		) -> DispatchResult {
+			<ConsentByMessage<T>>::try_mutate(message_hash, |accounts| -> DispatchResult {
+				if !accounts.contains(who) {
+					accounts
+						.try_push(who.clone())
+						.map_err(|_| Error::<T>::TooManyAccountsForMessage)?;
+				}
+				Ok(())
+			})
+		}
+
+		/// Drop `who` from `message_hash`'s entry in `ConsentByMessage`, unless another of
+		/// `who`'s remaining consents was granted with it.
+		pub(crate) fn unindex_consent_message(who: &T::AccountId, message_hash: &BoundedMessageHash<T>) {
+			if <ConsentRecords<T>>::iter_prefix_values(who)
+				.any(|consent| consent.message_hash == *message_hash)
+			{
+				return;
+			}
+			<ConsentByMessage<T>>::mutate_exists(message_hash, |maybe_accounts| {
+				if let Some(accounts) = maybe_accounts {
+					accounts.retain(|account| account != who);
+					if accounts.is_empty() {
+						*maybe_accounts = None;
+					}
+				}
+			});
+		}
+
+		/// Validate and store a shadow item under `who`.
+		///
+		/// `delegated` submissions come from an agent or session and may not use `who`'s
+		/// reserved slots; `who`'s own submissions consume a reserved slot if one is held.
+		#[allow(clippy::too_many_arguments)]
+		fn do_submit_shadow_item(
+			who: T::AccountId,
+			cid: Vec<u8>,
+			encrypted_key: Vec<u8>,
+			source: Source,
+			metadata: Vec<u8>,
+			encrypted_metadata: Option<Vec<u8>>,
+			delegated: bool,
+			tags: ItemTags<T>,
+			expires_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			// Check consent
+			Self::ensure_not_opted_out(&who)?;
+			Self::ensure_valid_consent(&who, source)?;
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					expires_at > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidItemExpiry
+				);
+			}
+
+			let item_id = Self::next_item_id(&who, &cid);
+			let mut item = Self::build_item(
+				&who,
+				item_id,
+				cid.clone(),
+				encrypted_key,
+				source,
+				metadata,
+				encrypted_metadata,
+			)?;
+			item.tags = tags;
+			item.expires_at = expires_at;
+			Self::ensure_bytes_available(&who, Self::stored_size(&item))?;
+			Self::reserve_item_deposit(&who, item.deposit)?;
+			Self::index_item_tags(&who, item_id, &item.tags)?;
+
+			// Store the item
+			let reserved = <ReservedSlots<T>>::get(&who);
+			Self::try_mutate_items(&who, |items| -> DispatchResult {
+				if delegated {
+					ensure!(
+						(items.len() as u32).saturating_add(reserved) <
+							T::MaxItemsPerAccount::get(),
+						Error::<T>::TooManyItems
+					);
+				}
+				Self::push_item(&who, items, item)
+			})?;
+			Self::note_items_stored(&who, 1);
+			if !delegated {
+				match reserved {
+					0 => {},
+					1 => <ReservedSlots<T>>::remove(&who),
+					_ => <ReservedSlots<T>>::insert(&who, reserved - 1),
+				}
+			}
+
+			<SubmissionsThisBlock<T>>::mutate(|count| count.saturating_inc());
+
+			// Emit event, unless it is summarised at the end of the block
+			if !(T::AggregateEvents::get() && Self::defer_stored_event(&who)) {
+				Self::deposit_event(Event::ShadowItemStored { who, item_id, cid });
+			}
+
+			Ok(())
+		}
+
+		/// Derive a fresh id for an item `who` is about to store.
+		///
+		/// Bumps `who`'s `ItemCounter`, so ids differ even when the same CID is stored twice
+		/// under one account nonce. The block number and extrinsic index are mixed in too, as
+		/// `prune_empty_accounts` restarts the counter of an emptied account.
+		fn next_item_id(who: &T::AccountId, cid: &[u8]) -> T::Hash {
+			let counter = <ItemCounter<T>>::mutate(who, |counter| {
+				*counter = counter.saturating_add(1);
+				*counter
+			});
+			let now = frame_system::Pallet::<T>::block_number();
+			let extrinsic_index = frame_system::Pallet::<T>::extrinsic_index();
+			T::Hashing::hash_of(&(who, counter, cid, now, extrinsic_index))
+		}
+
+		/// Validate a new item's fields and build it under `item_id`.
+		fn build_item(
+			who: &T::AccountId,
+			item_id: T::Hash,
+			cid: Vec<u8>,
+			encrypted_key: Vec<u8>,
+			source: Source,
+			metadata: Vec<u8>,
+			encrypted_metadata: Option<Vec<u8>>,
+		) -> Result<ShadowItem<T>, DispatchError> {
+			// Validate inputs - convert lengths to u32 for comparison
+			ensure!(cid.len() as u32 <= T::MaxCidLength::get(), Error::<T>::CidTooLong);
+			ensure!(encrypted_key.len() as u32 <= T::MaxKeyLength::get(), Error::<T>::KeyTooLong);
+			ensure!(
+				<RegisteredSources<T>>::contains_key(u16::from(source)),
+				Error::<T>::InvalidSource
+			);
+			Self::validate_cid(source, &cid)?;
+
+			// Fall back to the source's default metadata
+			let metadata = if metadata.is_empty() {
+				<SourceMetaTemplate<T>>::get(source)
+					.map(|template| template.into_inner())
+					.unwrap_or_default()
+			} else {
+				metadata
+			};
+			ensure!(metadata.len() as u32 <= Self::metadata_limit(who), Error::<T>::MetadataTooLong);
+			Self::ensure_item_size(&cid, &encrypted_key, &metadata)?;
+
+			// Convert vecs to bounded vecs
+			let bounded_cid = BoundedCid::<T>::try_from(cid)
+				.map_err(|_| Error::<T>::CidTooLong)?;
+			let bounded_key = BoundedKey::<T>::try_from(encrypted_key)
+				.map_err(|_| Error::<T>::KeyTooLong)?;
+			let bounded_metadata = BoundedMetadata::<T>::try_from(metadata)
+				.map_err(|_| Error::<T>::MetadataTooLong)?;
+			let bounded_encrypted_metadata = encrypted_metadata
+				.map(BoundedEncryptedMetadata::<T>::try_from)
+				.transpose()
+				.map_err(|_| Error::<T>::EncryptedMetadataTooLong)?;
+
+			Ok(ShadowItem {
+				id: item_id,
+				cid: bounded_cid,
+				encrypted_key: bounded_key,
+				timestamp: Self::current_timestamp(),
+				source,
+				metadata: bounded_metadata,
+				pinned: false,
+				encrypted_metadata: bounded_encrypted_metadata,
+				tags: Default::default(),
+				deposit: T::DepositPerItem::get(),
+				expires_at: None,
+			})
+		}
+
+		/// Reserve `amount` from `who` for items it is about to store.
+		fn reserve_item_deposit(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			T::Currency::reserve(who, amount)
+				.map_err(|_| Error::<T>::InsufficientDepositBalance.into())
+		}
+
+		/// Count `count` new items towards `who`'s statistics and `TotalItems`.
+		fn note_items_stored(who: &T::AccountId, count: u32) {
+			<AccountStats<T>>::mutate(who, |stats| {
+				stats.lifetime_submitted = stats.lifetime_submitted.saturating_add(count.into());
+				stats.current = stats.current.saturating_add(count);
+			});
+			<TotalItems<T>>::mutate(|total| *total = total.saturating_add(count));
+		}
+
+		/// Count `removed` towards `who`'s statistics and `TotalItems`, and return their
+		/// deposits.
+		pub(crate) fn note_items_deleted(who: &T::AccountId, removed: &[ShadowItem<T>]) {
+			if removed.is_empty() {
+				return;
+			}
+			let deposit = removed
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |total, item| total.saturating_add(item.deposit));
+			T::Currency::unreserve(who, deposit);
+			for item in removed {
+				Self::unindex_item_tags(who, item.id, &item.tags);
+				Self::clear_item_records(who, item.id);
+			}
+			let count = removed.len() as u32;
+			<AccountStats<T>>::mutate(who, |stats| {
+				stats.lifetime_deleted = stats.lifetime_deleted.saturating_add(count.into());
+				stats.current = stats.current.saturating_sub(count);
+			});
+			<TotalItems<T>>::mutate(|total| *total = total.saturating_sub(count));
+		}
+
+		/// Drop the shares, access log, flags, dead-CID reports and transfer offer kept for
+		/// `who`'s item `item_id`, returning each flagger's deposit.
+		fn clear_item_records(who: &T::AccountId, item_id: T::Hash) {
+			for recipient in <ShareRecipients<T>>::take(item_id) {
+				<SharedKeys<T>>::remove(&recipient, item_id);
+			}
+			<AccessLog<T>>::remove(who, item_id);
+			for flag in <Flags<T>>::take(who, item_id) {
+				T::Currency::unreserve(&flag.flagger, flag.deposit);
+			}
+			<DeadCidReports<T>>::remove(item_id);
+			<PendingTransfers<T>>::remove(who, item_id);
+		}
+
+		/// The full storage key of `account`'s `ShadowItems` entry, for raw state queries
+		/// such as `state_getStorage`.
+		pub fn items_storage_key(account: &T::AccountId) -> Vec<u8> {
+			<ShadowItems<T>>::hashed_key_for(account)
+		}
+
+		/// Lifetime and current item counts for `account`.
+		pub fn account_stats(account: &T::AccountId) -> Stats {
+			<AccountStats<T>>::get(account)
+		}
+
+		/// `who`'s items in the current `ShadowItem` layout.
+		pub fn items_of(who: &T::AccountId) -> BoundedVec<ShadowItem<T>, T::MaxItemsPerAccount> {
+			Self::current_items(<ShadowItems<T>>::get(who))
+		}
+
+		fn current_items(
+			stored: BoundedVec<VersionedShadowItem<T>, T::MaxItemsPerAccount>,
+		) -> BoundedVec<ShadowItem<T>, T::MaxItemsPerAccount> {
+			BoundedVec::truncate_from(stored.into_iter().map(VersionedShadowItem::current).collect())
+		}
+
+		/// Mutate `who`'s items in the current layout, storing them back in the latest version
+		/// and updating `AccountBytes` to match.
+		///
+		/// Fails with `AccountStorageFull` if `f` leaves the items larger than both
+		/// `MaxBytesPerAccount` and what they took up before, so shrinking an account that is
+		/// over a lowered cap still succeeds. Nothing is written if `f` returns an error.
+		fn try_mutate_items<R, E: From<Error<T>>>(
+			who: &T::AccountId,
+			f: impl FnOnce(&mut BoundedVec<ShadowItem<T>, T::MaxItemsPerAccount>) -> Result<R, E>,
+		) -> Result<R, E> {
+			Self::try_shrink_items(who, |items| {
+				let before = Self::items_size(items);
+				let result = f(items)?;
+				let after = Self::items_size(items);
+				ensure!(
+					after <= T::MaxBytesPerAccount::get() || after <= before,
+					Error::<T>::AccountStorageFull
+				);
+				Ok(result)
+			})
+		}
+
+		/// [`Self::try_mutate_items`] without the `MaxBytesPerAccount` check, for callers that
+		/// only remove items.
+		fn try_shrink_items<R, E>(
+			who: &T::AccountId,
+			f: impl FnOnce(&mut BoundedVec<ShadowItem<T>, T::MaxItemsPerAccount>) -> Result<R, E>,
+		) -> Result<R, E> {
+			<ShadowItems<T>>::try_mutate(who, |stored| {
+				let mut items = Self::current_items(core::mem::take(stored));
+				let result = f(&mut items)?;
+				let bytes = Self::items_size(&items);
+				match bytes {
+					0 => <AccountBytes<T>>::remove(who),
+					_ => <AccountBytes<T>>::insert(who, bytes),
+				}
+				*stored = BoundedVec::truncate_from(items.into_iter().map(Into::into).collect());
+				Ok(result)
+			})
+		}
+
+		/// The bytes `item` takes up in `ShadowItems`, including the index of the
+		/// `VersionedShadowItem` variant it is stored under.
+		pub fn stored_size(item: &ShadowItem<T>) -> u32 {
+			item.encoded_size().saturating_add(1) as u32
+		}
+
+		/// The bytes `items` take up in `ShadowItems`, as counted in `AccountBytes`.
+		pub(crate) fn items_size(items: &[ShadowItem<T>]) -> u32 {
+			items.iter().fold(0u32, |total, item| total.saturating_add(Self::stored_size(item)))
+		}
+
+		/// Ensure `bytes` more can be stored under `who` within `MaxBytesPerAccount`.
+		///
+		/// [`Self::try_mutate_items`] enforces the cap on every write; this lets callers fail
+		/// before reserving deposits for items that would not fit.
+		fn ensure_bytes_available(who: &T::AccountId, bytes: u32) -> DispatchResult {
+			let total = <AccountBytes<T>>::get(who).saturating_add(bytes);
+			ensure!(total <= T::MaxBytesPerAccount::get(), Error::<T>::AccountStorageFull);
+			Ok(())
+		}
+
+		/// The position of `who`'s item `item_id` in `items`, looked up in `ItemIndex`.
+		fn item_position(
+			who: &T::AccountId,
+			items: &[ShadowItem<T>],
+			item_id: T::Hash,
+		) -> Option<usize> {
+			let index = <ItemIndex<T>>::get(who, item_id)? as usize;
+			items.get(index).filter(|item| item.id == item_id).map(|_| index)
+		}
+
+		/// Append `item` to `who`'s `items`, recording its position in `ItemIndex`.
+		fn push_item(
+			who: &T::AccountId,
+			items: &mut BoundedVec<ShadowItem<T>, T::MaxItemsPerAccount>,
+			item: ShadowItem<T>,
+		) -> DispatchResult {
+			let item_id = item.id;
+			items.try_push(item).map_err(|_| Error::<T>::TooManyItems)?;
+			<ItemIndex<T>>::insert(who, item_id, items.len() as u32 - 1);
+			Ok(())
+		}
+
+		/// Remove `who`'s item `item_id` from `items`, returning it if it was present.
+		///
+		/// The last item is swapped into the freed position and its `ItemIndex` entry
+		/// updated, so item order is not preserved.
+		fn take_item(
+			who: &T::AccountId,
+			items: &mut BoundedVec<ShadowItem<T>, T::MaxItemsPerAccount>,
+			item_id: T::Hash,
+		) -> Option<ShadowItem<T>> {
+			let index = Self::item_position(who, items, item_id)?;
+			<ItemIndex<T>>::remove(who, item_id);
+			let removed = items.swap_remove(index);
+			if let Some(moved) = items.get(index) {
+				<ItemIndex<T>>::insert(who, moved.id, index as u32);
+			}
+			Some(removed)
+		}
+
+		/// Infallible [`Self::try_shrink_items`].
+		fn shrink_items<R>(
+			who: &T::AccountId,
+			f: impl FnOnce(&mut BoundedVec<ShadowItem<T>, T::MaxItemsPerAccount>) -> R,
+		) -> R {
+			match Self::try_shrink_items::<R, core::convert::Infallible>(who, |items| Ok(f(items))) {
+				Ok(result) => result,
+				Err(never) => match never {},
+			}
+		}
+
+		/// Count a stored item towards `who`'s `ItemsStored` summary for this block.
+		///
+		/// Returns `false` if the summary buffer is full, in which case the caller must emit
+		/// `ShadowItemStored` itself.
+		fn defer_stored_event(who: &T::AccountId) -> bool {
+			<PendingItemsStored<T>>::mutate(|pending| {
+				if let Some(count) = pending.get_mut(who) {
+					count.saturating_inc();
+					return true;
+				}
+				pending.try_insert(who.clone(), 1).is_ok()
+			})
+		}
+
+		/// Check `cid` against the configured rules for `source`.
+		pub fn validate_cid(source: Source, cid: &[u8]) -> DispatchResult {
+			ensure!(cid.len() as u32 <= Self::cid_limit(source), Error::<T>::CidTooLong);
+			ensure!(
+				!T::StrictCidValidation::get() || crate::cid::is_well_formed(cid),
+				Error::<T>::InvalidCid
+			);
+			ensure!(T::CidValidator::validate(source, cid), Error::<T>::InvalidCid);
+			Ok(())
+		}
+
+		/// Whether `who` is exempt from submission throttling.
+		pub fn is_trusted(who: &T::AccountId) -> bool {
+			<TrustedSubmitters<T>>::contains_key(who)
+		}
+
+		/// Count `count` items submitted by `submitter` for `who` against `who`'s current
+		/// submission window. A window opens at the first submission and lasts
+		/// `RateLimitWindow` blocks.
+		///
+		/// Items sent by a trusted submitter are neither limited nor counted.
+		fn ensure_within_rate_limit(
+			submitter: &T::AccountId,
+			who: &T::AccountId,
+			count: u32,
+		) -> DispatchResult {
+			if Self::is_trusted(submitter) {
+				return Ok(());
+			}
+			let now = frame_system::Pallet::<T>::block_number();
+			<SubmissionRate<T>>::try_mutate(who, |(window_start, submitted)| {
+				let window_end = window_start.saturating_add(T::RateLimitWindow::get());
+				if *submitted == 0 || now >= window_end {
+					*window_start = now;
+					*submitted = 0;
+				}
+				let total = submitted.saturating_add(count);
+				ensure!(total <= T::MaxSubmissionsPerWindow::get(), Error::<T>::RateLimited);
+				*submitted = total;
+				Ok(())
+			})
+		}
+
+		/// Ensure an item's byte fields fit within `MaxItemTotalBytes` together.
+		fn ensure_item_size(cid: &[u8], encrypted_key: &[u8], metadata: &[u8]) -> DispatchResult {
+			let total =
+				cid.len().saturating_add(encrypted_key.len()).saturating_add(metadata.len());
+			ensure!(total <= T::MaxItemTotalBytes::get() as usize, Error::<T>::ItemTooLarge);
+			Ok(())
+		}
+
+		/// The effective CID length limit for `source`.
+		///
+		/// Uses the source's limit if one is configured, else `MaxCidLength`, never exceeding
+		/// `MaxCidLength`.
+		pub fn cid_limit(source: Source) -> u32 {
+			T::CidLimitForSource::max_cid_length(source)
+				.unwrap_or_else(T::MaxCidLength::get)
+				.min(T::MaxCidLength::get())
+		}
+
+		/// The effective metadata length limit for an account.
+		///
+		/// Uses the account's override if set, else `MaxMetadataLength`, never exceeding
+		/// `MaxMetadataLengthHard`.
+		pub fn metadata_limit(account: &T::AccountId) -> u32 {
+			<AccountMetadataLimit<T>>::get(account)
+				.unwrap_or_else(T::MaxMetadataLength::get)
+				.min(T::MaxMetadataLengthHard::get())
+		}
+
+		/// Compute the difference between a client's known item ids and current storage.
+		///
+		/// Returns the items the client is missing and the known ids that no longer exist.
+		/// Fails with `TooManyKnownIds` if `known_ids` holds more than `MaxItemsPerAccount`
+		/// entries, so that no id is silently left out of the comparison.
+		pub fn items_diff(
+			account: &T::AccountId,
+			known_ids: Vec<T::Hash>,
+		) -> Result<ItemsDiff<T>, DispatchError> {
+			ensure!(
+				known_ids.len() <= T::MaxItemsPerAccount::get() as usize,
+				Error::<T>::TooManyKnownIds
+			);
+			let known: BTreeSet<T::Hash> = known_ids.iter().cloned().collect();
+
+			let items = Self::items_of(account);
+			let current: BTreeSet<T::Hash> = items.iter().map(|item| item.id).collect();
+
+			let added = items.into_iter().filter(|item| !known.contains(&item.id)).collect();
+			let removed = known_ids.into_iter().filter(|id| !current.contains(id)).collect();
+
+			Ok((added, removed))
+		}
+
+		/// Summarise whether `account` can submit an item right now.
+		///
+		/// Unlike [`Self::ensure_valid_consent`], this never touches the consent cache.
+		pub fn submit_readiness(account: &T::AccountId) -> SubmitReadiness<BlockNumberFor<T>> {
+			let current_block = frame_system::Pallet::<T>::block_number();
+			let valid: Vec<_> = <ConsentRecords<T>>::iter_prefix_values(account)
+				.filter(|c| Self::check_consent(c, current_block).is_ok())
+				.map(|c| c.expires_at)
+				.collect();
+
+			let consent_valid = !valid.is_empty();
+			let consent_remaining = if valid.iter().any(Option::is_none) {
+				None
+			} else {
+				valid
+					.into_iter()
+					.flatten()
+					.max()
+					.map(|expires_at| expires_at.saturating_sub(current_block))
+			};
+			let used = <ShadowItems<T>>::decode_len(account).unwrap_or(0) as u32;
+
+			SubmitReadiness {
+				consent_valid,
+				consent_remaining,
+				slots_remaining: T::MaxItemsPerAccount::get().saturating_sub(used),
+			}
+		}
+
+		/// The state of `account`'s consent at the current block.
+		///
+		/// A permanent consent takes precedence, then the unexpired consent expiring last.
+		pub fn consent_status(account: &T::AccountId) -> ConsentStatus<BlockNumberFor<T>> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let records: Vec<_> = <ConsentRecords<T>>::iter_prefix_values(account).collect();
+			if let Some(consent) = records.iter().find(|c| c.expires_at.is_none()) {
+				return ConsentStatus::Permanent { granted_at: consent.granted_at };
+			}
+
+			let expiring = records.iter().filter_map(|c| c.expires_at.map(|e| (c.granted_at, e)));
+			let active = expiring
+				.clone()
+				.filter(|(_, expires_at)| !Self::is_expired(*expires_at, now))
+				.max_by_key(|(_, expires_at)| *expires_at);
+			if let Some((granted_at, expires_at)) = active {
+				return ConsentStatus::Active {
+					granted_at,
+					expires_at,
+					remaining: expires_at.saturating_sub(now),
+				};
+			}
+
+			match expiring.map(|(_, expires_at)| expires_at).max() {
+				Some(expired_at) => ConsentStatus::Expired { expired_at },
+				None => ConsentStatus::None,
+			}
+		}
+
+		/// Total encoded size in bytes of the items stored by `account`.
+		pub fn account_storage_bytes(account: &T::AccountId) -> u64 {
+			<AccountBytes<T>>::get(account).into()
+		}
+
+		/// Up to `limit` of `account`'s item ids, in ascending order, greater than `after`.
+		///
+		/// Pass the last id of the previous page as `after` to continue; `None` starts from the
+		/// beginning. Pages follow id rather than storage order, which deletes rearrange, so
+		/// the cursor stays valid even once its item is deleted.
+		pub fn item_ids_paged(
+			account: &T::AccountId,
+			after: Option<T::Hash>,
+			limit: u32,
+		) -> Vec<T::Hash> {
+			let mut ids: Vec<_> = Self::items_of(account)
+				.into_iter()
+				.map(|item| item.id)
+				.filter(|id| after.is_none_or(|cursor| *id > cursor))
+				.collect();
+			ids.sort();
+			ids.truncate(limit as usize);
+			ids
+		}
+
+		/// Whether `account` already stores an item with exactly `cid`.
+		pub fn has_cid(account: &T::AccountId, cid: &[u8]) -> bool {
+			Self::items_of(account).iter().any(|item| item.cid.as_slice() == cid)
+		}
+
+		/// A `submit_shadow_item` call with zeroed fields of the given lengths, for the
+		/// runtime to estimate its fee with transaction payment.
+		///
+		/// Lengths above their configured maximum are capped, as such a call cannot succeed.
+		pub fn submit_call(
+			cid_len: u32,
+			key_len: u32,
+			metadata_len: u32,
+			source: Source,
+		) -> Call<T> {
+			let metadata_len = metadata_len.min(T::MaxMetadataLengthHard::get());
+			Call::<T>::submit_shadow_item {
+				cid: alloc::vec![0; cid_len.min(T::MaxCidLength::get()) as usize],
+				encrypted_key: alloc::vec![0; key_len.min(T::MaxKeyLength::get()) as usize],
+				source,
+				metadata: alloc::vec![0; metadata_len as usize],
+				expires_at: None,
+			}
+		}
+
+		/// Up to `limit` of `account`'s items whose CID starts with `prefix`, in storage order.
+		pub fn items_by_cid_prefix(
+			account: &T::AccountId,
+			prefix: &[u8],
+			limit: u32,
+		) -> Vec<ShadowItem<T>> {
+			Self::items_of(account)
+				.into_iter()
+				.filter(|item| item.cid.starts_with(prefix))
+				.take(limit as usize)
+				.collect()
+		}
+
+		/// A page of `account`'s items from `source`, in storage order.
+		///
+		/// Skips the first `offset` matching items and returns up to `limit` of the rest, with
+		/// `limit` capped at `MaxItemsPerAccount`. An offset past the end yields an empty page.
+		pub fn items_by_source(
+			account: &T::AccountId,
+			source: Source,
+			offset: u32,
+			limit: u32,
+		) -> Vec<ShadowItem<T>> {
+			let limit = limit.min(T::MaxItemsPerAccount::get());
+			Self::items_of(account)
+				.into_iter()
+				.filter(|item| item.source == source)
+				.skip(offset as usize)
+				.take(limit as usize)
+				.collect()
+		}
+
+		/// The id and timestamp of `account`'s oldest item, if it has any.
+		pub fn oldest_item(account: &T::AccountId) -> Option<(T::Hash, u64)> {
+			Self::items_of(account)
+				.iter()
+				.min_by_key(|item| item.timestamp)
+				.map(|item| (item.id, item.timestamp))
+		}
+
+		/// Number of `account`'s items per source, as `(source, count)` in ascending source
+		/// order. Empty when the account has no items.
+		pub fn source_breakdown(account: &T::AccountId) -> Vec<(Source, u32)> {
+			let mut counts = BTreeMap::<Source, u32>::new();
+			for item in Self::items_of(account) {
+				let count = counts.entry(item.source).or_default();
+				*count = count.saturating_add(1);
+			}
+			counts.into_iter().collect()
+		}
+
+		/// Accounts whose current consent was granted with `message_hash`.
+		pub fn accounts_with_message(message_hash: Vec<u8>) -> Vec<T::AccountId> {
+			BoundedMessageHash::<T>::try_from(message_hash)
+				.map(|hash| <ConsentByMessage<T>>::get(hash).into_inner())
+				.unwrap_or_default()
+		}
+
+		/// Record `item_id` under each of `tags` in `who`'s `TagIndex`.
+		fn index_item_tags(
+			who: &T::AccountId,
+			item_id: T::Hash,
+			tags: &[BoundedTag<T>],
+		) -> DispatchResult {
+			for tag in tags {
+				<TagIndex<T>>::try_mutate(who, tag, |ids| {
+					if ids.contains(&item_id) {
+						return Ok(());
+					}
+					ids.try_push(item_id).map_err(|_| Error::<T>::TooManyItemsWithTag)
+				})?;
+			}
+			Ok(())
+		}
+
+		/// Drop `item_id` from `who`'s `TagIndex` entries for `tags`.
+		fn unindex_item_tags(who: &T::AccountId, item_id: T::Hash, tags: &[BoundedTag<T>]) {
+			for tag in tags {
+				<TagIndex<T>>::mutate_exists(who, tag, |maybe_ids| {
+					if let Some(ids) = maybe_ids {
+						ids.retain(|id| *id != item_id);
+						if ids.is_empty() {
+							*maybe_ids = None;
+						}
+					}
+				});
+			}
+		}
+
+		/// Ids of `account`'s items tagged `tag`, in the order the tag was added.
+		pub fn items_with_tag(account: &T::AccountId, tag: Vec<u8>) -> Vec<T::Hash> {
+			BoundedTag::<T>::try_from(tag)
+				.map(|tag| <TagIndex<T>>::get(account, tag).into_inner())
+				.unwrap_or_default()
+		}
+
+		/// `item`'s metadata as [`ItemMetadata`], if it was stored by `submit_structured_item`
+		/// or otherwise holds exactly one encoded [`ItemMetadata`].
+		pub fn decode_metadata(item: &ShadowItem<T>) -> Option<ItemMetadata<T>> {
+			codec::DecodeAll::decode_all(&mut &item.metadata[..]).ok()
+		}
+
+		/// The key of `item_id` wrapped for `recipient`, if the item is shared with it.
+		pub fn shared_key(recipient: &T::AccountId, item_id: T::Hash) -> Option<Vec<u8>> {
+			<SharedKeys<T>>::get(recipient, item_id).map(|key| key.into_inner())
+		}
+
+		/// Number of shadow items stored across all accounts.
+		pub fn total_items() -> u32 {
+			<TotalItems<T>>::get()
+		}
+
+		/// The current consent records and history log for `account`.
+		pub fn consent_audit(account: &T::AccountId) -> ConsentAudit<T> {
+			ConsentAudit {
+				records: Self::list_consents(account),
+				history: <ConsentHistory<T>>::get(account).into_inner(),
+			}
+		}
+
+		/// `account`'s consent records, expired or not, in id order.
+		pub fn list_consents(
+			account: &T::AccountId,
+		) -> Vec<(ConsentId, ConsentRecord<T, BlockNumberFor<T>>)> {
+			let mut consents: Vec<_> = <ConsentRecords<T>>::iter_prefix(account).collect();
+			consents.sort_by_key(|(consent_id, _)| *consent_id);
+			consents
+		}
+
+		/// Number of accounts holding a consent record, expired or not.
+		///
+		/// Iterates every record; intended for off-chain queries only.
+		pub fn consent_count() -> u32 {
+			let accounts: BTreeSet<_> =
+				<ConsentRecords<T>>::iter_keys().map(|(who, _)| who).collect();
+			accounts.len() as u32
+		}
+
+		/// Check the pallet's storage invariants.
+		///
+		/// Every account's items must decode, number at most `MaxItemsPerAccount` and hold
+		/// fields within their bounds, and `TotalItems`, where set, must equal the number of
+		/// items stored.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), polkadot_sdk::sp_runtime::TryRuntimeError> {
+			let mut total: u32 = 0;
+			for who in <ShadowItems<T>>::iter_keys() {
+				// Decoded unbounded, as an over-full entry would fail to decode as stored
+				let items: Vec<VersionedShadowItem<T>> =
+					polkadot_sdk::frame_support::storage::unhashed::get(
+						&<ShadowItems<T>>::hashed_key_for(&who),
+					)
+					.ok_or("an account's items cannot be decoded")?;
+				ensure!(
+					items.len() as u32 <= T::MaxItemsPerAccount::get(),
+					"an account holds more than MaxItemsPerAccount items"
+				);
+				for item in items.iter().cloned().map(VersionedShadowItem::current) {
+					ensure!(
+						item.cid.len() as u32 <= T::MaxCidLength::get(),
+						"an item's cid is longer than MaxCidLength"
+					);
+					ensure!(
+						item.encrypted_key.len() as u32 <= T::MaxKeyLength::get(),
+						"an item's encrypted key is longer than MaxKeyLength"
+					);
+					ensure!(
+						item.metadata.len() as u32 <= T::MaxMetadataLengthHard::get(),
+						"an item's metadata is longer than MaxMetadataLengthHard"
+					);
+				}
+				total = total.saturating_add(items.len() as u32);
+			}
+
+			if <TotalItems<T>>::exists() {
+				ensure!(
+					<TotalItems<T>>::get() == total,
+					"TotalItems does not match the number of items stored"
+				);
+			}
+
+			Ok(())
+		}
+	}
+
+	/// Drops a reaped account's consents, items and per-account records so they do not
+	/// linger as dead storage.
+	///
+	/// Only work bounded by `MaxConsentsPerAccount` and `MaxItemsPerAccount` is done here.
+	/// The items' index entries and records, the keys shared with the account and its
+	/// sessions are queued in `ReapedItems` and `ReapedAccounts` for `on_idle` to clear.
+	///
+	/// Each dropped consent is reported as `ConsentRevoked` and the items as
+	/// `AllShadowItemsDeleted`, followed by a summary `AccountReaped`. `ConsentNonce` and
+	/// `NextConsentId` are kept so signatures and consent ids from before the reaping are
+	/// never reused, and `OptedOut` so a recreated account stays opted out.
+	impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
+		fn on_killed_account(who: &T::AccountId) {
+			let consents: Vec<_> = <ConsentRecords<T>>::drain_prefix(who).collect();
+			for (consent_id, consent) in &consents {
+				Self::unindex_consent_message(who, &consent.message_hash);
+				T::OnConsentRevoked::on_consent_revoked(who);
+				Self::deposit_event(Event::ConsentRevoked {
+					who: who.clone(),
+					consent_id: *consent_id,
+					reason: RevocationReason::AccountReaped,
+				});
+			}
+			if !consents.is_empty() {
+				Self::invalidate_consent_cache(who);
+			}
+			let items = Self::current_items(<ShadowItems<T>>::take(who));
+			for item in &items {
+				<ReapedItems<T>>::insert(who, item.id, &item.tags);
+			}
+			if !items.is_empty() ||
+				<SharedKeys<T>>::iter_key_prefix(who).next().is_some() ||
+				<Sessions<T>>::iter_key_prefix(who).next().is_some()
+			{
+				<ReapedAccounts<T>>::insert(who, ());
+			}
+			let items_removed = items.len() as u32;
+			<TotalItems<T>>::mutate(|total| *total = total.saturating_sub(items_removed));
+			if items_removed > 0 {
+				Self::deposit_event(Event::AllShadowItemsDeleted {
+					who: who.clone(),
+					count: items_removed,
+				});
+			}
+			<AuthorizedAgents<T>>::remove(who);
+			<ConsentHistory<T>>::remove(who);
+			<AccountBytes<T>>::remove(who);
+			<ReservedSlots<T>>::remove(who);
+			<AccountStats<T>>::remove(who);
+			<RevokedConsents<T>>::remove(who);
+
+			if !consents.is_empty() || items_removed > 0 {
+				Self::deposit_event(Event::AccountReaped { who: who.clone(), items_removed });
+			}
+		}
 	}
 }
\ No newline at end of file
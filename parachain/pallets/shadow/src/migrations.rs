@@ -0,0 +1,1106 @@
+//! Storage migrations for the Shadow pallet.
+
+use super::*;
+use core::marker::PhantomData;
+use frame::prelude::*;
+use polkadot_sdk::frame_support::{
+	migrations::VersionedMigration, storage_alias, traits::UncheckedOnRuntimeUpgrade,
+};
+
+/// Migration to storage version 1, adding the `pinned` flag to shadow items.
+pub mod v1 {
+	use super::*;
+
+	/// A shadow item as stored before version 1.
+	#[derive(Encode, Decode)]
+	pub struct OldShadowItem<T: Config> {
+		pub id: [u8; 32],
+		pub cid: BoundedCid<T>,
+		pub encrypted_key: BoundedKey<T>,
+		pub timestamp: u64,
+		pub source: u8,
+		pub metadata: BoundedMetadata<T>,
+	}
+
+	/// A shadow item as stored at version 1.
+	#[derive(Encode, Decode)]
+	pub struct ShadowItemV1<T: Config> {
+		pub id: [u8; 32],
+		pub cid: BoundedCid<T>,
+		pub encrypted_key: BoundedKey<T>,
+		pub timestamp: u64,
+		pub source: u8,
+		pub metadata: BoundedMetadata<T>,
+		pub pinned: bool,
+	}
+
+	/// `ShadowItems` with its version 1 value type.
+	#[storage_alias]
+	pub type ShadowItems<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<ShadowItemV1<T>, <T as Config>::MaxItemsPerAccount>,
+		ValueQuery,
+	>;
+
+	/// Rewrites every stored item with `pinned: false`.
+	pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ShadowItems::<T>::translate::<BoundedVec<OldShadowItem<T>, T::MaxItemsPerAccount>, _>(
+				|_, old_items| {
+					translated.saturating_inc();
+					let items: Vec<ShadowItemV1<T>> = old_items
+						.into_iter()
+						.map(|old| ShadowItemV1 {
+							id: old.id,
+							cid: old.cid,
+							encrypted_key: old.encrypted_key,
+							timestamp: old.timestamp,
+							source: old.source,
+							metadata: old.metadata,
+							pinned: false,
+						})
+						.collect();
+					Some(BoundedVec::truncate_from(items))
+				},
+			);
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV0ToV1`] gated on the on-chain storage version being 0.
+	pub type MigrateV0ToV1<T> = VersionedMigration<
+		0,
+		1,
+		InnerMigrateV0ToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 2, adding the source scope to consent records.
+pub mod v2 {
+	use super::*;
+
+	/// A consent scope as stored from version 2 until version 18, as raw source ids.
+	pub type ConsentScopeV2<T> = BoundedBTreeSet<u8, <T as Config>::MaxScopeSources>;
+
+	/// A consent record as stored before version 2.
+	#[derive(Encode, Decode)]
+	pub struct OldConsentRecord<T: Config> {
+		pub granted_at: BlockNumberFor<T>,
+		pub expires_at: Option<BlockNumberFor<T>>,
+		pub message_hash: BoundedMessageHash<T>,
+	}
+
+	/// A consent record as stored at version 2.
+	#[derive(Encode, Decode)]
+	pub struct ConsentRecordV2<T: Config> {
+		pub granted_at: BlockNumberFor<T>,
+		pub expires_at: Option<BlockNumberFor<T>>,
+		pub message_hash: BoundedMessageHash<T>,
+		pub scope: ConsentScopeV2<T>,
+	}
+
+	/// `ConsentRecords` with its version 2 value type.
+	#[storage_alias]
+	pub type ConsentRecords<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		ConsentRecordV2<T>,
+		OptionQuery,
+	>;
+
+	/// Rewrites every consent record with an empty (all sources) scope.
+	pub struct InnerMigrateV1ToV2<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ConsentRecords::<T>::translate::<OldConsentRecord<T>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(ConsentRecordV2 {
+					granted_at: old.granted_at,
+					expires_at: old.expires_at,
+					message_hash: old.message_hash,
+					scope: Default::default(),
+				})
+			});
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV1ToV2`] gated on the on-chain storage version being 1.
+	pub type MigrateV1ToV2<T> = VersionedMigration<
+		1,
+		2,
+		InnerMigrateV1ToV2<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 3, adding the re-sign flag to consent records.
+pub mod v3 {
+	use super::*;
+	use super::v2::ConsentRecordV2 as OldConsentRecord;
+	use super::v2::ConsentScopeV2;
+
+	/// A consent record as stored at version 3.
+	#[derive(Encode, Decode)]
+	pub struct ConsentRecordV3<T: Config> {
+		pub granted_at: BlockNumberFor<T>,
+		pub expires_at: Option<BlockNumberFor<T>>,
+		pub message_hash: BoundedMessageHash<T>,
+		pub scope: ConsentScopeV2<T>,
+		pub needs_resign: bool,
+	}
+
+	/// `ConsentRecords` with its version 3 value type.
+	#[storage_alias]
+	pub type ConsentRecords<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		ConsentRecordV3<T>,
+		OptionQuery,
+	>;
+
+	/// Rewrites every consent record with `needs_resign: false`.
+	pub struct InnerMigrateV2ToV3<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV2ToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ConsentRecords::<T>::translate::<OldConsentRecord<T>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(ConsentRecordV3 {
+					granted_at: old.granted_at,
+					expires_at: old.expires_at,
+					message_hash: old.message_hash,
+					scope: old.scope,
+					needs_resign: false,
+				})
+			});
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV2ToV3`] gated on the on-chain storage version being 2.
+	pub type MigrateV2ToV3<T> = VersionedMigration<
+		2,
+		3,
+		InnerMigrateV2ToV3<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 4, adding encrypted metadata to shadow items.
+pub mod v4 {
+	use super::*;
+	use super::v1::ShadowItemV1 as OldShadowItem;
+
+	/// A shadow item as stored at version 4.
+	#[derive(Encode, Decode)]
+	pub struct ShadowItemV4<T: Config> {
+		pub id: [u8; 32],
+		pub cid: BoundedCid<T>,
+		pub encrypted_key: BoundedKey<T>,
+		pub timestamp: u64,
+		pub source: u8,
+		pub metadata: BoundedMetadata<T>,
+		pub pinned: bool,
+		pub encrypted_metadata: Option<BoundedEncryptedMetadata<T>>,
+	}
+
+	/// `ShadowItems` with its version 4 value type.
+	#[storage_alias]
+	pub type ShadowItems<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<ShadowItemV4<T>, <T as Config>::MaxItemsPerAccount>,
+		ValueQuery,
+	>;
+
+	/// Rewrites every stored item with no encrypted metadata.
+	pub struct InnerMigrateV3ToV4<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV3ToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ShadowItems::<T>::translate::<BoundedVec<OldShadowItem<T>, T::MaxItemsPerAccount>, _>(
+				|_, old_items| {
+					translated.saturating_inc();
+					let items: Vec<ShadowItemV4<T>> = old_items
+						.into_iter()
+						.map(|old| ShadowItemV4 {
+							id: old.id,
+							cid: old.cid,
+							encrypted_key: old.encrypted_key,
+							timestamp: old.timestamp,
+							source: old.source,
+							metadata: old.metadata,
+							pinned: old.pinned,
+							encrypted_metadata: None,
+						})
+						.collect();
+					Some(BoundedVec::truncate_from(items))
+				},
+			);
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV3ToV4`] gated on the on-chain storage version being 3.
+	pub type MigrateV3ToV4<T> = VersionedMigration<
+		3,
+		4,
+		InnerMigrateV3ToV4<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 5, storing item ids as `T::Hash` instead of 32 raw bytes.
+pub mod v5 {
+	use super::*;
+	use super::v4::ShadowItemV4 as OldShadowItem;
+
+	/// A shadow item as stored at version 5.
+	#[derive(Encode, Decode)]
+	pub struct ShadowItemV5<T: Config> {
+		pub id: <T as frame_system::Config>::Hash,
+		pub cid: BoundedCid<T>,
+		pub encrypted_key: BoundedKey<T>,
+		pub timestamp: u64,
+		pub source: u8,
+		pub metadata: BoundedMetadata<T>,
+		pub pinned: bool,
+		pub encrypted_metadata: Option<BoundedEncryptedMetadata<T>>,
+	}
+
+	/// `ShadowItems` with its version 5 value type.
+	#[storage_alias]
+	pub type ShadowItems<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<ShadowItemV5<T>, <T as Config>::MaxItemsPerAccount>,
+		ValueQuery,
+	>;
+
+	/// Rewrites every stored item with its id decoded into `T::Hash`.
+	///
+	/// Ids were only ever written from a 32-byte `T::Hash`, so decoding them cannot fail for
+	/// the hashers this pallet could store items under.
+	pub struct InnerMigrateV4ToV5<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV4ToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ShadowItems::<T>::translate::<BoundedVec<OldShadowItem<T>, T::MaxItemsPerAccount>, _>(
+				|_, old_items| {
+					translated.saturating_inc();
+					let items: Vec<ShadowItemV5<T>> = old_items
+						.into_iter()
+						.filter_map(|old| {
+							Some(ShadowItemV5 {
+								id: T::Hash::decode(&mut &old.id[..]).ok()?,
+								cid: old.cid,
+								encrypted_key: old.encrypted_key,
+								timestamp: old.timestamp,
+								source: old.source,
+								metadata: old.metadata,
+								pinned: old.pinned,
+								encrypted_metadata: old.encrypted_metadata,
+							})
+						})
+						.collect();
+					Some(BoundedVec::truncate_from(items))
+				},
+			);
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV4ToV5`] gated on the on-chain storage version being 4.
+	pub type MigrateV4ToV5<T> = VersionedMigration<
+		4,
+		5,
+		InnerMigrateV4ToV5<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 6, tagging every stored item with its layout version.
+///
+/// This is the last migration needed for `ShadowItem` layout changes; later layouts are
+/// handled by [`VersionedShadowItem`].
+pub mod v6 {
+	use super::*;
+	use super::v5::ShadowItemV5 as OldShadowItem;
+
+	/// A shadow item as stored at versions 6 and 7, with its untagged `u8` source.
+	#[derive(Encode, Decode)]
+	pub enum VersionedShadowItemV6<T: Config> {
+		#[codec(index = 0)]
+		V1(OldShadowItem<T>),
+	}
+
+	/// `ShadowItems` with its version 6 value type.
+	#[storage_alias]
+	pub type ShadowItems<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<VersionedShadowItemV6<T>, <T as Config>::MaxItemsPerAccount>,
+		ValueQuery,
+	>;
+
+	/// Rewrites every stored item as [`VersionedShadowItemV6::V1`].
+	pub struct InnerMigrateV5ToV6<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV5ToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ShadowItems::<T>::translate::<BoundedVec<OldShadowItem<T>, T::MaxItemsPerAccount>, _>(
+				|_, old_items| {
+					translated.saturating_inc();
+					let items: Vec<VersionedShadowItemV6<T>> =
+						old_items.into_iter().map(VersionedShadowItemV6::V1).collect();
+					Some(BoundedVec::truncate_from(items))
+				},
+			);
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV5ToV6`] gated on the on-chain storage version being 5.
+	pub type MigrateV5ToV6<T> = VersionedMigration<
+		5,
+		6,
+		InnerMigrateV5ToV6<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Truncation of accounts holding more items than `MaxItemsPerAccount`.
+///
+/// A `BoundedVec` longer than its bound fails to decode, so lowering `MaxItemsPerAccount`
+/// would otherwise leave overflowing accounts reading as empty. Include
+/// [`TruncateToMaxItems`] in the runtime upgrade that lowers the cap; it does not change the
+/// storage version.
+pub mod truncate {
+	use super::*;
+	use polkadot_sdk::frame_support::traits::OnRuntimeUpgrade;
+
+	/// `ShadowItems` read without the `MaxItemsPerAccount` bound.
+	#[storage_alias]
+	pub type ShadowItems<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Vec<VersionedShadowItem<T>>,
+		ValueQuery,
+	>;
+
+	/// Drops the oldest items of every account above `MaxItemsPerAccount`.
+	///
+	/// The `MaxItemsPerAccount` items with the latest timestamps are kept, in their stored
+	/// order, and `ItemIndex` is rewritten for them. Dropped items are deleted as
+	/// `delete_shadow_item` would: their deposits are returned, their tags, shares, flags and
+	/// other records cleared, and `AccountStats`, `AccountBytes` and `TotalItems` updated.
+	/// Emits `MigrationTruncated` for each account that lost items.
+	pub struct TruncateToMaxItems<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for TruncateToMaxItems<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let max = T::MaxItemsPerAccount::get() as usize;
+			let mut reads = 0u64;
+			let mut writes = 0u64;
+			let mut truncated = Vec::new();
+
+			for (who, items) in ShadowItems::<T>::iter() {
+				reads.saturating_inc();
+				if items.len() > max {
+					truncated.push((who, items));
+				}
+			}
+
+			for (who, items) in truncated {
+				let items: Vec<_> = items.into_iter().map(VersionedShadowItem::current).collect();
+				let dropped = items.len() - max;
+
+				// Oldest first, ties broken by stored position
+				let mut by_age: Vec<usize> = (0..items.len()).collect();
+				by_age.sort_by_key(|&index| items[index].timestamp);
+				let mut keep = vec![true; items.len()];
+				for &index in &by_age[..dropped] {
+					keep[index] = false;
+				}
+
+				let (kept, removed): (Vec<_>, Vec<_>) =
+					items.into_iter().zip(keep).partition(|(_, keep)| *keep);
+				let kept: Vec<_> = kept.into_iter().map(|(item, _)| item).collect();
+				let removed: Vec<_> = removed.into_iter().map(|(item, _)| item).collect();
+
+				for item in &removed {
+					ItemIndex::<T>::remove(&who, item.id);
+					// Item records, tag entries and the flags' deposits
+					reads = reads.saturating_add(4 + item.tags.len() as u64);
+					writes = writes.saturating_add(7 + item.tags.len() as u64);
+				}
+				Pallet::<T>::note_items_deleted(&who, &removed);
+				for (index, item) in kept.iter().enumerate() {
+					ItemIndex::<T>::insert(&who, item.id, index as u32);
+				}
+				AccountBytes::<T>::insert(&who, Pallet::<T>::items_size(&kept));
+				ShadowItems::<T>::insert(
+					&who,
+					kept.into_iter().map(Into::into).collect::<Vec<VersionedShadowItem<T>>>(),
+				);
+				// Deposit, stats and total, then the survivors' index entries, bytes and items
+				reads = reads.saturating_add(3);
+				writes = writes.saturating_add(5 + max as u64);
+
+				Pallet::<T>::deposit_event(Event::MigrationTruncated {
+					who,
+					dropped: dropped as u32,
+				});
+			}
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+}
+
+/// Migration to storage version 7, recording the signature scheme on consent records.
+pub mod v7 {
+	use super::*;
+	use super::v3::ConsentRecordV3 as OldConsentRecord;
+	use super::v2::ConsentScopeV2;
+
+	/// A consent record as stored at versions 7 and 8.
+	#[derive(Encode, Decode)]
+	pub struct ConsentRecordV7<T: Config> {
+		pub granted_at: BlockNumberFor<T>,
+		pub expires_at: Option<BlockNumberFor<T>>,
+		pub message_hash: BoundedMessageHash<T>,
+		pub scope: ConsentScopeV2<T>,
+		pub needs_resign: bool,
+		pub signature_scheme: Option<u8>,
+	}
+
+	/// `ConsentRecords` with its version 7 value type.
+	#[storage_alias]
+	pub type ConsentRecords<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		ConsentRecordV7<T>,
+		OptionQuery,
+	>;
+
+	/// Rewrites every consent record as unsigned.
+	pub struct InnerMigrateV6ToV7<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV6ToV7<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ConsentRecords::<T>::translate::<OldConsentRecord<T>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(ConsentRecordV7 {
+					granted_at: old.granted_at,
+					expires_at: old.expires_at,
+					message_hash: old.message_hash,
+					scope: old.scope,
+					needs_resign: old.needs_resign,
+					signature_scheme: None,
+				})
+			});
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV6ToV7`] gated on the on-chain storage version being 6.
+	pub type MigrateV6ToV7<T> = VersionedMigration<
+		6,
+		7,
+		InnerMigrateV6ToV7<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 8, typing each item's source as [`Source`].
+///
+/// `Source` encodes as the same byte as the old `u8` ids, so valid items are unchanged; the
+/// migration only removes items whose source byte names no known platform.
+pub mod v8 {
+	use super::*;
+	use super::v6::VersionedShadowItemV6 as OldVersionedItem;
+
+	/// Rewrites every stored item with a typed source, dropping those with an unknown one.
+	///
+	/// Emits `ShadowItemRemoved` with [`RemovalReason::UnknownSource`] for each dropped item.
+	pub struct InnerMigrateV7ToV8<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV7ToV8<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			let mut dropped = Vec::new();
+
+			ShadowItems::<T>::translate::<BoundedVec<OldVersionedItem<T>, T::MaxItemsPerAccount>, _>(
+				|who, old_items| {
+					translated.saturating_inc();
+					let mut items = Vec::with_capacity(old_items.len());
+					for OldVersionedItem::V1(old) in old_items {
+						match Source::try_from(old.source) {
+							Ok(source) => items.push(VersionedShadowItem::V1(ShadowItemV1 {
+								id: old.id,
+								cid: old.cid,
+								encrypted_key: old.encrypted_key,
+								timestamp: old.timestamp,
+								source,
+								metadata: old.metadata,
+								pinned: old.pinned,
+								encrypted_metadata: old.encrypted_metadata,
+							})),
+							Err(()) => dropped.push((who.clone(), old.id)),
+						}
+					}
+					(!items.is_empty()).then(|| BoundedVec::truncate_from(items))
+				},
+			);
+
+			for (who, item_id) in dropped {
+				Pallet::<T>::deposit_event(Event::ShadowItemRemoved {
+					who,
+					item_id,
+					reason: RemovalReason::UnknownSource,
+				});
+			}
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV7ToV8`] gated on the on-chain storage version being 7.
+	pub type MigrateV7ToV8<T> = VersionedMigration<
+		7,
+		8,
+		InnerMigrateV7ToV8<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 9, adding the `cosigned` flag to consent records.
+pub mod v9 {
+	use super::*;
+	use super::v7::ConsentRecordV7 as OldConsentRecord;
+	use super::v2::ConsentScopeV2;
+
+	/// A consent record as stored at versions 9 and 10.
+	#[derive(Encode, Decode)]
+	pub struct ConsentRecordV9<T: Config> {
+		pub granted_at: BlockNumberFor<T>,
+		pub expires_at: Option<BlockNumberFor<T>>,
+		pub message_hash: BoundedMessageHash<T>,
+		pub scope: ConsentScopeV2<T>,
+		pub needs_resign: bool,
+		pub signature_scheme: Option<u8>,
+		pub cosigned: bool,
+	}
+
+	/// `ConsentRecords` with its version 9 value type.
+	#[storage_alias]
+	pub type ConsentRecords<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		ConsentRecordV9<T>,
+		OptionQuery,
+	>;
+
+	/// Rewrites every consent record as not co-signed.
+	pub struct InnerMigrateV8ToV9<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV8ToV9<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ConsentRecords::<T>::translate::<OldConsentRecord<T>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(ConsentRecordV9 {
+					granted_at: old.granted_at,
+					expires_at: old.expires_at,
+					message_hash: old.message_hash,
+					scope: old.scope,
+					needs_resign: old.needs_resign,
+					signature_scheme: old.signature_scheme,
+					cosigned: false,
+				})
+			});
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV8ToV9`] gated on the on-chain storage version being 8.
+	pub type MigrateV8ToV9<T> = VersionedMigration<
+		8,
+		9,
+		InnerMigrateV8ToV9<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 10, seeding `AccountStats` from the items already stored.
+pub mod v10 {
+	use super::*;
+
+	/// Records each account's current items as both its lifetime and current count.
+	///
+	/// Deletions made before this version were not tracked, so `lifetime_deleted` starts at 0.
+	pub struct InnerMigrateV9ToV10<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV9ToV10<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut accounts = 0u64;
+
+			for who in ShadowItems::<T>::iter_keys() {
+				accounts.saturating_inc();
+				let current = ShadowItems::<T>::decode_len(&who).unwrap_or_default() as u32;
+				if current > 0 {
+					AccountStats::<T>::insert(
+						&who,
+						Stats { lifetime_submitted: current.into(), lifetime_deleted: 0, current },
+					);
+				}
+			}
+
+			T::DbWeight::get().reads_writes(accounts, accounts)
+		}
+	}
+
+	/// [`InnerMigrateV9ToV10`] gated on the on-chain storage version being 9.
+	pub type MigrateV9ToV10<T> = VersionedMigration<
+		9,
+		10,
+		InnerMigrateV9ToV10<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 11, adding a receipt to each consent record.
+pub mod v11 {
+	use super::*;
+	use super::v9::ConsentRecordV9 as OldConsentRecord;
+	use super::v2::ConsentScopeV2;
+
+	/// A consent record as stored at version 11, keyed by account alone.
+	#[derive(Encode, Decode)]
+	pub struct ConsentRecordV11<T: Config> {
+		pub granted_at: BlockNumberFor<T>,
+		pub expires_at: Option<BlockNumberFor<T>>,
+		pub message_hash: BoundedMessageHash<T>,
+		pub scope: ConsentScopeV2<T>,
+		pub needs_resign: bool,
+		pub signature_scheme: Option<u8>,
+		pub cosigned: bool,
+		pub receipt: T::Hash,
+	}
+
+	/// `ConsentRecords` with its version 11 key and value types.
+	#[storage_alias]
+	pub type ConsentRecords<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		ConsentRecordV11<T>,
+		OptionQuery,
+	>;
+
+	/// Rewrites every consent record with the receipt its grant would have produced.
+	pub struct InnerMigrateV10ToV11<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV10ToV11<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ConsentRecords::<T>::translate::<OldConsentRecord<T>, _>(|who, old| {
+				translated.saturating_inc();
+				let receipt = Pallet::<T>::consent_receipt(
+					&who,
+					&old.message_hash,
+					old.granted_at,
+					old.expires_at,
+				);
+				Some(ConsentRecordV11 {
+					granted_at: old.granted_at,
+					expires_at: old.expires_at,
+					message_hash: old.message_hash,
+					scope: old.scope,
+					needs_resign: old.needs_resign,
+					signature_scheme: old.signature_scheme,
+					cosigned: old.cosigned,
+					receipt,
+				})
+			});
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV10ToV11`] gated on the on-chain storage version being 10.
+	pub type MigrateV10ToV11<T> = VersionedMigration<
+		10,
+		11,
+		InnerMigrateV10ToV11<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 12, keying consent records by account and consent id.
+pub mod v12 {
+	use super::*;
+	use super::v11::ConsentRecordV11;
+
+	/// `ConsentRecords` with its version 12 value type, unchanged from version 11.
+	#[storage_alias]
+	pub type ConsentRecords<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Twox64Concat,
+		ConsentId,
+		ConsentRecordV11<T>,
+		OptionQuery,
+	>;
+
+	/// Moves each account's single consent record to consent id 0.
+	pub struct InnerMigrateV11ToV12<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV11ToV12<T> {
+		fn on_runtime_upgrade() -> Weight {
+			// Both layouts share a prefix, so drain the old entries before writing new ones
+			let old: Vec<_> = v11::ConsentRecords::<T>::drain().collect();
+			let moved = old.len() as u64;
+
+			for (who, old) in old {
+				ConsentRecords::<T>::insert(&who, 0, old);
+				NextConsentId::<T>::insert(&who, 1);
+			}
+			ConsentPruneCursor::<T>::kill();
+
+			T::DbWeight::get().reads_writes(moved, moved.saturating_mul(3).saturating_add(1))
+		}
+	}
+
+	/// [`InnerMigrateV11ToV12`] gated on the on-chain storage version being 11.
+	pub type MigrateV11ToV12<T> = VersionedMigration<
+		11,
+		12,
+		InnerMigrateV11ToV12<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 13, seeding the `TotalItems` counter.
+pub mod v13 {
+	use super::*;
+
+	/// Sets `TotalItems` to the number of items stored across all accounts.
+	pub struct InnerMigrateV12ToV13<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV12ToV13<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut accounts = 0u64;
+			let mut total = 0u32;
+
+			for who in ShadowItems::<T>::iter_keys() {
+				accounts.saturating_inc();
+				let count = ShadowItems::<T>::decode_len(&who).unwrap_or_default() as u32;
+				total = total.saturating_add(count);
+			}
+			TotalItems::<T>::put(total);
+
+			T::DbWeight::get().reads_writes(accounts, 1)
+		}
+	}
+
+	/// [`InnerMigrateV12ToV13`] gated on the on-chain storage version being 12.
+	pub type MigrateV12ToV13<T> = VersionedMigration<
+		12,
+		13,
+		InnerMigrateV12ToV13<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 14, seeding the `ItemIndex` of item positions.
+pub mod v14 {
+	use super::*;
+
+	/// Records the position of every stored item in `ItemIndex`.
+	pub struct InnerMigrateV13ToV14<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV13ToV14<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut accounts = 0u64;
+			let mut indexed = 0u64;
+
+			for (who, items) in ShadowItems::<T>::iter() {
+				accounts.saturating_inc();
+				for (index, item) in items.into_iter().enumerate() {
+					ItemIndex::<T>::insert(&who, item.current().id, index as u32);
+					indexed.saturating_inc();
+				}
+			}
+
+			T::DbWeight::get().reads_writes(accounts, indexed)
+		}
+	}
+
+	/// [`InnerMigrateV13ToV14`] gated on the on-chain storage version being 13.
+	pub type MigrateV13ToV14<T> = VersionedMigration<
+		13,
+		14,
+		InnerMigrateV13ToV14<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 15, adding a delegate to each consent record.
+pub mod v15 {
+	use super::*;
+	use super::v11::ConsentRecordV11 as OldConsentRecord;
+	use super::v2::ConsentScopeV2;
+
+	/// A consent record as stored from version 15 until version 18.
+	#[derive(Encode, Decode)]
+	pub struct ConsentRecordV15<T: Config> {
+		pub granted_at: BlockNumberFor<T>,
+		pub expires_at: Option<BlockNumberFor<T>>,
+		pub message_hash: BoundedMessageHash<T>,
+		pub scope: ConsentScopeV2<T>,
+		pub needs_resign: bool,
+		pub signature_scheme: Option<u8>,
+		pub cosigned: bool,
+		pub receipt: T::Hash,
+		pub delegate: Option<<T as frame_system::Config>::AccountId>,
+	}
+
+	/// `ConsentRecords` with its version 15 value type.
+	#[storage_alias]
+	pub type ConsentRecords<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Twox64Concat,
+		ConsentId,
+		ConsentRecordV15<T>,
+		OptionQuery,
+	>;
+
+	/// Rewrites every consent record with no delegate.
+	pub struct InnerMigrateV14ToV15<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV14ToV15<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+
+			ConsentRecords::<T>::translate::<OldConsentRecord<T>, _>(|_, _, old| {
+				translated.saturating_inc();
+				Some(ConsentRecordV15 {
+					granted_at: old.granted_at,
+					expires_at: old.expires_at,
+					message_hash: old.message_hash,
+					scope: old.scope,
+					needs_resign: old.needs_resign,
+					signature_scheme: old.signature_scheme,
+					cosigned: old.cosigned,
+					receipt: old.receipt,
+					delegate: None,
+				})
+			});
+
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// [`InnerMigrateV14ToV15`] gated on the on-chain storage version being 14.
+	pub type MigrateV14ToV15<T> = VersionedMigration<
+		14,
+		15,
+		InnerMigrateV14ToV15<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 16, seeding `AccountBytes` with each account's item sizes.
+pub mod v16 {
+	use super::*;
+
+	/// Records the total size of every account's items, as stored in the current layout.
+	pub struct InnerMigrateV15ToV16<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV15ToV16<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut accounts = 0u64;
+			let mut seeded = 0u64;
+
+			for (who, items) in ShadowItems::<T>::iter() {
+				accounts.saturating_inc();
+				let bytes = items.into_iter().fold(0u32, |total, item| {
+					total.saturating_add(Pallet::<T>::stored_size(&item.current()))
+				});
+				if bytes > 0 {
+					AccountBytes::<T>::insert(&who, bytes);
+					seeded.saturating_inc();
+				}
+			}
+
+			T::DbWeight::get().reads_writes(accounts, seeded)
+		}
+	}
+
+	/// [`InnerMigrateV15ToV16`] gated on the on-chain storage version being 15.
+	pub type MigrateV15ToV16<T> = VersionedMigration<
+		15,
+		16,
+		InnerMigrateV15ToV16<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 17, registering the sources submissions were accepted from.
+pub mod v17 {
+	use super::*;
+
+	/// Registers every source, so the registry accepts all that was accepted before.
+	pub struct InnerMigrateV16ToV17<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV16ToV17<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut registered = 0u64;
+
+			for source in Source::ALL {
+				// A name that does not fit is truncated rather than left unregistered
+				RegisteredSources::<T>::insert(
+					u16::from(source),
+					BoundedVec::truncate_from(source.name().to_vec()),
+				);
+				registered.saturating_inc();
+			}
+
+			T::DbWeight::get().writes(registered)
+		}
+	}
+
+	/// [`InnerMigrateV16ToV17`] gated on the on-chain storage version being 16.
+	pub type MigrateV16ToV17<T> = VersionedMigration<
+		16,
+		17,
+		InnerMigrateV16ToV17<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migration to storage version 18, storing consent scopes as [`Source`]s.
+pub mod v18 {
+	use super::*;
+	use super::v15::ConsentRecordV15 as OldConsentRecord;
+
+	/// Rewrites every consent scope as the sources its ids name.
+	///
+	/// Scopes were only ever built from [`Source`]s, so an unknown id should not occur. Should
+	/// one be dropped and leave a scope empty, the consent is removed rather than widened to
+	/// every source.
+	pub struct InnerMigrateV17ToV18<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV17ToV18<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			let mut removed = Vec::new();
+
+			ConsentRecords::<T>::translate::<OldConsentRecord<T>, _>(|who, consent_id, old| {
+				translated.saturating_inc();
+				let sources: BTreeSet<Source> =
+					old.scope.iter().filter_map(|id| Source::try_from(*id).ok()).collect();
+				if sources.is_empty() && !old.scope.is_empty() {
+					removed.push((who, consent_id, old.message_hash));
+					return None;
+				}
+				Some(ConsentRecord {
+					granted_at: old.granted_at,
+					expires_at: old.expires_at,
+					message_hash: old.message_hash,
+					// No more sources than ids, so the bound still holds
+					scope: BoundedBTreeSet::try_from(sources).unwrap_or_default(),
+					needs_resign: old.needs_resign,
+					signature_scheme: old.signature_scheme,
+					cosigned: old.cosigned,
+					receipt: old.receipt,
+					delegate: old.delegate,
+				})
+			});
+
+			let dropped = removed.len() as u64;
+			for (who, consent_id, message_hash) in removed {
+				Pallet::<T>::unindex_consent_message(&who, &message_hash);
+				Pallet::<T>::deposit_event(Event::ConsentRevoked {
+					who,
+					consent_id,
+					reason: RevocationReason::UnknownSource,
+				});
+			}
+
+			T::DbWeight::get()
+				.reads_writes(translated, translated)
+				.saturating_add(T::DbWeight::get().reads_writes(dropped, dropped))
+		}
+	}
+
+	/// [`InnerMigrateV17ToV18`] gated on the on-chain storage version being 17.
+	pub type MigrateV17ToV18<T> = VersionedMigration<
+		17,
+		18,
+		InnerMigrateV17ToV18<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
@@ -1,12 +1,12 @@
 //! Test environment for Shadow pallet
 
 use crate as pallet_shadow;
-use sp_runtime::BuildStorage;
-use frame::{
-	deps::{frame_support::weights::IdentityFee, frame_system as system, sp_core::ConstU32},
-	runtime::prelude::*,
-	traits::VariantCount,
+use polkadot_sdk::{
+	sp_core::{sr25519, Pair},
+	sp_keystore::{testing::MemoryKeystore, KeystoreExt},
 };
+use sp_runtime::{traits::Convert, BuildStorage, DispatchResult};
+use frame::runtime::prelude::*;
 
 // Configure a mock runtime to test the pallet.
 #[frame_support::runtime]
@@ -33,6 +33,9 @@ mod test_runtime {
 	
 	#[runtime::pallet_index(2)]
 	pub type Shadow = pallet_shadow;
+
+	#[runtime::pallet_index(3)]
+	pub type Utility = pallet_utility;
 }
 
 parameter_types! {
@@ -43,6 +46,7 @@ parameter_types! {
 impl frame_system::Config for Test {
 	type Block = Block<Self>;
 	type AccountData = pallet_balances::AccountData<u64>;
+	type OnKilledAccount = Shadow;
 }
 
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
@@ -50,28 +54,321 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 }
 
+impl pallet_utility::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const MaxItemsPerAccount: u32 = 100;
+	pub const MaxBatchSize: u32 = 5;
+	pub const RateLimitWindow: u64 = 10;
+	pub const MaxConsentPrunedPerBlock: u32 = 2;
+	pub const MaxAccountsExpiredPerBlock: u32 = 5;
+	pub const MaxConsentsPerAccount: u32 = 3;
 	pub const MaxCidLength: u32 = 100;
 	pub const MaxKeyLength: u32 = 512;
 	pub const MaxMetadataLength: u32 = 256;
+	pub const MaxMetadataLengthHard: u32 = 1024;
 	pub const MaxMessageHashLength: u32 = 64;
+	pub const MaxSourceNameLen: u32 = 16;
+	pub const MaxSessionDuration: u64 = 100;
+	pub const MaxConsentCacheEntries: u32 = 16;
+	pub const MaxAgents: u32 = 4;
+	pub const MaxSharesPerItem: u32 = 2;
+	pub const MaxAccessLogEntries: u32 = 2;
+	pub const MaxScopeSources: u32 = 4;
+	pub const MaxTagsPerItem: u32 = 2;
+	pub const MaxTagLength: u32 = 16;
+	pub const MaxItemsPerTag: u32 = 3;
+	pub const MaxAggregatedAccounts: u32 = 2;
+	pub static MinBalanceForConsent: u64 = 0;
+	pub static EnforceGitHubCidV0: bool = false;
+	pub static GitHubCidLimit: Option<u32> = None;
+	pub static AggregateEvents: bool = false;
+	pub static EnforceTemplates: bool = false;
+	pub const MaxAccountsPerMessage: u32 = 2;
+	pub static ConsentExpiryInclusive: bool = true;
+	pub const DeadCidThreshold: u32 = 2;
+	pub const MaxEncMetaLength: u32 = 128;
+	pub const MaxConsentHistory: u32 = 3;
+	pub const FlagDeposit: u64 = 10;
+	pub const FlagReviewThreshold: u32 = 2;
+	pub const MaxFlagsPerItem: u32 = 3;
+	pub static NormalizeMessageHash: bool = false;
+	pub static ExpiredPolicy: pallet_shadow::ExpiredConsentPolicy =
+		pallet_shadow::ExpiredConsentPolicy::Reject;
+	pub static SignerKeys: Vec<(u64, [u8; 32])> = Vec::new();
+	pub static MaxItemTotalBytes: u32 = 100 + 512 + 1024;
+	pub static MaxBytesPerAccount: u32 = u32::MAX;
+	pub static ComplianceAccount: Option<u64> = Some(99);
+	pub static RequireCosignedConsent: bool = false;
+	pub static ClearItemsOnOptOut: bool = false;
+	pub static DepositPerItem: u64 = 0;
+	pub static RevokedConsentCount: u32 = 0;
+	pub static StrictCidValidation: bool = false;
+	pub static RequireRecipientConsent: bool = true;
+	pub static MaxSubmissionsPerWindow: u32 = u32::MAX;
+}
+
+/// Looks accounts up in `SignerKeys`; unregistered accounts use their `account_pair` key.
+pub struct MockAccountPublicKey;
+impl Convert<u64, [u8; 32]> for MockAccountPublicKey {
+	fn convert(who: u64) -> [u8; 32] {
+		SignerKeys::get()
+			.into_iter()
+			.find_map(|(account, key)| (account == who).then_some(key))
+			.unwrap_or_else(|| account_pair(who).public().0)
+	}
+}
+
+/// Registers benchmark keys in `SignerKeys` under fresh accounts from 1000 up.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_shadow::BenchmarkHelper<u64> for MockBenchmarkHelper {
+	fn account_for_key(public: [u8; 32]) -> u64 {
+		let account = 1_000 + SignerKeys::get().len() as u64;
+		SignerKeys::mutate(|keys| keys.push((account, public)));
+		account
+	}
+}
+
+/// Accounts 10, 11 and 12 act as the trusted dead-CID oracles.
+pub struct DeadCidReporters;
+impl frame_support::traits::SortedMembers<u64> for DeadCidReporters {
+	fn sorted_members() -> Vec<u64> {
+		vec![10, 11, 12]
+	}
+}
+
+/// Requires CIDv0 (`Qm...`, 46 chars) for GitHub items when enabled; accepts anything else.
+pub struct MockCidValidator;
+impl pallet_shadow::CidValidator for MockCidValidator {
+	fn validate(source: pallet_shadow::Source, cid: &[u8]) -> bool {
+		if source == pallet_shadow::Source::GitHub && EnforceGitHubCidV0::get() {
+			return cid.len() == 46 && cid.starts_with(b"Qm");
+		}
+		true
+	}
+}
+
+/// Applies `GitHubCidLimit` to GitHub items; other sources use `MaxCidLength`.
+pub struct MockCidLimit;
+impl pallet_shadow::CidLimitForSource for MockCidLimit {
+	fn max_cid_length(source: pallet_shadow::Source) -> Option<u32> {
+		if source == pallet_shadow::Source::GitHub {
+			GitHubCidLimit::get()
+		} else {
+			None
+		}
+	}
+}
+
+/// Counts the consents lost across all accounts in `RevokedConsentCount`.
+pub struct CountRevocations;
+impl pallet_shadow::OnConsentRevoked<u64> for CountRevocations {
+	fn on_consent_revoked(_who: &u64) {
+		RevokedConsentCount::mutate(|count| *count += 1);
+	}
 }
 
 impl pallet_shadow::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 	type MaxItemsPerAccount = MaxItemsPerAccount;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxSubmissionsPerWindow = MaxSubmissionsPerWindow;
+	type RateLimitWindow = RateLimitWindow;
+	type MaxConsentPrunedPerBlock = MaxConsentPrunedPerBlock;
+	type MaxAccountsExpiredPerBlock = MaxAccountsExpiredPerBlock;
+	type MaxConsentsPerAccount = MaxConsentsPerAccount;
+	type ClearItemsOnOptOut = ClearItemsOnOptOut;
+	type DepositPerItem = DepositPerItem;
+	type OnConsentRevoked = CountRevocations;
 	type MaxCidLength = MaxCidLength;
 	type MaxKeyLength = MaxKeyLength;
 	type MaxMetadataLength = MaxMetadataLength;
+	type MaxMetadataLengthHard = MaxMetadataLengthHard;
 	type MaxMessageHashLength = MaxMessageHashLength;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type RegisterOrigin = frame_system::EnsureRoot<u64>;
+	type MaxSourceNameLen = MaxSourceNameLen;
+	type MaxSessionDuration = MaxSessionDuration;
+	type MaxConsentCacheEntries = MaxConsentCacheEntries;
+	type MaxAgents = MaxAgents;
+	type Currency = Balances;
+	type MinBalanceForConsent = MinBalanceForConsent;
+	type CidValidator = MockCidValidator;
+	type CidLimitForSource = MockCidLimit;
+	type StrictCidValidation = StrictCidValidation;
+	type MaxSharesPerItem = MaxSharesPerItem;
+	type MaxAccessLogEntries = MaxAccessLogEntries;
+	type MaxScopeSources = MaxScopeSources;
+	type MaxTagsPerItem = MaxTagsPerItem;
+	type MaxTagLength = MaxTagLength;
+	type MaxItemsPerTag = MaxItemsPerTag;
+	type AggregateEvents = AggregateEvents;
+	type MaxAggregatedAccounts = MaxAggregatedAccounts;
+	type EnforceTemplates = EnforceTemplates;
+	type MaxAccountsPerMessage = MaxAccountsPerMessage;
+	type ConsentExpiryInclusive = ConsentExpiryInclusive;
+	type ReporterOrigin = frame_system::EnsureSignedBy<DeadCidReporters, u64>;
+	type DeadCidThreshold = DeadCidThreshold;
+	type MaxEncMetaLength = MaxEncMetaLength;
+	type MaxConsentHistory = MaxConsentHistory;
+	type FlagDeposit = FlagDeposit;
+	type FlagReviewThreshold = FlagReviewThreshold;
+	type MaxFlagsPerItem = MaxFlagsPerItem;
+	type NormalizeMessageHash = NormalizeMessageHash;
+	type ExpiredConsentPolicy = ExpiredPolicy;
+	type AccountPublicKey = MockAccountPublicKey;
+	type MaxItemTotalBytes = MaxItemTotalBytes;
+	type MaxBytesPerAccount = MaxBytesPerAccount;
+	type ComplianceKey = ComplianceAccount;
+	type RequireCosignedConsent = RequireCosignedConsent;
+	type RequireRecipientConsent = RequireRecipientConsent;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockBenchmarkHelper;
+}
+
+/// The sr25519 key pair `who` signs with unless `SignerKeys` registers another key.
+pub fn account_pair(who: u64) -> sr25519::Pair {
+	sr25519::Pair::from_seed(&sp_io::hashing::blake2_256(&who.to_le_bytes()))
+}
+
+/// `who`'s sr25519 signature authorizing `action`, valid until the current block.
+pub fn sign_consent(
+	who: u64,
+	action: pallet_shadow::ConsentAuthorization<Test>,
+) -> pallet_shadow::ConsentSignature<u64> {
+	sign_consent_with(&account_pair(who), who, action)
+}
+
+/// `pair`'s sr25519 signature authorizing `action` for `who`, valid until the current block.
+pub fn sign_consent_with(
+	pair: &sr25519::Pair,
+	who: u64,
+	action: pallet_shadow::ConsentAuthorization<Test>,
+) -> pallet_shadow::ConsentSignature<u64> {
+	let valid_until = System::block_number();
+	let payload = Shadow::consent_payload(&who, action, valid_until);
+	let signature = pair.sign(&payload).0.to_vec();
+	pallet_shadow::ConsentSignature {
+		valid_until,
+		scheme: pallet_shadow::signature_scheme::SR25519,
+		signature,
+	}
+}
+
+/// `who`'s signature over a grant of `message_hash` for `duration`, covering all sources
+/// without a delegate.
+pub fn consent_signature(
+	who: u64,
+	message_hash: &[u8],
+	duration: Option<u64>,
+) -> pallet_shadow::ConsentSignature<u64> {
+	sign_consent(
+		who,
+		pallet_shadow::ConsentAuthorization::Grant {
+			message_hash: message_hash.to_vec(),
+			duration,
+			scope: Default::default(),
+			delegate: None,
+		},
+	)
+}
+
+/// Grant consent as `who`, signed with `who`'s `account_pair` key.
+pub fn grant_consent_as(who: u64, message_hash: Vec<u8>, duration: Option<u64>) -> DispatchResult {
+	let signature = consent_signature(who, &message_hash, duration);
+	Shadow::grant_consent(
+		RuntimeOrigin::signed(who),
+		message_hash,
+		duration,
+		vec![],
+		None,
+		signature,
+		who,
+	)
+}
+
+/// Grant consent as `who` naming `delegate` as the account that may submit for it.
+pub fn grant_delegated_consent_as(who: u64, message_hash: Vec<u8>, delegate: u64) -> DispatchResult {
+	let signature = sign_consent(
+		who,
+		pallet_shadow::ConsentAuthorization::Grant {
+			message_hash: message_hash.clone(),
+			duration: None,
+			scope: Default::default(),
+			delegate: Some(delegate),
+		},
+	);
+	Shadow::grant_consent(
+		RuntimeOrigin::signed(who),
+		message_hash,
+		None,
+		vec![],
+		Some(delegate),
+		signature,
+		who,
+	)
+}
+
+/// Grant consent as `who` covering only `scope`, signed with `who`'s `account_pair` key.
+pub fn grant_scoped_consent_as(
+	who: u64,
+	message_hash: Vec<u8>,
+	scope: Vec<pallet_shadow::Source>,
+) -> DispatchResult {
+	let signature = sign_consent(
+		who,
+		pallet_shadow::ConsentAuthorization::Grant {
+			message_hash: message_hash.clone(),
+			duration: None,
+			// An oversized scope is refused before its signature is checked
+			scope: scope
+				.iter()
+				.copied()
+				.collect::<std::collections::BTreeSet<_>>()
+				.try_into()
+				.unwrap_or_default(),
+			delegate: None,
+		},
+	);
+	Shadow::grant_consent(
+		RuntimeOrigin::signed(who),
+		message_hash,
+		None,
+		scope,
+		None,
+		signature,
+		who,
+	)
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let t = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let t = RuntimeGenesisConfig::default().build_storage().unwrap();
 	let mut ext = sp_io::TestExternalities::new(t);
+	ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
 	ext.execute_with(|| System::set_block_number(1));
 	ext
-}
\ No newline at end of file
+}
+
+// Build genesis storage with the shadow pallet disabled.
+pub fn new_disabled_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with_genesis(pallet_shadow::GenesisConfig { enabled: false, ..Default::default() })
+}
+
+// Build genesis storage from the given shadow pallet genesis config.
+pub fn new_test_ext_with_genesis(
+	shadow: pallet_shadow::GenesisConfig<Test>,
+) -> sp_io::TestExternalities {
+	let t = RuntimeGenesisConfig { shadow, ..Default::default() }.build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,115 @@
+//! Runtime API definition for the Shadow pallet.
+
+use alloc::vec::Vec;
+use codec::Codec;
+use polkadot_sdk::sp_runtime::DispatchError;
+
+use crate::{ConsentStatus, Source, Stats, SubmitReadiness};
+
+polkadot_sdk::sp_api::decl_runtime_apis! {
+	/// Read-only queries over shadow items stored by the pallet.
+	pub trait ShadowApi<AccountId, Hash, Item, BlockNumber, Audit, Balance, Consent>
+	where
+		AccountId: Codec,
+		Hash: Codec,
+		Item: Codec,
+		BlockNumber: Codec,
+		Audit: Codec,
+		Balance: Codec,
+		Consent: Codec,
+	{
+		/// All items currently stored by `account`, in storage order.
+		fn shadow_items(account: AccountId) -> Vec<Item>;
+
+		/// Whether `account`'s consent is permanent, active (with the blocks it has left) or
+		/// expired, so a wallet can show it next to the items.
+		fn consent_status(account: AccountId) -> ConsentStatus<BlockNumber>;
+
+		/// Compare the ids a client already knows against current storage.
+		///
+		/// Returns `(added, removed)`: items the client is missing and known ids that no
+		/// longer exist on-chain. Fails with `TooManyKnownIds` for more than
+		/// `MaxItemsPerAccount` known ids; an account never holds more items than that.
+		fn items_diff(
+			account: AccountId,
+			known_ids: Vec<Hash>,
+		) -> Result<(Vec<Item>, Vec<Hash>), DispatchError>;
+
+		/// Consent and capacity status for `account`, so a wallet can check both in one call
+		/// before submitting.
+		fn submit_readiness(account: AccountId) -> SubmitReadiness<BlockNumber>;
+
+		/// Total encoded size in bytes of the items stored by `account`.
+		fn account_storage_bytes(account: AccountId) -> u64;
+
+		/// Up to `limit` of `account`'s item ids, in ascending order, following the `after`
+		/// cursor.
+		///
+		/// Pass the last id of the previous page to continue, or `None` for the first page.
+		/// The cursor stays valid when items, including its own, are deleted between pages.
+		fn item_ids_paged(account: AccountId, after: Option<Hash>, limit: u32) -> Vec<Hash>;
+
+		/// Whether `account` already stores an item with exactly `cid`, so a client can skip
+		/// uploading and submitting it again.
+		fn has_cid(account: AccountId, cid: Vec<u8>) -> bool;
+
+		/// Up to `limit` of `account`'s items whose CID starts with `prefix`, in storage order.
+		fn items_by_cid_prefix(account: AccountId, prefix: Vec<u8>, limit: u32) -> Vec<Item>;
+
+		/// A page of `account`'s items from `source`: up to `limit` (capped at
+		/// `MaxItemsPerAccount`) after skipping the first `offset` matches.
+		fn items_by_source(
+			account: AccountId,
+			source: Source,
+			offset: u32,
+			limit: u32,
+		) -> Vec<Item>;
+
+		/// The id and timestamp of `account`'s oldest item, if it has any.
+		fn oldest_item(account: AccountId) -> Option<(Hash, u64)>;
+
+		/// Number of `account`'s items per source as `(source, count)`, ascending by source.
+		///
+		/// Empty when the account has no items.
+		fn source_breakdown(account: AccountId) -> Vec<(Source, u32)>;
+
+		/// Accounts whose current consent was granted with `message_hash`.
+		fn accounts_with_message(message_hash: Vec<u8>) -> Vec<AccountId>;
+
+		/// Number of shadow items stored across all accounts.
+		fn total_items() -> u32;
+
+		/// Number of accounts holding a consent record.
+		fn consent_count() -> u32;
+
+		/// `account`'s current consent record together with its grant and revocation history.
+		///
+		/// Meant for off-chain compliance tooling that needs the full consent trail in one call.
+		fn consent_audit(account: AccountId) -> Audit;
+
+		/// Fee of submitting an item whose CID, encrypted key and metadata have the given
+		/// lengths in bytes.
+		///
+		/// Includes the base, length and weight fees of a signed extrinsic, but no tip.
+		fn estimate_submit_fee(
+			cid_len: u32,
+			key_len: u32,
+			metadata_len: u32,
+			source: Source,
+		) -> Balance;
+
+		/// Lifetime submitted and deleted item counts for `account`, with its current count.
+		fn account_stats(account: AccountId) -> Stats;
+
+		/// `account`'s consent records by consent id, expired or not, in id order.
+		fn list_consents(account: AccountId) -> Vec<(u32, Consent)>;
+
+		/// The key of `item_id` wrapped for `recipient` by `share_item`, if it was shared.
+		fn shared_key(recipient: AccountId, item_id: Hash) -> Option<Vec<u8>>;
+
+		/// The full storage key under which `account`'s items are stored.
+		///
+		/// The value at this key is the SCALE-encoded list of the account's versioned items.
+		fn items_storage_key(account: AccountId) -> Vec<u8>;
+	}
+}
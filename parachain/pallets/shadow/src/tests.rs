@@ -2,38 +2,39 @@
 
 use super::*;
 use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use codec::{Compact, Encode};
+use frame_support::{
+	assert_noop, assert_ok, dispatch::GetDispatchInfo, traits::Hooks, weights::Weight, BoundedVec,
+};
+use sp_runtime::DispatchError;
 
 #[test]
 fn submit_shadow_item_works() {
 	new_test_ext().execute_with(|| {
 		// Grant consent first
-		assert_ok!(Shadow::grant_consent(
-			RuntimeOrigin::signed(1),
-			b"test_consent".to_vec(),
-			None
-		));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
 
 		// Submit shadow item
 		assert_ok!(Shadow::submit_shadow_item(
 			RuntimeOrigin::signed(1),
 			b"QmTest123".to_vec(),
 			b"encrypted_key_123".to_vec(),
-			0, // GitHub source
-			b"test metadata".to_vec()
+			Source::GitHub, // GitHub source
+			b"test metadata".to_vec(),
+			None
 		));
 
 		// Check storage
-		let items = ShadowItems::<Test>::get(1);
+		let items = Shadow::items_of(&1);
 		assert_eq!(items.len(), 1);
 		assert_eq!(items[0].cid, b"QmTest123".to_vec());
-		assert_eq!(items[0].source, 0);
+		assert_eq!(items[0].source, Source::GitHub);
 
 		// Check event
 		System::assert_last_event(
 			Event::ShadowItemStored {
 				who: 1,
-				item_id: items[0].id.clone().try_into().unwrap(),
+				item_id: items[0].hash_id(),
 				cid: b"QmTest123".to_vec(),
 			}
 			.into(),
@@ -50,8 +51,9 @@ fn submit_shadow_item_fails_without_consent() {
 				RuntimeOrigin::signed(1),
 				b"QmTest123".to_vec(),
 				b"encrypted_key_123".to_vec(),
-				0,
-				b"test metadata".to_vec()
+				Source::GitHub,
+				b"test metadata".to_vec(),
+				None
 			),
 			Error::<Test>::NoConsent
 		);
@@ -59,59 +61,132 @@ fn submit_shadow_item_fails_without_consent() {
 }
 
 #[test]
-fn submit_shadow_item_fails_with_invalid_source() {
-	new_test_ext().execute_with(|| {
-		// Grant consent
-		assert_ok!(Shadow::grant_consent(
-			RuntimeOrigin::signed(1),
-			b"test_consent".to_vec(),
-			None
-		));
+fn source_converts_from_legacy_ids() {
+	assert_eq!(Source::try_from(0u8), Ok(Source::GitHub));
+	assert_eq!(Source::try_from(1u8), Ok(Source::Twitter));
+	assert_eq!(Source::try_from(4u8), Ok(Source::StackOverflow));
+	assert_eq!(Source::try_from(5u8), Err(()));
 
-		// Try to submit with invalid source
-		assert_noop!(
-			Shadow::submit_shadow_item(
-				RuntimeOrigin::signed(1),
-				b"QmTest123".to_vec(),
-				b"encrypted_key_123".to_vec(),
-				5, // Invalid source
-				b"test metadata".to_vec()
-			),
-			Error::<Test>::InvalidSource
-		);
-	});
+	// The encoding is the legacy id, so stored items and template keys stay readable
+	for id in 0u8..=4 {
+		let source = Source::try_from(id).unwrap();
+		assert_eq!(source.encode(), vec![id]);
+		assert_eq!(u8::from(source), id);
+	}
 }
 
 #[test]
 fn delete_shadow_item_works() {
 	new_test_ext().execute_with(|| {
 		// Grant consent and submit item
-		assert_ok!(Shadow::grant_consent(
-			RuntimeOrigin::signed(1),
-			b"test_consent".to_vec(),
-			None
-		));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
 		assert_ok!(Shadow::submit_shadow_item(
 			RuntimeOrigin::signed(1),
 			b"QmTest123".to_vec(),
 			b"encrypted_key_123".to_vec(),
-			0,
-			b"test metadata".to_vec()
+			Source::GitHub,
+			b"test metadata".to_vec(),
+			None
 		));
 
 		// Get the item ID
-		let items = ShadowItems::<Test>::get(1);
-		let item_id: <Test as frame_system::Config>::Hash = items[0].id.clone().try_into().unwrap();
+		let items = Shadow::items_of(&1);
+		let item_id = items[0].hash_id();
 
-		// Delete the item
-		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), item_id));
+		// Delete the item, charged for the single item held
+		let info = Shadow::delete_shadow_item(RuntimeOrigin::signed(1), item_id).unwrap();
+		assert_eq!(
+			info.actual_weight,
+			Some(<() as crate::weights::WeightInfo>::delete_shadow_item(1))
+		);
 
 		// Check storage
-		let items_after = ShadowItems::<Test>::get(1);
+		let items_after = Shadow::items_of(&1);
 		assert_eq!(items_after.len(), 0);
 
 		// Check event
-		System::assert_last_event(Event::ShadowItemDeleted { who: 1, item_id }.into());
+		System::assert_last_event(
+			Event::ShadowItemRemoved { who: 1, item_id, reason: RemovalReason::UserDeleted }.into(),
+		);
+	});
+}
+
+#[test]
+fn delete_nonexistent_item_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest123".to_vec(),
+			b"encrypted_key_123".to_vec(),
+			Source::GitHub,
+			b"test metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+		System::reset_events();
+
+		// Neither an unknown id nor another account's item can be deleted
+		assert_noop!(
+			Shadow::delete_shadow_item(
+				RuntimeOrigin::signed(1),
+				<Test as frame_system::Config>::Hash::repeat_byte(0xAB)
+			),
+			Error::<Test>::ItemNotFound
+		);
+		assert_noop!(
+			Shadow::delete_shadow_item(RuntimeOrigin::signed(2), item_id),
+			Error::<Test>::ItemNotFound
+		);
+		assert!(System::events().is_empty());
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+	});
+}
+
+/// Every item of `who` is indexed at its position, and nothing else is.
+fn assert_item_index_consistent(who: u64) {
+	let items = Shadow::items_of(&who);
+	for (index, item) in items.iter().enumerate() {
+		assert_eq!(ItemIndex::<Test>::get(who, item.id), Some(index as u32));
+	}
+	assert_eq!(ItemIndex::<Test>::iter_prefix(who).count(), items.len());
+}
+
+#[test]
+fn item_index_follows_swap_remove_on_delete() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(4, "QmIndex")
+		));
+		let ids: Vec<_> = Shadow::items_of(&1).iter().map(|item| item.id).collect();
+		assert_item_index_consistent(1);
+
+		// The last item moves into the deleted middle item's slot
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), ids[1]));
+		let remaining: Vec<_> = Shadow::items_of(&1).iter().map(|item| item.id).collect();
+		assert_eq!(remaining, vec![ids[0], ids[3], ids[2]]);
+		assert_eq!(ItemIndex::<Test>::get(1, ids[1]), None);
+		assert_eq!(ItemIndex::<Test>::get(1, ids[3]), Some(1));
+		assert_item_index_consistent(1);
+
+		// Deleting the last item moves nothing
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), ids[2]));
+		assert_item_index_consistent(1);
+
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmIndexNew".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		assert_item_index_consistent(1);
+
+		assert_ok!(Shadow::delete_all_shadow_items(RuntimeOrigin::signed(1)));
+		assert_eq!(ItemIndex::<Test>::iter_prefix(1).count(), 0);
 	});
 }
 
@@ -121,20 +196,122 @@ fn grant_consent_works() {
 		let message_hash = b"consent_message_hash".to_vec();
 
 		// Grant consent
-		assert_ok!(Shadow::grant_consent(
-			RuntimeOrigin::signed(1),
-			message_hash.clone(),
-			Some(100)
-		));
+		assert_ok!(grant_consent_as(1, message_hash.clone(), Some(100)));
 
 		// Check storage
-		let consent = ConsentRecords::<Test>::get(1).unwrap();
+		let consent = ConsentRecords::<Test>::get(1, 0).unwrap();
 		assert_eq!(consent.message_hash, message_hash);
 		assert_eq!(consent.granted_at, 1);
 		assert_eq!(consent.expires_at, Some(101));
 
 		// Check event
-		System::assert_last_event(Event::ConsentGranted { who: 1, message_hash }.into());
+		System::assert_last_event(
+			Event::ConsentGranted {
+				who: 1,
+				consent_id: 0,
+				message_hash,
+				granted_at: 1,
+				expires_at: Some(101),
+				receipt: consent.receipt,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn grant_consent_verifies_signature_over_grant_expiry_and_nonce() {
+	use polkadot_sdk::sp_core::{sr25519, Pair};
+
+	new_test_ext().execute_with(|| {
+		let message_hash = b"consent_message_hash".to_vec();
+		let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+		SignerKeys::set(vec![(1, pair.public().0)]);
+		let action = |message_hash: &[u8], duration, scope: &[Source]| ConsentAuthorization::Grant {
+			message_hash: message_hash.to_vec(),
+			duration,
+			scope: scope.iter().copied().collect::<BTreeSet<_>>().try_into().unwrap(),
+			delegate: None,
+		};
+
+		// The context, then the signer, the grant's arguments, the expiry and the nonce
+		let payload = Shadow::consent_payload(&1, action(&message_hash, None, &[Source::GitHub]), 5);
+		let mut expected = CONSENT_SIGNING_CONTEXT.to_vec();
+		(1u64, 0u8, &message_hash, None::<u64>, vec![Source::GitHub], None::<u64>, 5u64, 0u32)
+			.encode_to(&mut expected);
+		assert_eq!(payload, expected);
+		let signature = pair.sign(&payload).0.to_vec();
+		let grant = |sender, message_hash: &[u8], duration, scope: Vec<Source>, valid_until, signer| {
+			Shadow::grant_consent(
+				RuntimeOrigin::signed(sender),
+				message_hash.to_vec(),
+				duration,
+				scope,
+				None,
+				ConsentSignature {
+					valid_until,
+					scheme: crate::signature_scheme::SR25519,
+					signature: signature.clone(),
+				},
+				signer,
+			)
+		};
+
+		// Signed by someone other than the signer
+		assert_noop!(
+			grant(2, &message_hash, None, vec![Source::GitHub], 5, 2),
+			Error::<Test>::InvalidConsentSignature
+		);
+		// Signed over a different message, expiry, duration or scope, so a relayer cannot
+		// widen the grant
+		assert_noop!(
+			grant(1, b"other_message", None, vec![Source::GitHub], 5, 1),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_noop!(
+			grant(1, &message_hash, None, vec![Source::GitHub], 6, 1),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_noop!(
+			grant(1, &message_hash, Some(10), vec![Source::GitHub], 5, 1),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_noop!(
+			grant(1, &message_hash, None, vec![], 5, 1),
+			Error::<Test>::InvalidConsentSignature
+		);
+
+		// The backend may relay the user's signed grant; consent is recorded for the signer
+		assert_ok!(grant(2, &message_hash, None, vec![Source::GitHub], 5, 1));
+		assert!(ConsentRecords::<Test>::contains_key(1, 0));
+		assert!(!ConsentRecords::<Test>::contains_key(2, 0));
+		assert_eq!(ConsentNonce::<Test>::get(1), 1);
+
+		// The used signature cannot be replayed, even before it expires
+		System::set_block_number(2);
+		assert_noop!(
+			grant(1, &message_hash, None, vec![Source::GitHub], 5, 1),
+			Error::<Test>::InvalidConsentSignature
+		);
+
+		// A signature is refused once its `valid_until` block has passed
+		let stale = pair.sign(&Shadow::consent_payload(&1, action(&message_hash, None, &[]), 1));
+		assert_noop!(
+			Shadow::grant_consent(
+				RuntimeOrigin::signed(1),
+				message_hash.clone(),
+				None,
+				vec![],
+				None,
+				ConsentSignature {
+					valid_until: 1,
+					scheme: crate::signature_scheme::SR25519,
+					signature: stale.0.to_vec(),
+				},
+				1,
+			),
+			Error::<Test>::ConsentSignatureExpired
+		);
 	});
 }
 
@@ -142,20 +319,19 @@ fn grant_consent_works() {
 fn revoke_consent_works() {
 	new_test_ext().execute_with(|| {
 		// Grant consent first
-		assert_ok!(Shadow::grant_consent(
-			RuntimeOrigin::signed(1),
-			b"test_consent".to_vec(),
-			None
-		));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
 
 		// Revoke consent
-		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1)));
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 0));
 
 		// Check storage
-		assert!(ConsentRecords::<Test>::get(1).is_none());
+		assert!(ConsentRecords::<Test>::get(1, 0).is_none());
 
 		// Check event
-		System::assert_last_event(Event::ConsentRevoked { who: 1 }.into());
+		System::assert_last_event(
+			Event::ConsentRevoked { who: 1, consent_id: 0, reason: RevocationReason::UserRevoked }
+				.into(),
+		);
 	});
 }
 
@@ -163,8 +339,8 @@ fn revoke_consent_works() {
 fn consent_expiry_works() {
 	new_test_ext().execute_with(|| {
 		// Grant consent with expiry
-		assert_ok!(Shadow::grant_consent(
-			RuntimeOrigin::signed(1),
+		assert_ok!(grant_consent_as(
+			1,
 			b"test_consent".to_vec(),
 			Some(10) // Expires after 10 blocks
 		));
@@ -174,11 +350,13 @@ fn consent_expiry_works() {
 			RuntimeOrigin::signed(1),
 			b"QmTest123".to_vec(),
 			b"encrypted_key_123".to_vec(),
-			0,
-			b"test metadata".to_vec()
+			Source::GitHub,
+			b"test metadata".to_vec(),
+			None
 		));
 
-		// Move to block 12 (past expiry)
+		// Move to block 12 (past expiry), ending block 1 and its cached consent checks
+		Shadow::on_finalize(1);
 		System::set_block_number(12);
 
 		// Should fail after expiry
@@ -187,8 +365,9 @@ fn consent_expiry_works() {
 				RuntimeOrigin::signed(1),
 				b"QmTest456".to_vec(),
 				b"encrypted_key_456".to_vec(),
-				0,
-				b"test metadata 2".to_vec()
+				Source::GitHub,
+				b"test metadata 2".to_vec(),
+				None
 			),
 			Error::<Test>::ConsentExpired
 		);
@@ -199,11 +378,7 @@ fn consent_expiry_works() {
 fn cid_too_long_fails() {
 	new_test_ext().execute_with(|| {
 		// Grant consent
-		assert_ok!(Shadow::grant_consent(
-			RuntimeOrigin::signed(1),
-			b"test_consent".to_vec(),
-			None
-		));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
 
 		// Create a CID that's too long (more than 100 bytes)
 		let long_cid = vec![b'Q'; 101];
@@ -213,8 +388,9 @@ fn cid_too_long_fails() {
 				RuntimeOrigin::signed(1),
 				long_cid,
 				b"encrypted_key_123".to_vec(),
-				0,
-				b"test metadata".to_vec()
+				Source::GitHub,
+				b"test metadata".to_vec(),
+				None
 			),
 			Error::<Test>::CidTooLong
 		);
@@ -225,11 +401,7 @@ fn cid_too_long_fails() {
 fn too_many_items_fails() {
 	new_test_ext().execute_with(|| {
 		// Grant consent
-		assert_ok!(Shadow::grant_consent(
-			RuntimeOrigin::signed(1),
-			b"test_consent".to_vec(),
-			None
-		));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
 
 		// Submit max items (100)
 		for i in 0..100 {
@@ -237,8 +409,9 @@ fn too_many_items_fails() {
 				RuntimeOrigin::signed(1),
 				format!("QmTest{}", i).as_bytes().to_vec(),
 				b"encrypted_key".to_vec(),
-				0,
-				b"metadata".to_vec()
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
 			));
 		}
 
@@ -248,10 +421,5057 @@ fn too_many_items_fails() {
 				RuntimeOrigin::signed(1),
 				b"QmTest101".to_vec(),
 				b"encrypted_key".to_vec(),
-				0,
-				b"metadata".to_vec()
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
 			),
 			Error::<Test>::TooManyItems
 		);
 	});
-}
\ No newline at end of file
+}
+#[test]
+fn items_diff_works() {
+	new_test_ext().execute_with(|| {
+		// Grant consent and submit three items
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for i in 0..3 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+
+		let items = Shadow::items_of(&1);
+		let ids: Vec<_> = items.iter().map(|item| item.hash_id()).collect();
+		let stale_id = <Test as frame_system::Config>::Hash::repeat_byte(0xAB);
+
+		// Client knows the first two items plus one that no longer exists
+		let (added, removed) = Shadow::items_diff(&1, vec![ids[0], ids[1], stale_id]).unwrap();
+
+		assert_eq!(added, vec![items[2].clone()]);
+		assert_eq!(removed, vec![stale_id]);
+
+		// More known ids than an account can hold are rejected rather than cut short
+		let known = vec![stale_id; MaxItemsPerAccount::get() as usize + 1];
+		assert_eq!(Shadow::items_diff(&1, known), Err(Error::<Test>::TooManyKnownIds.into()));
+	});
+}
+
+#[test]
+fn metadata_limit_override_works() {
+	new_test_ext().execute_with(|| {
+		// Metadata longer than the default 256 byte limit
+		let long_metadata = vec![b'm'; 300];
+
+		for account in [1, 2] {
+			assert_ok!(grant_consent_as(account, b"test_consent".to_vec(), None));
+		}
+
+		// Only the admin can set a limit, and never above the hard cap
+		assert_noop!(
+			Shadow::set_metadata_limit_for_account(RuntimeOrigin::signed(1), 2, Some(512)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Shadow::set_metadata_limit_for_account(RuntimeOrigin::root(), 2, Some(2048)),
+			Error::<Test>::MetadataLimitTooHigh
+		);
+		assert_ok!(Shadow::set_metadata_limit_for_account(RuntimeOrigin::root(), 2, Some(512)));
+		System::assert_last_event(Event::MetadataLimitSet { who: 2, limit: Some(512) }.into());
+
+		// A normal account is held to the default limit
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest123".to_vec(),
+				b"encrypted_key_123".to_vec(),
+				Source::GitHub,
+				long_metadata.clone(),
+				None
+			),
+			Error::<Test>::MetadataTooLong
+		);
+
+		// The premium account can use its larger allowance
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(2),
+			b"QmTest123".to_vec(),
+			b"encrypted_key_123".to_vec(),
+			Source::GitHub,
+			long_metadata.clone(),
+			None
+		));
+		assert_eq!(Shadow::items_of(&2)[0].metadata, long_metadata);
+	});
+}
+
+#[test]
+fn session_submission_works() {
+	new_test_ext().execute_with(|| {
+		// Owner grants consent and authorizes session key 10 until block 5
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::authorize_session(RuntimeOrigin::signed(1), 10, 5));
+		System::assert_last_event(
+			Event::SessionAuthorized { who: 1, session: 10, expires_at: 5 }.into(),
+		);
+
+		// The session key submits on the owner's behalf
+		assert_ok!(Shadow::submit_shadow_item_for(
+			RuntimeOrigin::signed(10),
+			1,
+			b"QmTest123".to_vec(),
+			b"encrypted_key_123".to_vec(),
+			Source::GitHub,
+			b"test metadata".to_vec()
+		));
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+		assert_eq!(Shadow::items_of(&10).len(), 0);
+
+		// Any other account is rejected
+		assert_noop!(
+			Shadow::submit_shadow_item_for(
+				RuntimeOrigin::signed(11),
+				1,
+				b"QmTest456".to_vec(),
+				b"encrypted_key_456".to_vec(),
+				Source::GitHub,
+				b"test metadata".to_vec()
+			),
+			Error::<Test>::NotAuthorizedSubmitter
+		);
+	});
+}
+
+#[test]
+fn consent_delegate_submits_for_the_consenting_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_delegated_consent_as(1, b"test_consent".to_vec(), 20));
+		assert_eq!(ConsentRecords::<Test>::get(1, 0).unwrap().delegate, Some(20));
+		let submit_as = |submitter: u64, cid: &[u8]| {
+			Shadow::submit_shadow_item_for(
+				RuntimeOrigin::signed(submitter),
+				1,
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+			)
+		};
+
+		// The named delegate submits, and the item is stored under the consenting account
+		assert_ok!(submit_as(20, b"QmDelegated"));
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+		assert!(Shadow::items_of(&20).is_empty());
+
+		// Anyone else is told they are not the delegate
+		assert_noop!(submit_as(21, b"QmOther"), Error::<Test>::NotAuthorizedDelegate);
+
+		// The delegate is bound by the signature; a relayer cannot swap it
+		let signature = consent_signature(1, b"test_consent", None);
+		assert_noop!(
+			Shadow::grant_consent(
+				RuntimeOrigin::signed(21),
+				b"test_consent".to_vec(),
+				None,
+				vec![],
+				Some(21),
+				signature,
+				1
+			),
+			Error::<Test>::InvalidConsentSignature
+		);
+
+		// Revoking the consent withdraws the delegation
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 0));
+		assert_noop!(submit_as(20, b"QmLate"), Error::<Test>::NotAuthorizedSubmitter);
+	});
+}
+
+#[test]
+fn session_submission_fails_after_expiry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::authorize_session(RuntimeOrigin::signed(1), 10, 5));
+
+		// Expiry must be within MaxSessionDuration
+		assert_noop!(
+			Shadow::authorize_session(RuntimeOrigin::signed(1), 11, 1000),
+			Error::<Test>::InvalidSessionExpiry
+		);
+
+		// Move past the session's expiry
+		System::set_block_number(6);
+
+		assert_noop!(
+			Shadow::submit_shadow_item_for(
+				RuntimeOrigin::signed(10),
+				1,
+				b"QmTest123".to_vec(),
+				b"encrypted_key_123".to_vec(),
+				Source::GitHub,
+				b"test metadata".to_vec()
+			),
+			Error::<Test>::SessionExpired
+		);
+
+		// Idle blocks clean up the expired session
+		Shadow::on_idle(6, Weight::MAX);
+		assert!(Sessions::<Test>::get(1, 10).is_none());
+	});
+}
+
+#[test]
+fn consent_cache_preserves_correctness_within_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		// Three same-account submissions in one block; only the first reads the record
+		for i in 0..3 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		assert_eq!(Shadow::items_of(&1).len(), 3);
+		assert!(ConsentCheckCache::<Test>::get().contains(&1));
+
+		// Revoking in the same block must not leave a stale cache hit
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 0));
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest3".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			),
+			Error::<Test>::ConsentRevoked
+		);
+
+		// The cache does not outlive the block
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest3".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		Shadow::on_finalize(1);
+		assert!(ConsentCheckCache::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn submit_weight_scales_with_metadata_length() {
+	new_test_ext().execute_with(|| {
+		let weight_of = |metadata: Vec<u8>| {
+			crate::Call::<Test>::submit_shadow_item {
+				cid: b"QmTest123".to_vec(),
+				encrypted_key: b"encrypted_key_123".to_vec(),
+				source: Source::GitHub,
+				metadata,
+				expires_at: None,
+			}
+			.get_dispatch_info()
+			.call_weight
+		};
+
+		let minimal = weight_of(Vec::new());
+		let maximal = weight_of(vec![b'm'; MaxMetadataLengthHard::get() as usize]);
+
+		assert!(maximal.ref_time() > minimal.ref_time());
+	});
+}
+
+#[test]
+fn submit_call_has_requested_lengths_within_limits() {
+	new_test_ext().execute_with(|| {
+		let minimal = Shadow::submit_call(1, 1, 0, Source::GitHub);
+		let maximal = Shadow::submit_call(
+			MaxCidLength::get(),
+			MaxKeyLength::get(),
+			MaxMetadataLengthHard::get(),
+			Source::GitHub,
+		);
+		assert!(
+			maximal.get_dispatch_info().call_weight.ref_time() >
+				minimal.get_dispatch_info().call_weight.ref_time()
+		);
+		assert!(maximal.encoded_size() > minimal.encoded_size());
+
+		// Lengths beyond the limits are capped
+		let oversized = Shadow::submit_call(u32::MAX, u32::MAX, u32::MAX, Source::GitHub);
+		assert_eq!(oversized, maximal);
+	});
+}
+
+#[test]
+fn pin_and_unpin_item_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest123".to_vec(),
+			b"encrypted_key_123".to_vec(),
+			Source::GitHub,
+			b"test metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].hash_id();
+		assert!(!Shadow::items_of(&1)[0].pinned);
+
+		// Pin the item
+		assert_ok!(Shadow::pin_item(RuntimeOrigin::signed(1), item_id));
+		assert!(Shadow::items_of(&1)[0].pinned);
+		System::assert_last_event(Event::ItemPinned { who: 1, item_id }.into());
+
+		// Unpin the item
+		assert_ok!(Shadow::unpin_item(RuntimeOrigin::signed(1), item_id));
+		assert!(!Shadow::items_of(&1)[0].pinned);
+		System::assert_last_event(Event::ItemUnpinned { who: 1, item_id }.into());
+
+		// Unknown ids, including another account's item, are rejected
+		assert_noop!(
+			Shadow::pin_item(RuntimeOrigin::signed(2), item_id),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
+
+#[test]
+fn migrate_v0_to_v1_adds_pinned_flag() {
+	use crate::migrations::v1::{self, MigrateV0ToV1, OldShadowItem};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		let old_item = OldShadowItem::<Test> {
+			id: [7u8; 32],
+			cid: b"QmTest123".to_vec().try_into().unwrap(),
+			encrypted_key: b"encrypted_key_123".to_vec().try_into().unwrap(),
+			timestamp: 1,
+			source: 0,
+			metadata: b"test metadata".to_vec().try_into().unwrap(),
+		};
+		StorageVersion::new(0).put::<Shadow>();
+		frame_support::storage::unhashed::put(
+			&ShadowItems::<Test>::hashed_key_for(1),
+			&vec![old_item],
+		);
+
+		MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		let items = v1::ShadowItems::<Test>::get(1);
+		assert_eq!(items.len(), 1);
+		assert_eq!(items[0].id, [7u8; 32]);
+		assert_eq!(items[0].cid, b"QmTest123".to_vec());
+		assert!(!items[0].pinned);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(1));
+	});
+}
+
+#[test]
+fn delete_all_shadow_items_emits_single_aggregate_event() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for i in 0..3 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		System::reset_events();
+
+		let info = Shadow::delete_all_shadow_items(RuntimeOrigin::signed(1)).unwrap();
+
+		assert!(!ShadowItems::<Test>::contains_key(1));
+		// Only the deleted items are charged for
+		assert_eq!(
+			info.actual_weight,
+			Some(<() as crate::weights::WeightInfo>::delete_all_shadow_items(3))
+		);
+		// Exactly one aggregate event, no per-item deletions
+		assert_eq!(
+			System::events().into_iter().map(|record| record.event).collect::<Vec<_>>(),
+			vec![RuntimeEvent::Shadow(Event::AllShadowItemsDeleted { who: 1, count: 3 })]
+		);
+	});
+}
+
+#[test]
+fn delete_items_by_source_removes_only_that_source() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for (i, source) in
+			[Source::Twitter, Source::GitHub, Source::Twitter, Source::GitHub, Source::Twitter]
+				.into_iter()
+				.enumerate()
+		{
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				source,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		let github: Vec<_> = Shadow::items_by_source(&1, Source::GitHub, 0, 10)
+			.into_iter()
+			.map(|item| item.id)
+			.collect();
+		System::reset_events();
+
+		let info =
+			Shadow::delete_items_by_source(RuntimeOrigin::signed(1), Source::Twitter).unwrap();
+
+		let remaining: Vec<_> = Shadow::items_of(&1).iter().map(|item| item.id).collect();
+		assert_eq!(remaining, github);
+		assert_item_index_consistent(1);
+		assert_eq!(Shadow::account_stats(&1).current, 2);
+		assert_eq!(Shadow::total_items(), 2);
+		// Every examined item is charged for, not only the deleted ones
+		assert_eq!(
+			info.actual_weight,
+			Some(<() as crate::weights::WeightInfo>::delete_items_by_source(5))
+		);
+		assert_eq!(
+			System::events().into_iter().map(|record| record.event).collect::<Vec<_>>(),
+			vec![RuntimeEvent::Shadow(Event::ItemsDeletedBySource {
+				who: 1,
+				source: Source::Twitter,
+				count: 3
+			})]
+		);
+	});
+}
+
+#[test]
+fn delete_items_by_source_without_matches_deletes_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+
+		assert_ok!(Shadow::delete_items_by_source(RuntimeOrigin::signed(1), Source::Twitter));
+		System::assert_last_event(
+			Event::ItemsDeletedBySource { who: 1, source: Source::Twitter, count: 0 }.into(),
+		);
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+
+		// An account with no items at all succeeds the same way
+		assert_ok!(Shadow::delete_items_by_source(RuntimeOrigin::signed(2), Source::GitHub));
+		System::assert_last_event(
+			Event::ItemsDeletedBySource { who: 2, source: Source::GitHub, count: 0 }.into(),
+		);
+		assert!(!ShadowItems::<Test>::contains_key(2));
+	});
+}
+
+#[test]
+fn revoke_all_agents_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		// Revoking with no agents is a no-op
+		assert_ok!(Shadow::revoke_all_agents(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::AllAgentsRevoked { who: 1, count: 0 }.into());
+
+		// Authorize two agents, both of which can submit
+		assert_ok!(Shadow::authorize_agent(RuntimeOrigin::signed(1), 20));
+		assert_ok!(Shadow::authorize_agent(RuntimeOrigin::signed(1), 21));
+		for agent in [20, 21] {
+			assert_ok!(Shadow::submit_shadow_item_for(
+				RuntimeOrigin::signed(agent),
+				1,
+				format!("QmAgent{}", agent).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec()
+			));
+		}
+
+		// Revoke them all at once
+		assert_ok!(Shadow::revoke_all_agents(RuntimeOrigin::signed(1)));
+		assert!(AuthorizedAgents::<Test>::get(1).is_empty());
+		System::assert_last_event(Event::AllAgentsRevoked { who: 1, count: 2 }.into());
+
+		for agent in [20, 21] {
+			assert_noop!(
+				Shadow::submit_shadow_item_for(
+					RuntimeOrigin::signed(agent),
+					1,
+					b"QmAfterRevoke".to_vec(),
+					b"encrypted_key".to_vec(),
+					Source::GitHub,
+					b"metadata".to_vec()
+				),
+				Error::<Test>::NotAuthorizedSubmitter
+			);
+		}
+	});
+}
+
+#[test]
+fn grant_consent_requires_min_balance() {
+	new_test_ext().execute_with(|| {
+		MinBalanceForConsent::set(100);
+
+		// An unfunded account cannot grant consent
+		assert_noop!(
+			grant_consent_as(1, b"test_consent".to_vec(), None),
+			Error::<Test>::InsufficientBalanceForConsent
+		);
+
+		// A funded account can, and nothing is reserved
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 2, 100));
+		assert_ok!(grant_consent_as(2, b"test_consent".to_vec(), None));
+		assert_eq!(Balances::free_balance(2), 100);
+
+		MinBalanceForConsent::set(0);
+	});
+}
+
+#[test]
+fn submit_and_grant_works_for_fresh_user() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Shadow::submit_and_grant(
+			RuntimeOrigin::signed(1),
+			b"test_consent".to_vec(),
+			None,
+			consent_signature(1, b"test_consent", None),
+			b"QmTest123".to_vec(),
+			b"encrypted_key_123".to_vec(),
+			Source::GitHub,
+			b"test metadata".to_vec()
+		));
+
+		assert!(ConsentRecords::<Test>::get(1, 0).is_some());
+		let items = Shadow::items_of(&1);
+		assert_eq!(items.len(), 1);
+
+		// Both the grant and the submission are reported
+		System::assert_has_event(
+			Event::ConsentGranted {
+				who: 1,
+				consent_id: 0,
+				message_hash: b"test_consent".to_vec(),
+				granted_at: 1,
+				expires_at: None,
+				receipt: ConsentRecords::<Test>::get(1, 0).unwrap().receipt,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::ShadowItemStored {
+				who: 1,
+				item_id: items[0].hash_id(),
+				cid: b"QmTest123".to_vec(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn submit_and_grant_requires_a_consent_signature() {
+	new_test_ext().execute_with(|| {
+		let submit_and_grant = |signature| {
+			Shadow::submit_and_grant(
+				RuntimeOrigin::signed(1),
+				b"test_consent".to_vec(),
+				None,
+				signature,
+				b"QmTest123".to_vec(),
+				b"encrypted_key_123".to_vec(),
+				Source::GitHub,
+				b"test metadata".to_vec(),
+			)
+		};
+
+		// A fresh user's grant must carry its own signature
+		assert_noop!(
+			submit_and_grant(consent_signature(2, b"test_consent", None)),
+			Error::<Test>::InvalidConsentSignature
+		);
+
+		// Transferred consents need re-signing, which the auto-grant flow cannot skip
+		assert_ok!(grant_consent_as(2, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::transfer_consent(RuntimeOrigin::signed(2), 1));
+		assert_noop!(
+			submit_and_grant(consent_signature(2, b"test_consent", None)),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_ok!(submit_and_grant(consent_signature(1, b"test_consent", None)));
+		assert_eq!(
+			ConsentRecords::<Test>::get(1, 1).unwrap().signature_scheme,
+			Some(crate::signature_scheme::SR25519)
+		);
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+	});
+}
+
+#[test]
+fn submit_and_grant_only_submits_when_consented() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"original_consent".to_vec(), None));
+		System::reset_events();
+
+		assert_ok!(Shadow::submit_and_grant(
+			RuntimeOrigin::signed(1),
+			b"new_consent".to_vec(),
+			None,
+			consent_signature(1, b"new_consent", None),
+			b"QmTest123".to_vec(),
+			b"encrypted_key_123".to_vec(),
+			Source::GitHub,
+			b"test metadata".to_vec()
+		));
+
+		// The existing consent is kept and only the submission is reported
+		assert_eq!(
+			ConsentRecords::<Test>::get(1, 0).unwrap().message_hash,
+			b"original_consent".to_vec()
+		);
+		assert!(!ConsentRecords::<Test>::contains_key(1, 1));
+		let items = Shadow::items_of(&1);
+		assert_eq!(
+			System::events().into_iter().map(|record| record.event).collect::<Vec<_>>(),
+			vec![RuntimeEvent::Shadow(Event::ShadowItemStored {
+				who: 1,
+				item_id: items[0].hash_id(),
+				cid: b"QmTest123".to_vec(),
+			})]
+		);
+	});
+}
+
+#[test]
+fn cid_validation_is_source_specific() {
+	new_test_ext().execute_with(|| {
+		EnforceGitHubCidV0::set(true);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		// GitHub (0) requires a CIDv0
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"bafyCustomScheme".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			),
+			Error::<Test>::InvalidCid
+		);
+		let cid_v0 = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec();
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			cid_v0,
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+
+		// Twitter (1) accepts anything
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"bafyCustomScheme".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::Twitter,
+			b"metadata".to_vec(),
+			None
+		));
+
+		EnforceGitHubCidV0::set(false);
+	});
+}
+
+#[test]
+fn submit_readiness_reports_consent_and_slots() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// No consent yet
+		assert_eq!(
+			Shadow::submit_readiness(&1),
+			SubmitReadiness { consent_valid: false, consent_remaining: None, slots_remaining: 100 }
+		);
+
+		// Grant consent for 10 blocks and submit three items
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(10)));
+		for i in 0..3 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+
+		System::set_block_number(4);
+		assert_eq!(
+			Shadow::submit_readiness(&1),
+			SubmitReadiness { consent_valid: true, consent_remaining: Some(7), slots_remaining: 97 }
+		);
+
+		// Once consent expires, the remaining slots are still reported
+		System::set_block_number(12);
+		assert_eq!(
+			Shadow::submit_readiness(&1),
+			SubmitReadiness { consent_valid: false, consent_remaining: None, slots_remaining: 97 }
+		);
+	});
+}
+
+#[test]
+fn consent_status_reports_remaining_blocks() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(Shadow::consent_status(&1), ConsentStatus::None);
+
+		// Expires at block 11
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(10)));
+		System::set_block_number(4);
+		assert_eq!(
+			Shadow::consent_status(&1),
+			ConsentStatus::Active { granted_at: 1, expires_at: 11, remaining: 7 }
+		);
+
+		// The expiry block itself is still covered when expiry is inclusive
+		System::set_block_number(11);
+		assert_eq!(
+			Shadow::consent_status(&1),
+			ConsentStatus::Active { granted_at: 1, expires_at: 11, remaining: 0 }
+		);
+		ConsentExpiryInclusive::set(false);
+		assert_eq!(Shadow::consent_status(&1), ConsentStatus::Expired { expired_at: 11 });
+		ConsentExpiryInclusive::set(true);
+
+		System::set_block_number(12);
+		assert_eq!(Shadow::consent_status(&1), ConsentStatus::Expired { expired_at: 11 });
+
+		// A permanent consent outranks the expired one
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_eq!(Shadow::consent_status(&1), ConsentStatus::Permanent { granted_at: 12 });
+
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Shadow::consent_status(&1), ConsentStatus::Expired { expired_at: 11 });
+	});
+}
+
+#[test]
+fn share_item_overwrites_existing_recipient() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].hash_id();
+
+		// First share inserts
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item_id, 2, b"key_v1".to_vec()));
+		System::assert_last_event(Event::ItemShared { who: 1, item_id, recipient: 2 }.into());
+
+		// Fill the remaining share slot
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item_id, 3, b"key_v1".to_vec()));
+
+		// Re-sharing with an existing recipient updates the key even at capacity
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item_id, 2, b"key_v2".to_vec()));
+		System::assert_last_event(Event::ItemShareUpdated { who: 1, item_id, recipient: 2 }.into());
+		assert_eq!(SharedKeys::<Test>::get(2, item_id).unwrap(), b"key_v2".to_vec());
+		assert_eq!(ShareRecipients::<Test>::get(item_id).into_inner(), vec![2, 3]);
+
+		// A new recipient beyond the cap is rejected
+		assert_noop!(
+			Shadow::share_item(RuntimeOrigin::signed(1), item_id, 4, b"key_v1".to_vec()),
+			Error::<Test>::TooManyShares
+		);
+
+		// Only the owner can share
+		assert_noop!(
+			Shadow::share_item(RuntimeOrigin::signed(2), item_id, 4, b"key_v1".to_vec()),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
+
+#[test]
+fn total_items_and_consent_count_span_accounts() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Shadow::total_items(), 0);
+		assert_eq!(Shadow::consent_count(), 0);
+
+		for (account, count) in [(1, 2), (2, 3)] {
+			assert_ok!(grant_consent_as(account, b"test_consent".to_vec(), None));
+			for i in 0..count {
+				assert_ok!(Shadow::submit_shadow_item(
+					RuntimeOrigin::signed(account),
+					format!("QmTest{}", i).as_bytes().to_vec(),
+					b"encrypted_key".to_vec(),
+					Source::GitHub,
+					b"metadata".to_vec(),
+					None
+				));
+			}
+		}
+
+		assert_eq!(Shadow::total_items(), 5);
+		assert_eq!(Shadow::consent_count(), 2);
+	});
+}
+
+#[test]
+fn amend_consent_scope_works() {
+	use Source::*;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// No consent to amend
+		assert_noop!(
+			Shadow::amend_consent_scope(RuntimeOrigin::signed(1), 0, vec![], vec![GitHub]),
+			Error::<Test>::NoConsent
+		);
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(50)));
+		let granted = ConsentRecords::<Test>::get(1, 0).unwrap();
+
+		// Adding to an all-sources scope changes nothing
+		assert_ok!(Shadow::amend_consent_scope(RuntimeOrigin::signed(1), 0, vec![GitHub], vec![]));
+		assert!(ConsentRecords::<Test>::get(1, 0).unwrap().scope.is_empty());
+
+		// Narrow to GitHub only, then add Twitter; re-adding GitHub is a no-op
+		assert_ok!(Shadow::amend_consent_scope(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![],
+			vec![Twitter, GitLab, Reddit, StackOverflow]
+		));
+		assert_ok!(Shadow::amend_consent_scope(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![GitHub, Twitter],
+			vec![]
+		));
+		System::assert_last_event(
+			Event::ConsentScopeAmended { who: 1, consent_id: 0, scope: vec![GitHub, Twitter] }
+				.into(),
+		);
+
+		// Remove GitHub; removing an absent source is a no-op
+		System::set_block_number(5);
+		assert_ok!(Shadow::amend_consent_scope(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![],
+			vec![GitHub, GitHub, Reddit]
+		));
+		System::assert_last_event(
+			Event::ConsentScopeAmended { who: 1, consent_id: 0, scope: vec![Twitter] }.into(),
+		);
+
+		// The grant itself is untouched
+		let amended = ConsentRecords::<Test>::get(1, 0).unwrap();
+		assert_eq!(amended.scope.into_iter().collect::<Vec<_>>(), vec![Twitter]);
+		assert_eq!(amended.granted_at, granted.granted_at);
+		assert_eq!(amended.expires_at, granted.expires_at);
+		assert_eq!(amended.message_hash, granted.message_hash);
+
+		// Removing the last source would widen the scope back to all sources
+		assert_noop!(
+			Shadow::amend_consent_scope(RuntimeOrigin::signed(1), 0, vec![], vec![Twitter]),
+			Error::<Test>::EmptyScope
+		);
+	});
+}
+
+#[test]
+fn amend_consent_scope_invalidates_cached_consent_checks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let submit = || {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None,
+			)
+		};
+
+		// The all-sources consent is cached by the first submission
+		assert_ok!(submit());
+		assert!(ConsentCheckCache::<Test>::get().contains(&1));
+
+		assert_ok!(Shadow::amend_consent_scope(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![],
+			vec![Source::GitHub]
+		));
+		assert!(!ConsentCheckCache::<Test>::get().contains(&1));
+		assert_noop!(submit(), Error::<Test>::SourceNotConsented);
+	});
+}
+
+#[test]
+fn migrate_v1_to_v2_adds_empty_consent_scope() {
+	use crate::migrations::v2::{ConsentRecords, MigrateV1ToV2, OldConsentRecord};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Shadow>();
+		let old_record = OldConsentRecord::<Test> {
+			granted_at: 1,
+			expires_at: Some(10),
+			message_hash: b"test_consent".to_vec().try_into().unwrap(),
+		};
+		frame_support::storage::unhashed::put(
+			&ConsentRecords::<Test>::hashed_key_for(1),
+			&old_record,
+		);
+
+		MigrateV1ToV2::<Test>::on_runtime_upgrade();
+
+		let record = ConsentRecords::<Test>::get(1).unwrap();
+		assert_eq!(record.expires_at, Some(10));
+		assert_eq!(record.message_hash, b"test_consent".to_vec());
+		assert!(record.scope.is_empty());
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(2));
+	});
+}
+
+#[test]
+fn aggregate_events_summarises_same_block_submissions() {
+	new_test_ext().execute_with(|| {
+		AggregateEvents::set(true);
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		System::reset_events();
+
+		// Three separate extrinsics in the same block
+		for i in 0..3 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		assert!(System::events().is_empty());
+
+		Shadow::on_finalize(1);
+
+		assert_eq!(
+			System::events().into_iter().map(|record| record.event).collect::<Vec<_>>(),
+			vec![RuntimeEvent::Shadow(Event::ItemsStored { who: 1, count: 3 })]
+		);
+		assert!(PendingItemsStored::<Test>::get().is_empty());
+
+		AggregateEvents::set(false);
+	});
+}
+
+#[test]
+fn account_bytes_quota_rejects_items_before_item_limit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let submit = |cid: &[u8]| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.to_vec(),
+				vec![b'k'; 512],
+				Source::GitHub,
+				vec![b'm'; 256],
+				None
+			)
+		};
+		assert_ok!(submit(b"QmLarge0"));
+		let size = Shadow::stored_size(&Shadow::items_of(&1)[0]);
+		MaxBytesPerAccount::set(2 * size);
+
+		assert_ok!(submit(b"QmLarge1"));
+		assert_eq!(AccountBytes::<Test>::get(1), 2 * size);
+
+		// Two items in, far below `MaxItemsPerAccount`, the byte budget is spent
+		assert_noop!(submit(b"QmLarge2"), Error::<Test>::AccountStorageFull);
+		assert_noop!(
+			Shadow::submit_shadow_items_batch(RuntimeOrigin::signed(1), batch_of(1, "QmSmall")),
+			Error::<Test>::AccountStorageFull
+		);
+
+		// Deleting an item frees its bytes
+		let first = Shadow::items_of(&1)[0].id;
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), first));
+		assert_eq!(AccountBytes::<Test>::get(1), size);
+		assert_ok!(submit(b"QmLarge2"));
+
+		assert_ok!(Shadow::delete_all_shadow_items(RuntimeOrigin::signed(1)));
+		assert!(!AccountBytes::<Test>::contains_key(1));
+
+		MaxBytesPerAccount::set(u32::MAX);
+	});
+}
+
+#[test]
+fn account_bytes_quota_covers_growing_updates() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmSmall".to_vec(),
+			vec![b'k'; 16],
+			Source::GitHub,
+			vec![b'm'; 16],
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+		let size = AccountBytes::<Test>::get(1);
+		MaxBytesPerAccount::set(size + 8);
+
+		// Growing the item past the budget in place is rejected
+		assert_noop!(
+			Shadow::update_shadow_item(
+				RuntimeOrigin::signed(1),
+				item_id,
+				b"QmSmall".to_vec(),
+				vec![b'k'; 512],
+				vec![b'm'; 16]
+			),
+			Error::<Test>::AccountStorageFull
+		);
+		assert_ok!(Shadow::update_shadow_item(
+			RuntimeOrigin::signed(1),
+			item_id,
+			b"QmSmall".to_vec(),
+			vec![b'k'; 20],
+			vec![b'm'; 16]
+		));
+		assert_eq!(AccountBytes::<Test>::get(1), size + 4);
+
+		// Under a lowered cap the item may still shrink
+		MaxBytesPerAccount::set(size / 2);
+		assert_ok!(Shadow::update_shadow_item(
+			RuntimeOrigin::signed(1),
+			item_id,
+			b"QmSmall".to_vec(),
+			vec![b'k'; 8],
+			vec![b'm'; 16]
+		));
+		assert_eq!(AccountBytes::<Test>::get(1), size - 8);
+
+		MaxBytesPerAccount::set(u32::MAX);
+	});
+}
+
+#[test]
+fn account_storage_bytes_sums_item_sizes() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Shadow::account_storage_bytes(&1), 0);
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for (cid_len, key_len, metadata_len) in [(10, 20, 0), (46, 128, 200)] {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				vec![b'Q'; cid_len],
+				vec![b'k'; key_len],
+				Source::GitHub,
+				vec![b'm'; metadata_len],
+				None
+			));
+		}
+
+		// The stored vector's size, minus its own length prefix
+		let items = ShadowItems::<Test>::get(1);
+		let expected = items.encoded_size() - Compact(items.len() as u32).encoded_size();
+		assert_eq!(Shadow::account_storage_bytes(&1), expected as u64);
+
+		// Fixed fields are the version tag (1), id (32), timestamp (8), source (1), pinned (1),
+		// the `encrypted_metadata` and `expires_at` option tags (1 each) and the deposit (8);
+		// each vector, including the empty tag list, adds a compact length prefix of one byte
+		// below 64 entries and two above
+		assert_eq!(
+			expected,
+			(53 + 1 + 10 + 1 + 20 + 1 + 1) + (53 + 1 + 46 + 2 + 128 + 2 + 200 + 1)
+		);
+	});
+}
+
+#[test]
+fn enforce_templates_rejects_unknown_hashes() {
+	new_test_ext().execute_with(|| {
+		EnforceTemplates::set(true);
+
+		assert_ok!(Shadow::register_consent_template(
+			RuntimeOrigin::root(),
+			b"template_v1".to_vec()
+		));
+		assert_noop!(
+			Shadow::register_consent_template(RuntimeOrigin::signed(1), b"template_v2".to_vec()),
+			DispatchError::BadOrigin
+		);
+
+		assert_noop!(
+			grant_consent_as(1, b"random_hash".to_vec(), None),
+			Error::<Test>::UnknownTemplate
+		);
+		assert_ok!(grant_consent_as(1, b"template_v1".to_vec(), None));
+
+		// Without enforcement any hash is accepted
+		EnforceTemplates::set(false);
+		assert_ok!(grant_consent_as(2, b"random_hash".to_vec(), None));
+	});
+}
+
+#[test]
+fn update_cid_preserves_item_id() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for cid in [b"QmOld".to_vec(), b"QmOther".to_vec()] {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid,
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		let before = Shadow::items_of(&1)[0].clone();
+		let item_id = before.hash_id();
+
+		assert_ok!(Shadow::update_cid(RuntimeOrigin::signed(1), item_id, b"QmNew".to_vec()));
+		System::assert_last_event(
+			Event::CidUpdated { who: 1, item_id, new_cid: b"QmNew".to_vec() }.into(),
+		);
+
+		let after = Shadow::items_of(&1)[0].clone();
+		assert_eq!(after.id, before.id);
+		assert_eq!(after.cid, b"QmNew".to_vec());
+		assert_eq!(after.encrypted_key, before.encrypted_key);
+		assert_eq!(after.metadata, before.metadata);
+
+		// Colliding with another item's CID is rejected
+		assert_noop!(
+			Shadow::update_cid(RuntimeOrigin::signed(1), item_id, b"QmOther".to_vec()),
+			Error::<Test>::DuplicateCid
+		);
+		// Unknown items are rejected
+		assert_noop!(
+			Shadow::update_cid(
+				RuntimeOrigin::signed(1),
+				<Test as frame_system::Config>::Hash::repeat_byte(0xAB),
+				b"QmNewer".to_vec()
+			),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
+
+#[test]
+fn genesis_config_seeds_consents_and_items() {
+	new_test_ext_with_genesis(crate::GenesisConfig {
+		consents: vec![(1, 0, Some(10), b"genesis_consent".to_vec())],
+		items: vec![(
+			1,
+			b"QmGenesis".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+		)],
+		..Default::default()
+	})
+	.execute_with(|| {
+		let consent = ConsentRecords::<Test>::get(1, 0).unwrap();
+		assert_eq!((consent.granted_at, consent.expires_at), (0, Some(10)));
+		assert_eq!(consent.message_hash.to_vec(), b"genesis_consent".to_vec());
+		assert_eq!(Shadow::accounts_with_message(b"genesis_consent".to_vec()), vec![1]);
+
+		let items = Shadow::items_of(&1);
+		assert_eq!(items.len(), 1);
+		assert_eq!(items[0].cid.to_vec(), b"QmGenesis".to_vec());
+		assert_eq!(TotalItems::<Test>::get(), 1);
+		assert_eq!(Shadow::account_stats(&1).current, 1);
+
+		// The seeded consent is usable like a granted one
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmAfterGenesis".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "genesis item is invalid")]
+fn genesis_config_rejects_items_without_consent() {
+	new_test_ext_with_genesis(crate::GenesisConfig {
+		items: vec![(
+			1,
+			b"QmGenesis".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+		)],
+		..Default::default()
+	});
+}
+
+#[test]
+fn genesis_disabled_pallet_rejects_calls() {
+	new_disabled_test_ext().execute_with(|| {
+		assert!(!Enabled::<Test>::get());
+
+		assert_noop!(
+			grant_consent_as(1, b"test_consent".to_vec(), None),
+			Error::<Test>::PalletDisabled
+		);
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			),
+			Error::<Test>::PalletDisabled
+		);
+		assert_noop!(
+			Shadow::set_metadata_limit_for_account(RuntimeOrigin::root(), 1, Some(512)),
+			Error::<Test>::PalletDisabled
+		);
+	});
+
+	// Enabled by default
+	new_test_ext().execute_with(|| assert!(Enabled::<Test>::get()));
+}
+
+#[test]
+fn consent_by_message_tracks_grants_and_revocations() {
+	new_test_ext().execute_with(|| {
+		for account in [1, 2] {
+			assert_ok!(grant_consent_as(account, b"template_v1".to_vec(), None));
+		}
+		assert_eq!(Shadow::accounts_with_message(b"template_v1".to_vec()), vec![1, 2]);
+
+		// Re-granting with the same message does not duplicate the account
+		assert_ok!(grant_consent_as(1, b"template_v1".to_vec(), None));
+		assert_eq!(Shadow::accounts_with_message(b"template_v1".to_vec()), vec![1, 2]);
+
+		// The index is capped at MaxAccountsPerMessage
+		assert_noop!(
+			grant_consent_as(3, b"template_v1".to_vec(), None),
+			Error::<Test>::TooManyAccountsForMessage
+		);
+
+		// A grant under a new message is indexed alongside the existing ones
+		assert_ok!(grant_consent_as(1, b"template_v2".to_vec(), None));
+		assert_eq!(Shadow::accounts_with_message(b"template_v1".to_vec()), vec![1, 2]);
+		assert_eq!(Shadow::accounts_with_message(b"template_v2".to_vec()), vec![1]);
+
+		// An account stays indexed until its last consent under the message is revoked
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 0));
+		assert_eq!(Shadow::accounts_with_message(b"template_v1".to_vec()), vec![1, 2]);
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Shadow::accounts_with_message(b"template_v1".to_vec()), vec![2]);
+
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(2), 0));
+		assert!(Shadow::accounts_with_message(b"template_v1".to_vec()).is_empty());
+		assert!(!ConsentByMessage::<Test>::contains_key(
+			BoundedMessageHash::<Test>::try_from(b"template_v1".to_vec()).unwrap()
+		));
+	});
+}
+
+#[test]
+fn consent_expiry_boundary_follows_inclusive_setting() {
+	for (inclusive, expected) in [(true, Ok(())), (false, Err(Error::<Test>::ConsentExpired))] {
+		new_test_ext().execute_with(|| {
+			ConsentExpiryInclusive::set(inclusive);
+
+			// Granted at block 1, expiring at block 11
+			assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(10)));
+
+			// Exactly at the expiry block the modes diverge
+			System::set_block_number(11);
+			assert_eq!(
+				Shadow::submit_shadow_item(
+					RuntimeOrigin::signed(1),
+					b"QmTest".to_vec(),
+					b"encrypted_key".to_vec(),
+					Source::GitHub,
+					b"metadata".to_vec(),
+					None
+				),
+				expected.map_err(Into::into)
+			);
+			assert_eq!(Shadow::submit_readiness(&1).consent_valid, inclusive);
+
+			ConsentExpiryInclusive::set(true);
+		});
+	}
+}
+
+#[test]
+fn transfer_consent_requires_resign() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(50)));
+
+		assert_ok!(Shadow::transfer_consent(RuntimeOrigin::signed(1), 2));
+		System::assert_last_event(Event::ConsentTransferred { from: 1, to: 2 }.into());
+
+		assert!(ConsentRecords::<Test>::get(1, 0).is_none());
+		let moved = ConsentRecords::<Test>::get(2, 0).unwrap();
+		assert!(moved.needs_resign);
+		assert_eq!(moved.expires_at, Some(51));
+		assert_eq!(Shadow::accounts_with_message(b"test_consent".to_vec()), vec![2]);
+
+		// The new account must re-sign before submitting
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(2),
+				b"QmTest".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			),
+			Error::<Test>::ConsentNeedsResign
+		);
+		// Only the new account's own signature over this consent re-signs it
+		let resign = ConsentAuthorization::Resign {
+			receipt: moved.receipt,
+			scope: moved.scope.clone(),
+			delegate: None,
+		};
+		assert_noop!(
+			Shadow::resign_consent(RuntimeOrigin::signed(2), 0, sign_consent(1, resign.clone())),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_noop!(
+			Shadow::resign_consent(
+				RuntimeOrigin::signed(2),
+				0,
+				consent_signature(2, b"test_consent", Some(50))
+			),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_ok!(Shadow::resign_consent(
+			RuntimeOrigin::signed(2),
+			0,
+			sign_consent(2, resign.clone())
+		));
+		System::assert_last_event(Event::ConsentResigned { who: 2, consent_id: 0 }.into());
+		let resigned = ConsentRecords::<Test>::get(2, 0).unwrap();
+		assert!(!resigned.needs_resign);
+		assert_eq!(resigned.expires_at, Some(51));
+		assert_noop!(
+			Shadow::resign_consent(RuntimeOrigin::signed(2), 0, sign_consent(2, resign)),
+			Error::<Test>::ConsentNotTransferred
+		);
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(2),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+	});
+}
+
+#[test]
+fn transfer_consent_rejects_target_with_consent() {
+	new_test_ext().execute_with(|| {
+		for account in [1, 2] {
+			assert_ok!(grant_consent_as(account, b"test_consent".to_vec(), None));
+		}
+
+		assert_noop!(
+			Shadow::transfer_consent(RuntimeOrigin::signed(1), 2),
+			Error::<Test>::ConsentAlreadyExists
+		);
+		assert_noop!(
+			Shadow::transfer_consent(RuntimeOrigin::signed(3), 4),
+			Error::<Test>::NoConsent
+		);
+	});
+}
+
+#[test]
+fn migrate_v2_to_v3_adds_resign_flag() {
+	use crate::migrations::{
+		v2::{self, ConsentRecordV2},
+		v3::{self, MigrateV2ToV3},
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<Shadow>();
+		v2::ConsentRecords::<Test>::insert(
+			1,
+			ConsentRecordV2::<Test> {
+				granted_at: 1,
+				expires_at: None,
+				message_hash: b"test_consent".to_vec().try_into().unwrap(),
+				scope: Default::default(),
+			},
+		);
+
+		MigrateV2ToV3::<Test>::on_runtime_upgrade();
+
+		let record = v3::ConsentRecords::<Test>::get(1).unwrap();
+		assert_eq!(record.message_hash, b"test_consent".to_vec());
+		assert!(!record.needs_resign);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(3));
+	});
+}
+
+#[test]
+fn source_meta_template_fills_empty_metadata() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Shadow::set_source_meta_template(
+			RuntimeOrigin::root(),
+			Source::GitHub,
+			Some(b"github:v1".to_vec())
+		));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		// Empty GitHub metadata takes the template
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest1".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			vec![],
+			None
+		));
+		// Explicit metadata and other sources are left alone
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest2".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"custom".to_vec(),
+			None
+		));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest3".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::Twitter,
+			vec![],
+			None
+		));
+
+		let items = Shadow::items_of(&1);
+		assert_eq!(items[0].metadata, b"github:v1".to_vec());
+		assert_eq!(items[1].metadata, b"custom".to_vec());
+		assert!(items[2].metadata.is_empty());
+
+		// Templates must fit the default metadata limit
+		assert_noop!(
+			Shadow::set_source_meta_template(
+				RuntimeOrigin::root(),
+				Source::GitHub,
+				Some(vec![b't'; 257])
+			),
+			Error::<Test>::MetadataTooLong
+		);
+	});
+}
+
+#[test]
+fn report_dead_cid_removes_item_at_threshold() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].hash_id();
+
+		// Only oracles may report
+		assert_noop!(
+			Shadow::report_dead_cid(RuntimeOrigin::signed(2), 1, item_id),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Shadow::report_dead_cid(RuntimeOrigin::signed(10), 1, item_id));
+		System::assert_last_event(
+			Event::DeadCidReported { who: 1, item_id, reporter: 10, reports: 1 }.into(),
+		);
+
+		// A single oracle cannot reach the threshold alone
+		assert_noop!(
+			Shadow::report_dead_cid(RuntimeOrigin::signed(10), 1, item_id),
+			Error::<Test>::AlreadyReported
+		);
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+
+		// A second distinct oracle removes the item
+		assert_ok!(Shadow::report_dead_cid(RuntimeOrigin::signed(11), 1, item_id));
+		System::assert_last_event(
+			Event::ShadowItemRemoved { who: 1, item_id, reason: RemovalReason::DeadCid }.into(),
+		);
+		assert!(Shadow::items_of(&1).is_empty());
+		assert!(DeadCidReports::<Test>::get(item_id).is_empty());
+
+		assert_noop!(
+			Shadow::report_dead_cid(RuntimeOrigin::signed(12), 1, item_id),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
+
+#[test]
+fn oldest_item_finds_minimum_timestamp() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Shadow::oldest_item(&1), None);
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for block in [3, 5, 9] {
+			System::set_block_number(block);
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", block).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		let items = Shadow::items_of(&1);
+		let first_id = items[0].hash_id();
+		let second_id = items[1].hash_id();
+
+		assert_eq!(Shadow::oldest_item(&1), Some((first_id, 3)));
+
+		// Once the oldest is deleted the next one takes its place
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), first_id));
+		assert_eq!(Shadow::oldest_item(&1), Some((second_id, 5)));
+	});
+}
+
+#[test]
+fn force_delete_item_reports_reason() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].hash_id();
+
+		assert_noop!(
+			Shadow::force_delete_item(RuntimeOrigin::signed(2), 1, item_id),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Shadow::force_delete_item(RuntimeOrigin::root(), 1, item_id));
+		System::assert_last_event(
+			Event::ShadowItemRemoved { who: 1, item_id, reason: RemovalReason::ForceDeleted }
+				.into(),
+		);
+		assert!(Shadow::items_of(&1).is_empty());
+
+		assert_noop!(
+			Shadow::force_delete_item(RuntimeOrigin::root(), 1, item_id),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
+
+#[test]
+fn current_timestamp_tracks_block_number() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(42);
+		assert_eq!(Shadow::current_timestamp(), 42);
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		assert_eq!(Shadow::items_of(&1)[0].timestamp, Shadow::current_timestamp());
+	});
+}
+
+#[test]
+fn submit_with_encrypted_metadata_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		assert_ok!(Shadow::submit_shadow_item_with_encrypted_metadata(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"plain metadata".to_vec(),
+			b"encrypted notes".to_vec()
+		));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest2".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"plain metadata".to_vec(),
+			None
+		));
+
+		let items = Shadow::items_of(&1);
+		assert_eq!(items[0].metadata, b"plain metadata".to_vec());
+		assert_eq!(items[0].encrypted_metadata, Some(b"encrypted notes".to_vec().try_into().unwrap()));
+		assert_eq!(items[1].metadata, b"plain metadata".to_vec());
+		assert_eq!(items[1].encrypted_metadata, None);
+
+		assert_noop!(
+			Shadow::submit_shadow_item_with_encrypted_metadata(
+				RuntimeOrigin::signed(1),
+				b"QmTest3".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				vec![],
+				vec![0u8; MaxEncMetaLength::get() as usize + 1]
+			),
+			Error::<Test>::EncryptedMetadataTooLong
+		);
+	});
+}
+
+#[test]
+fn migrate_v3_to_v4_adds_encrypted_metadata() {
+	use crate::migrations::{
+		v1::{self, ShadowItemV1},
+		v4::{self, MigrateV3ToV4},
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(3).put::<Shadow>();
+		let old_item = ShadowItemV1::<Test> {
+			id: [7u8; 32],
+			cid: b"QmTest123".to_vec().try_into().unwrap(),
+			encrypted_key: b"encrypted_key_123".to_vec().try_into().unwrap(),
+			timestamp: 1,
+			source: 0,
+			metadata: b"test metadata".to_vec().try_into().unwrap(),
+			pinned: true,
+		};
+		v1::ShadowItems::<Test>::insert(1, BoundedVec::truncate_from(vec![old_item]));
+
+		MigrateV3ToV4::<Test>::on_runtime_upgrade();
+
+		let items = v4::ShadowItems::<Test>::get(1);
+		assert_eq!(items.len(), 1);
+		assert!(items[0].pinned);
+		assert_eq!(items[0].encrypted_metadata, None);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(4));
+	});
+}
+
+#[test]
+fn cid_limit_is_per_source() {
+	new_test_ext().execute_with(|| {
+		GitHubCidLimit::set(Some(10));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		let cid = vec![b'Q'; 20];
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.clone(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			),
+			Error::<Test>::CidTooLong
+		);
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			cid,
+			b"encrypted_key".to_vec(),
+			Source::Twitter,
+			b"metadata".to_vec(),
+			None
+		));
+
+		// A source limit above the hard bound is capped at `MaxCidLength`.
+		GitHubCidLimit::set(Some(MaxCidLength::get() + 1));
+		assert_eq!(Shadow::cid_limit(Source::GitHub), MaxCidLength::get());
+		assert_eq!(Shadow::cid_limit(Source::Twitter), MaxCidLength::get());
+	});
+}
+
+#[test]
+fn consent_audit_contains_full_sequence() {
+	new_test_ext().execute_with(|| {
+		let message_hash: BoundedMessageHash<Test> = b"test_consent".to_vec().try_into().unwrap();
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		System::set_block_number(2);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(100)));
+
+		let audit = Shadow::consent_audit(&1);
+		assert_eq!(
+			audit.records.iter().map(|(id, record)| (*id, record.expires_at)).collect::<Vec<_>>(),
+			vec![(0, None), (1, Some(102))]
+		);
+		assert_eq!(audit.history.len(), 2);
+
+		System::set_block_number(3);
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 0));
+
+		let audit = Shadow::consent_audit(&1);
+		assert_eq!(audit.records.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![1]);
+		assert_eq!(
+			audit.history,
+			vec![
+				ConsentHistoryEntry {
+					action: ConsentAction::Granted,
+					at: 1,
+					message_hash: message_hash.clone(),
+					expires_at: None,
+				},
+				ConsentHistoryEntry {
+					action: ConsentAction::Granted,
+					at: 2,
+					message_hash: message_hash.clone(),
+					expires_at: Some(102),
+				},
+				ConsentHistoryEntry {
+					action: ConsentAction::Revoked,
+					at: 3,
+					message_hash: message_hash.clone(),
+					expires_at: None,
+				},
+			]
+		);
+
+		// The log keeps only the most recent `MaxConsentHistory` entries.
+		System::set_block_number(4);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let history = Shadow::consent_audit(&1).history;
+		assert_eq!(history.len() as u32, MaxConsentHistory::get());
+		assert_eq!(history.first().unwrap().at, 2);
+		assert_eq!(history.last().unwrap().at, 4);
+	});
+}
+
+#[test]
+fn migrate_v4_to_v5_decodes_item_ids() {
+	use crate::migrations::{
+		v10::MigrateV9ToV10,
+		v11::MigrateV10ToV11,
+		v12::MigrateV11ToV12,
+		v13::MigrateV12ToV13,
+		v14::MigrateV13ToV14,
+		v15::MigrateV14ToV15,
+		v16::MigrateV15ToV16,
+		v17::MigrateV16ToV17,
+		v18::MigrateV17ToV18,
+		v4::{self, ShadowItemV4},
+		v5::{self, MigrateV4ToV5},
+		v6::MigrateV5ToV6,
+		v7::MigrateV6ToV7,
+		v8::MigrateV7ToV8,
+		v9::MigrateV8ToV9,
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+	use sp_runtime::testing::H256;
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(4).put::<Shadow>();
+		let old_item = ShadowItemV4::<Test> {
+			id: [7u8; 32],
+			cid: b"QmTest123".to_vec().try_into().unwrap(),
+			encrypted_key: b"encrypted_key_123".to_vec().try_into().unwrap(),
+			timestamp: 1,
+			source: 0,
+			metadata: b"test metadata".to_vec().try_into().unwrap(),
+			pinned: false,
+			encrypted_metadata: None,
+		};
+		v4::ShadowItems::<Test>::insert(1, BoundedVec::truncate_from(vec![old_item]));
+
+		MigrateV4ToV5::<Test>::on_runtime_upgrade();
+
+		let items = v5::ShadowItems::<Test>::get(1);
+		assert_eq!(items.len(), 1);
+		assert_eq!(items[0].id, H256::repeat_byte(7));
+		assert_eq!(items[0].cid, b"QmTest123".to_vec());
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(5));
+
+		// The migrated id is what clients pass to `delete_shadow_item`, once the later
+		// migrations have brought the rest of the pallet's storage up to date
+		<(
+			MigrateV5ToV6<Test>,
+			MigrateV6ToV7<Test>,
+			MigrateV7ToV8<Test>,
+			MigrateV8ToV9<Test>,
+			MigrateV9ToV10<Test>,
+			MigrateV10ToV11<Test>,
+			MigrateV11ToV12<Test>,
+			MigrateV12ToV13<Test>,
+			MigrateV13ToV14<Test>,
+			MigrateV14ToV15<Test>,
+			MigrateV15ToV16<Test>,
+			MigrateV16ToV17<Test>,
+			MigrateV17ToV18<Test>,
+		) as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), H256::repeat_byte(7)));
+		assert!(Shadow::items_of(&1).is_empty());
+	});
+}
+
+#[test]
+fn delete_matches_blake2_256_item_id() {
+	use sp_runtime::traits::Hash;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+
+		// The first item stored by the account has counter 1, here in block 1 at extrinsic 0
+		let expected = <Test as frame_system::Config>::Hashing::hash_of(&(
+			1u64,
+			1u64,
+			b"QmTest".to_vec(),
+			1u64,
+			Some(0u32),
+		));
+		assert_eq!(Shadow::items_of(&1)[0].id, expected);
+
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), expected));
+		assert!(Shadow::items_of(&1).is_empty());
+	});
+}
+
+#[test]
+fn hash_id_is_the_id_reported_at_submit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = match System::events().last().map(|record| record.event.clone()) {
+			Some(RuntimeEvent::Shadow(Event::ShadowItemStored { item_id, .. })) => item_id,
+			other => panic!("unexpected event {:?}", other),
+		};
+
+		assert_eq!(Shadow::items_of(&1)[0].hash_id(), item_id);
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), item_id));
+	});
+}
+
+#[test]
+fn trusted_submitters_are_admin_managed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Shadow::add_trusted(RuntimeOrigin::signed(1), 2), DispatchError::BadOrigin);
+
+		assert_ok!(Shadow::add_trusted(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::TrustedAdded { who: 2 }.into());
+		assert!(Shadow::is_trusted(&2));
+		assert!(!Shadow::is_trusted(&1));
+
+		assert_noop!(
+			Shadow::remove_trusted(RuntimeOrigin::signed(1), 2),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Shadow::remove_trusted(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::TrustedRemoved { who: 2 }.into());
+		assert!(!Shadow::is_trusted(&2));
+	});
+}
+
+#[test]
+fn item_ids_paged_follows_cursor() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for i in 0..5u8 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				vec![b'Q', i],
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		let mut all: Vec<_> = Shadow::items_of(&1).iter().map(|item| item.id).collect();
+		all.sort();
+
+		// Pages follow id order
+		let first = Shadow::item_ids_paged(&1, None, 3);
+		assert_eq!(first, all[..3].to_vec());
+		let second = Shadow::item_ids_paged(&1, first.last().copied(), 3);
+		assert_eq!(second, all[3..].to_vec());
+		assert!(Shadow::item_ids_paged(&1, second.last().copied(), 3).is_empty());
+
+		// A cursor need not be one of the account's ids
+		let cursor = <Test as frame_system::Config>::Hash::zero();
+		assert_eq!(Shadow::item_ids_paged(&1, Some(cursor), 3), first);
+	});
+}
+
+#[test]
+fn item_ids_paged_survives_deletes_between_pages() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for i in 0..5u8 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				vec![b'Q', i],
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		let mut all: Vec<_> = Shadow::items_of(&1).iter().map(|item| item.id).collect();
+		all.sort();
+
+		let first = Shadow::item_ids_paged(&1, None, 2);
+		assert_eq!(first, all[..2].to_vec());
+
+		// Deleting paged items, the cursor among them, moves the last item into a freed
+		// slot without skipping or repeating any
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), all[0]));
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), all[1]));
+		let second = Shadow::item_ids_paged(&1, first.last().copied(), 2);
+		assert_eq!(second, all[2..4].to_vec());
+
+		assert_item_index_consistent(1);
+		assert_eq!(Shadow::item_ids_paged(&1, second.last().copied(), 2), vec![all[4]]);
+	});
+}
+
+#[test]
+fn reaping_account_clears_consent_and_items() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 100));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 20, 100));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for cid in [b"QmOne".to_vec(), b"QmTwo".to_vec()] {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid,
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		let item_id = Shadow::items_of(&1)[0].id;
+		assert_ok!(Shadow::authorize_agent(RuntimeOrigin::signed(1), 10));
+		assert_ok!(Shadow::authorize_session(RuntimeOrigin::signed(1), 11, 5));
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item_id, 2, b"key".to_vec()));
+		assert_ok!(Shadow::access_item(RuntimeOrigin::signed(2), 1, item_id));
+		assert_ok!(Shadow::flag_item(RuntimeOrigin::signed(20), 1, item_id, 3));
+		// Keys shared with the reaped account go too
+		assert_ok!(grant_consent_as(3, b"other_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(3),
+			b"QmOther".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let other_item_id = Shadow::items_of(&3)[0].id;
+		assert_ok!(Shadow::share_item(
+			RuntimeOrigin::signed(3),
+			other_item_id,
+			1,
+			b"key".to_vec()
+		));
+
+		// Dropping the balance to zero reaps the account
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 0));
+
+		assert!(!System::account_exists(&1));
+		assert!(ConsentRecords::<Test>::get(1, 0).is_none());
+		assert!(Shadow::items_of(&1).is_empty());
+		assert!(Shadow::accounts_with_message(b"test_consent".to_vec()).is_empty());
+		assert!(AuthorizedAgents::<Test>::get(1).is_empty());
+		assert!(ConsentHistory::<Test>::get(1).is_empty());
+		System::assert_has_event(Event::AccountReaped { who: 1, items_removed: 2 }.into());
+
+		// The rest is queued for `on_idle`
+		assert_eq!(ReapedItems::<Test>::iter_prefix(1).count(), 2);
+		assert!(ReapedAccounts::<Test>::contains_key(1));
+		assert_eq!(Flags::<Test>::iter_prefix(1).count(), 1);
+		Shadow::on_idle(System::block_number(), Weight::MAX);
+
+		assert_eq!(ReapedItems::<Test>::iter_prefix(1).count(), 0);
+		assert!(!ReapedAccounts::<Test>::contains_key(1));
+		assert_eq!(ItemIndex::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(Sessions::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(Flags::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(Balances::reserved_balance(20), 0);
+		assert_eq!(AccessLog::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(SharedKeys::<Test>::iter_prefix(2).count(), 0);
+		assert!(ShareRecipients::<Test>::get(item_id).is_empty());
+		assert_eq!(SharedKeys::<Test>::iter_prefix(1).count(), 0);
+		assert!(ShareRecipients::<Test>::get(other_item_id).is_empty());
+	});
+}
+
+#[test]
+fn reaped_account_cleanup_is_spread_over_idle_blocks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 100));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(3, "QmReap")
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+		assert_ok!(Shadow::add_tag(RuntimeOrigin::signed(1), item_id, b"work".to_vec()));
+		assert_ok!(Shadow::authorize_session(RuntimeOrigin::signed(1), 11, 5));
+		assert_ok!(Shadow::opt_out(RuntimeOrigin::signed(1)));
+		let next_consent_id = NextConsentId::<Test>::get(1);
+
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 0));
+
+		// Opting out and consent ids outlive the account
+		assert!(OptedOut::<Test>::contains_key(1));
+		assert_eq!(NextConsentId::<Test>::get(1), next_consent_id);
+
+		// Room for a single item
+		let step = <() as WeightInfo>::clear_reaped_item();
+		let used = Shadow::on_idle(System::block_number(), step);
+		assert!(used.all_lte(step));
+		assert_eq!(ReapedItems::<Test>::iter_prefix(1).count(), 2);
+		assert!(ReapedAccounts::<Test>::contains_key(1));
+
+		// Nothing is done without room for a step
+		assert_eq!(Shadow::on_idle(System::block_number(), Weight::zero()), Weight::zero());
+		assert_eq!(ReapedItems::<Test>::iter_prefix(1).count(), 2);
+
+		Shadow::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(ReapedItems::<Test>::iter_prefix(1).count(), 0);
+		assert!(!ReapedAccounts::<Test>::contains_key(1));
+		assert!(Shadow::items_with_tag(&1, b"work".to_vec()).is_empty());
+		assert_eq!(Sessions::<Test>::iter_prefix(1).count(), 0);
+	});
+}
+
+#[test]
+fn recreated_account_keeps_sessions_given_before_cleanup() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 100));
+		assert_ok!(Shadow::authorize_session(RuntimeOrigin::signed(1), 11, 5));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 0));
+		assert!(ReapedAccounts::<Test>::contains_key(1));
+
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 100));
+		Shadow::on_idle(System::block_number(), Weight::MAX);
+
+		assert!(!ReapedAccounts::<Test>::contains_key(1));
+		assert_eq!(Sessions::<Test>::iter_prefix(1).count(), 1);
+	});
+}
+
+#[test]
+fn draining_account_below_existential_deposit_revokes_consent_and_deletes_items() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 100));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(grant_consent_as(1, b"other_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(3, "QmReap")
+		));
+		System::reset_events();
+
+		// Sending away the whole balance leaves nothing above the existential deposit
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), 2, 100));
+
+		assert!(!System::account_exists(&1));
+		assert!(Shadow::list_consents(&1).is_empty());
+		assert!(!ShadowItems::<Test>::contains_key(1));
+		assert_eq!(Shadow::total_items(), 0);
+		Shadow::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(ItemIndex::<Test>::iter_prefix(1).count(), 0);
+		for consent_id in [0, 1] {
+			System::assert_has_event(
+				Event::ConsentRevoked {
+					who: 1,
+					consent_id,
+					reason: RevocationReason::AccountReaped,
+				}
+				.into(),
+			);
+		}
+		System::assert_has_event(Event::AllShadowItemsDeleted { who: 1, count: 3 }.into());
+		System::assert_has_event(Event::AccountReaped { who: 1, items_removed: 3 }.into());
+	});
+}
+
+#[test]
+fn flag_item_raises_review_at_threshold() {
+	new_test_ext().execute_with(|| {
+		for flagger in [20, 21, 22] {
+			assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), flagger, 100));
+		}
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+
+		assert_noop!(
+			Shadow::flag_item(
+				RuntimeOrigin::signed(20),
+				1,
+				<Test as frame_system::Config>::Hash::repeat_byte(0xAB),
+				0
+			),
+			Error::<Test>::ItemNotFound
+		);
+
+		assert_ok!(Shadow::flag_item(RuntimeOrigin::signed(20), 1, item_id, 3));
+		System::assert_last_event(
+			Event::ItemFlagged { who: 1, item_id, flagger: 20, reason: 3 }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(20), FlagDeposit::get());
+		assert_noop!(
+			Shadow::flag_item(RuntimeOrigin::signed(20), 1, item_id, 3),
+			Error::<Test>::AlreadyFlagged
+		);
+
+		// The second flag reaches the threshold; the item is kept
+		assert_ok!(Shadow::flag_item(RuntimeOrigin::signed(21), 1, item_id, 1));
+		System::assert_last_event(Event::ItemNeedsReview { who: 1, item_id, flags: 2 }.into());
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+
+		assert_noop!(
+			Shadow::resolve_flags(RuntimeOrigin::signed(1), 1, item_id, true),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Shadow::resolve_flags(RuntimeOrigin::root(), 1, item_id, true));
+		System::assert_last_event(
+			Event::FlagsResolved { who: 1, item_id, frivolous: true }.into(),
+		);
+		assert!(Flags::<Test>::get(1, item_id).is_empty());
+		assert_eq!(Balances::reserved_balance(20), 0);
+		assert_eq!(Balances::free_balance(20), 100 - FlagDeposit::get());
+
+		// Upheld flags get their deposits back
+		assert_ok!(Shadow::flag_item(RuntimeOrigin::signed(22), 1, item_id, 0));
+		assert_ok!(Shadow::resolve_flags(RuntimeOrigin::root(), 1, item_id, false));
+		assert_eq!(Balances::free_balance(22), 100);
+		assert_noop!(
+			Shadow::resolve_flags(RuntimeOrigin::root(), 1, item_id, false),
+			Error::<Test>::NoFlags
+		);
+	});
+}
+
+#[test]
+fn migrate_v5_to_v6_tags_items_with_version() {
+	use crate::migrations::{
+		v5::{self, ShadowItemV5},
+		v6::{self, MigrateV5ToV6, VersionedShadowItemV6},
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(5).put::<Shadow>();
+		let id = <Test as frame_system::Config>::Hash::repeat_byte(7);
+		let old_item = ShadowItemV5::<Test> {
+			id,
+			cid: b"QmTest123".to_vec().try_into().unwrap(),
+			encrypted_key: b"encrypted_key_123".to_vec().try_into().unwrap(),
+			timestamp: 1,
+			source: 1,
+			metadata: b"test metadata".to_vec().try_into().unwrap(),
+			pinned: true,
+			encrypted_metadata: Some(b"notes".to_vec().try_into().unwrap()),
+		};
+		v5::ShadowItems::<Test>::insert(1, BoundedVec::truncate_from(vec![old_item]));
+
+		MigrateV5ToV6::<Test>::on_runtime_upgrade();
+
+		let stored = v6::ShadowItems::<Test>::get(1).into_inner();
+		let [VersionedShadowItemV6::V1(item)] = &stored[..] else { panic!("expected one item") };
+		assert_eq!(item.id, id);
+		assert_eq!(item.source, 1);
+		assert!(item.pinned);
+		assert_eq!(item.encrypted_metadata, Some(b"notes".to_vec().try_into().unwrap()));
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(6));
+	});
+}
+
+#[test]
+fn versioned_item_blob_decodes_to_current_layout() {
+	use codec::Decode;
+
+	let old_item = ShadowItemV1::<Test> {
+		id: <Test as frame_system::Config>::Hash::repeat_byte(7),
+		cid: b"QmTest123".to_vec().try_into().unwrap(),
+		encrypted_key: b"encrypted_key_123".to_vec().try_into().unwrap(),
+		timestamp: 1,
+		source: Source::GitHub,
+		metadata: b"test metadata".to_vec().try_into().unwrap(),
+		pinned: false,
+		encrypted_metadata: None,
+	};
+	let item = ShadowItem::<Test> {
+		id: old_item.id,
+		cid: old_item.cid.clone(),
+		encrypted_key: old_item.encrypted_key.clone(),
+		timestamp: old_item.timestamp,
+		source: old_item.source,
+		metadata: old_item.metadata.clone(),
+		pinned: old_item.pinned,
+		encrypted_metadata: old_item.encrypted_metadata.clone(),
+		tags: Default::default(),
+		deposit: 0,
+		expires_at: None,
+	};
+	let tagged_item = ShadowItemV2::<Test> {
+		id: old_item.id,
+		cid: old_item.cid.clone(),
+		encrypted_key: old_item.encrypted_key.clone(),
+		timestamp: old_item.timestamp,
+		source: old_item.source,
+		metadata: old_item.metadata.clone(),
+		pinned: old_item.pinned,
+		encrypted_metadata: old_item.encrypted_metadata.clone(),
+		tags: Default::default(),
+	};
+	let deposit_item = ShadowItemV3::<Test> {
+		id: old_item.id,
+		cid: old_item.cid.clone(),
+		encrypted_key: old_item.encrypted_key.clone(),
+		timestamp: old_item.timestamp,
+		source: old_item.source,
+		metadata: old_item.metadata.clone(),
+		pinned: old_item.pinned,
+		encrypted_metadata: old_item.encrypted_metadata.clone(),
+		tags: Default::default(),
+		deposit: 0,
+	};
+
+	// A versioned blob is the untagged item prefixed with its variant index.
+	let old_blob = VersionedShadowItem::V1(old_item.clone()).encode();
+	assert_eq!(old_blob[0], 0);
+	assert_eq!(&old_blob[1..], &old_item.encode()[..]);
+	let tagged_blob = VersionedShadowItem::V2(tagged_item.clone()).encode();
+	assert_eq!(tagged_blob[0], 1);
+	assert_eq!(&tagged_blob[1..], &tagged_item.encode()[..]);
+	let deposit_blob = VersionedShadowItem::V3(deposit_item.clone()).encode();
+	assert_eq!(deposit_blob[0], 2);
+	assert_eq!(&deposit_blob[1..], &deposit_item.encode()[..]);
+	let blob = VersionedShadowItem::from(item.clone()).encode();
+	assert_eq!(blob[0], 3);
+	assert_eq!(&blob[1..], &item.encode()[..]);
+
+	// Items written before tags, deposits or expiry existed read back with none
+	let decoded = VersionedShadowItem::<Test>::decode(&mut &old_blob[..]).unwrap();
+	assert_eq!(decoded.current(), item);
+	let decoded = VersionedShadowItem::<Test>::decode(&mut &tagged_blob[..]).unwrap();
+	assert_eq!(decoded.current(), item);
+	let decoded = VersionedShadowItem::<Test>::decode(&mut &deposit_blob[..]).unwrap();
+	assert_eq!(decoded.current(), item);
+	let decoded = VersionedShadowItem::<Test>::decode(&mut &blob[..]).unwrap();
+	assert_eq!(decoded.current(), item);
+}
+
+#[test]
+fn source_breakdown_counts_items_per_source() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Shadow::source_breakdown(&1), vec![]);
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for (i, source) in
+			[Source::Twitter, Source::GitHub, Source::GitHub, Source::Twitter, Source::GitHub]
+				.into_iter()
+				.enumerate()
+		{
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				source,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+
+		assert_eq!(
+			Shadow::source_breakdown(&1),
+			vec![(Source::GitHub, 3), (Source::Twitter, 2)]
+		);
+		assert_eq!(Shadow::source_breakdown(&2), vec![]);
+	});
+}
+
+#[test]
+fn reserved_slots_are_consumed_by_own_submissions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		assert_ok!(Shadow::reserve_slots(RuntimeOrigin::signed(1), 3));
+		System::assert_last_event(Event::SlotsReserved { who: 1, count: 3, reserved: 3 }.into());
+
+		for i in 0..2 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		assert_eq!(ReservedSlots::<Test>::get(1), 1);
+
+		// Release the leftover slot
+		assert_ok!(Shadow::release_slots(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::SlotsReleased { who: 1, count: 1 }.into());
+		assert!(!ReservedSlots::<Test>::contains_key(1));
+		assert_noop!(
+			Shadow::release_slots(RuntimeOrigin::signed(1)),
+			Error::<Test>::NoReservedSlots
+		);
+	});
+}
+
+#[test]
+fn reserved_slots_are_withheld_from_delegated_submissions() {
+	new_test_ext().execute_with(|| {
+		let max = MaxItemsPerAccount::get();
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::authorize_agent(RuntimeOrigin::signed(1), 20));
+
+		assert_ok!(Shadow::reserve_slots(RuntimeOrigin::signed(1), max - 1));
+		assert_noop!(
+			Shadow::reserve_slots(RuntimeOrigin::signed(1), 2),
+			Error::<Test>::InsufficientCapacity
+		);
+
+		// The agent can fill the one unreserved slot but no more
+		assert_ok!(Shadow::submit_shadow_item_for(
+			RuntimeOrigin::signed(20),
+			1,
+			b"QmAgent1".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec()
+		));
+		assert_noop!(
+			Shadow::submit_shadow_item_for(
+				RuntimeOrigin::signed(20),
+				1,
+				b"QmAgent2".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec()
+			),
+			Error::<Test>::TooManyItems
+		);
+
+		// The owner submits into its reservation
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmOwner".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		assert_eq!(ReservedSlots::<Test>::get(1), max - 2);
+		assert_eq!(Shadow::items_of(&1).len(), 2);
+	});
+}
+
+#[test]
+fn normalize_message_hash_stores_hash_of_input() {
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	new_test_ext().execute_with(|| {
+		NormalizeMessageHash::set(true);
+		let input = b"I consent to shadowing my GitHub activity".to_vec();
+		let expected = BlakeTwo256::hash(&input).as_bytes().to_vec();
+
+		assert_ok!(grant_consent_as(1, input, None));
+		let record = ConsentRecords::<Test>::get(1, 0).unwrap();
+		assert_eq!(record.message_hash.to_vec(), expected);
+		System::assert_last_event(
+			Event::ConsentGranted {
+				who: 1,
+				consent_id: 0,
+				message_hash: expected,
+				granted_at: 1,
+				expires_at: None,
+				receipt: record.receipt,
+			}
+			.into(),
+		);
+
+		// Without normalization the bytes are stored as sent
+		NormalizeMessageHash::set(false);
+		assert_ok!(grant_consent_as(2, b"raw".to_vec(), None));
+		assert_eq!(
+			ConsentRecords::<Test>::get(2, 0).unwrap().message_hash.to_vec(),
+			b"raw".to_vec()
+		);
+	});
+}
+
+#[test]
+fn items_by_cid_prefix_filters_and_limits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for cid in [&b"QmA1"[..], b"QmB1", b"QmA2", b"QmA3", b"QmB2"] {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+
+		let cids = |items: Vec<ShadowItem<Test>>| {
+			items.into_iter().map(|item| item.cid.to_vec()).collect::<Vec<_>>()
+		};
+		assert_eq!(
+			cids(Shadow::items_by_cid_prefix(&1, b"QmA", 10)),
+			vec![b"QmA1".to_vec(), b"QmA2".to_vec(), b"QmA3".to_vec()]
+		);
+		assert_eq!(
+			cids(Shadow::items_by_cid_prefix(&1, b"QmA", 2)),
+			vec![b"QmA1".to_vec(), b"QmA2".to_vec()]
+		);
+		assert!(Shadow::items_by_cid_prefix(&1, b"QmC", 10).is_empty());
+	});
+}
+
+#[test]
+fn items_by_source_filters_and_paginates() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let submissions = [
+			(&b"QmG1"[..], Source::GitHub),
+			(b"QmT1", Source::Twitter),
+			(b"QmG2", Source::GitHub),
+			(b"QmR1", Source::Reddit),
+			(b"QmG3", Source::GitHub),
+			(b"QmT2", Source::Twitter),
+		];
+		for (cid, source) in submissions {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				source,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+
+		let cids = |items: Vec<ShadowItem<Test>>| {
+			items.into_iter().map(|item| item.cid.to_vec()).collect::<Vec<_>>()
+		};
+		assert_eq!(
+			cids(Shadow::items_by_source(&1, Source::GitHub, 0, 10)),
+			vec![b"QmG1".to_vec(), b"QmG2".to_vec(), b"QmG3".to_vec()]
+		);
+		assert_eq!(
+			cids(Shadow::items_by_source(&1, Source::Twitter, 0, 10)),
+			vec![b"QmT1".to_vec(), b"QmT2".to_vec()]
+		);
+
+		// Pages follow storage order
+		assert_eq!(
+			cids(Shadow::items_by_source(&1, Source::GitHub, 0, 2)),
+			vec![b"QmG1".to_vec(), b"QmG2".to_vec()]
+		);
+		assert_eq!(
+			cids(Shadow::items_by_source(&1, Source::GitHub, 2, 2)),
+			vec![b"QmG3".to_vec()]
+		);
+
+		// Out-of-range offsets, zero limits and oversized limits do not panic
+		assert!(Shadow::items_by_source(&1, Source::GitHub, 3, 10).is_empty());
+		assert!(Shadow::items_by_source(&1, Source::GitHub, u32::MAX, u32::MAX).is_empty());
+		assert!(Shadow::items_by_source(&1, Source::GitHub, 0, 0).is_empty());
+		assert_eq!(Shadow::items_by_source(&1, Source::GitHub, 0, u32::MAX).len(), 3);
+		assert!(Shadow::items_by_source(&1, Source::GitLab, 0, 10).is_empty());
+		assert!(Shadow::items_by_source(&2, Source::GitHub, 0, 10).is_empty());
+	});
+}
+
+#[test]
+fn submissions_need_a_registered_source() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let submit = |source: Source| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest".to_vec(),
+				b"encrypted_key".to_vec(),
+				source,
+				b"metadata".to_vec(),
+				None,
+			)
+		};
+
+		// Every source is registered at genesis
+		for source in Source::ALL {
+			assert_eq!(
+				RegisteredSources::<Test>::get(u16::from(source)).unwrap().into_inner(),
+				source.name().to_vec()
+			);
+		}
+		assert_ok!(submit(Source::Twitter));
+		RegisteredSources::<Test>::remove(u16::from(Source::GitLab));
+		assert_noop!(submit(Source::GitLab), Error::<Test>::InvalidSource);
+
+		// Registering is reserved to `RegisterOrigin`, and names are bounded
+		assert_noop!(
+			Shadow::register_source(RuntimeOrigin::signed(1), 2, b"GitLab".to_vec()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Shadow::register_source(RuntimeOrigin::root(), 2, vec![b'n'; 17]),
+			Error::<Test>::SourceNameTooLong
+		);
+
+		// Ids without a `Source` variant cannot be registered
+		for id in [5, 256, u16::MAX] {
+			assert_noop!(
+				Shadow::register_source(RuntimeOrigin::root(), id, b"Mastodon".to_vec()),
+				Error::<Test>::InvalidSource
+			);
+		}
+
+		assert_ok!(Shadow::register_source(RuntimeOrigin::root(), 2, b"GitLab".to_vec()));
+		System::assert_last_event(Event::SourceRegistered { id: 2, name: b"GitLab".to_vec() }.into());
+		assert_ok!(submit(Source::GitLab));
+		assert_eq!(Shadow::items_by_source(&1, Source::GitLab, 0, 10).len(), 1);
+
+		// Unregistering rejects new submissions but keeps the items already submitted
+		assert_noop!(
+			Shadow::unregister_source(RuntimeOrigin::signed(1), 2),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Shadow::unregister_source(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::SourceUnregistered { id: 2 }.into());
+		assert!(!RegisteredSources::<Test>::contains_key(2));
+		assert_noop!(submit(Source::GitLab), Error::<Test>::InvalidSource);
+		assert_eq!(Shadow::items_by_source(&1, Source::GitLab, 0, 10).len(), 1);
+		assert_noop!(
+			Shadow::unregister_source(RuntimeOrigin::root(), 2),
+			Error::<Test>::InvalidSource
+		);
+	});
+}
+
+#[test]
+fn migrate_v16_to_v17_registers_every_source() {
+	use crate::migrations::v17::MigrateV16ToV17;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		let _ = RegisteredSources::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(16).put::<Shadow>();
+
+		MigrateV16ToV17::<Test>::on_runtime_upgrade();
+
+		for (id, name) in [(2, &b"GitLab"[..]), (3, b"Reddit"), (4, b"StackOverflow")] {
+			assert_eq!(RegisteredSources::<Test>::get(id).unwrap().into_inner(), name.to_vec());
+		}
+		assert_eq!(RegisteredSources::<Test>::iter().count(), 5);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(17));
+	});
+}
+
+#[test]
+fn migrate_v17_to_v18_types_consent_scopes() {
+	use crate::migrations::{
+		v15::{self, ConsentRecordV15},
+		v18::MigrateV17ToV18,
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(17).put::<Shadow>();
+		let record = |scope: Vec<u8>| ConsentRecordV15::<Test> {
+			granted_at: 1,
+			expires_at: None,
+			message_hash: b"test_consent".to_vec().try_into().unwrap(),
+			scope: scope.into_iter().collect::<BTreeSet<_>>().try_into().unwrap(),
+			needs_resign: false,
+			signature_scheme: None,
+			cosigned: false,
+			receipt: Default::default(),
+			delegate: None,
+		};
+		v15::ConsentRecords::<Test>::insert(1, 0, record(vec![]));
+		v15::ConsentRecords::<Test>::insert(1, 1, record(vec![1, 9]));
+		// Dropping the unknown id would widen this consent to every source
+		v15::ConsentRecords::<Test>::insert(2, 0, record(vec![9]));
+		let message_hash: BoundedMessageHash<Test> = b"test_consent".to_vec().try_into().unwrap();
+		ConsentByMessage::<Test>::insert(message_hash, BoundedVec::truncate_from(vec![1, 2]));
+
+		MigrateV17ToV18::<Test>::on_runtime_upgrade();
+
+		assert!(ConsentRecords::<Test>::get(1, 0).unwrap().scope.is_empty());
+		assert_eq!(
+			ConsentRecords::<Test>::get(1, 1).unwrap().scope.into_iter().collect::<Vec<_>>(),
+			vec![Source::Twitter]
+		);
+		assert!(ConsentRecords::<Test>::get(2, 0).is_none());
+		assert_eq!(Shadow::accounts_with_message(b"test_consent".to_vec()), vec![1]);
+		System::assert_has_event(
+			Event::ConsentRevoked { who: 2, consent_id: 0, reason: RevocationReason::UnknownSource }
+				.into(),
+		);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(18));
+	});
+}
+
+#[test]
+fn combined_item_size_is_capped() {
+	new_test_ext().execute_with(|| {
+		MaxItemTotalBytes::set(40);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		// Every field fits its own limit, but together they exceed the combined one
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest123".to_vec(),
+				vec![b'k'; 16],
+				Source::GitHub,
+				vec![b'm'; 16],
+				None
+			),
+			Error::<Test>::ItemTooLarge
+		);
+
+		// Exactly at the limit is accepted
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest12".to_vec(),
+			vec![b'k'; 16],
+			Source::GitHub,
+			vec![b'm'; 16],
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+
+		// Updates are held to the same limit
+		assert_noop!(
+			Shadow::update_shadow_item(
+				RuntimeOrigin::signed(1),
+				item_id,
+				b"QmTest123".to_vec(),
+				vec![b'k'; 16],
+				vec![b'm'; 16]
+			),
+			Error::<Test>::ItemTooLarge
+		);
+	});
+}
+
+#[test]
+fn truncate_to_max_items_keeps_newest_items() {
+	use crate::migrations::truncate::{self, TruncateToMaxItems};
+	use frame_support::traits::{OnRuntimeUpgrade, ReservableCurrency};
+
+	new_test_ext().execute_with(|| {
+		let tag: BoundedTag<Test> = b"work".to_vec().try_into().unwrap();
+		let item = |i: u8| {
+			let mut item = VersionedShadowItem::V1(ShadowItemV1::<Test> {
+				id: <Test as frame_system::Config>::Hash::repeat_byte(i),
+				cid: b"QmTest123".to_vec().try_into().unwrap(),
+				encrypted_key: b"encrypted_key_123".to_vec().try_into().unwrap(),
+				// Stored newest first from index 75 on
+				timestamp: if i < 75 { i as u64 } else { 224 - i as u64 },
+				source: Source::GitHub,
+				metadata: b"test metadata".to_vec().try_into().unwrap(),
+				pinned: false,
+				encrypted_metadata: None,
+			})
+			.current();
+			item.deposit = 2;
+			item.tags = vec![tag.clone()].try_into().unwrap();
+			item
+		};
+		let items: Vec<_> = (0..150).map(item).collect();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 1_000));
+		assert_ok!(Balances::reserve(&1, 300));
+		for (index, item) in items.iter().enumerate() {
+			ItemIndex::<Test>::insert(1, item.id, index as u32);
+		}
+		TagIndex::<Test>::insert(
+			1,
+			&tag,
+			BoundedVec::truncate_from(vec![items[0].id, items[60].id]),
+		);
+		AccountStats::<Test>::insert(
+			1,
+			Stats { lifetime_submitted: 150, lifetime_deleted: 0, current: 150 },
+		);
+		TotalItems::<Test>::put(153);
+		let dropped_id = items[0].id;
+		let recipient_key: BoundedKey<Test> = b"k".to_vec().try_into().unwrap();
+		ShareRecipients::<Test>::insert(dropped_id, BoundedVec::truncate_from(vec![2u64]));
+		SharedKeys::<Test>::insert(2, dropped_id, recipient_key);
+		truncate::ShadowItems::<Test>::insert(
+			1,
+			items.into_iter().map(Into::into).collect::<Vec<VersionedShadowItem<Test>>>(),
+		);
+		truncate::ShadowItems::<Test>::insert(
+			2,
+			(0..3).map(|i| item(i).into()).collect::<Vec<VersionedShadowItem<Test>>>(),
+		);
+		// Over the bound, the items do not decode at all
+		assert!(Shadow::items_of(&1).is_empty());
+
+		TruncateToMaxItems::<Test>::on_runtime_upgrade();
+
+		// The 100 newest items, timestamps 50 to 149, stay in their stored order
+		let items = Shadow::items_of(&1);
+		assert_eq!(items.len(), 100);
+		assert_eq!(items[0].timestamp, 50);
+		assert_eq!(items[24].timestamp, 74);
+		assert_eq!(items[25].timestamp, 149);
+		assert_eq!(items[99].timestamp, 75);
+		assert!(items.iter().all(|item| item.timestamp >= 50));
+		System::assert_has_event(Event::MigrationTruncated { who: 1, dropped: 50 }.into());
+
+		// Survivors are re-indexed and the dropped items cleaned up
+		assert_item_index_consistent(1);
+		assert_eq!(Balances::reserved_balance(1), 200);
+		assert_eq!(Shadow::items_with_tag(&1, b"work".to_vec()), vec![items[10].id]);
+		assert!(!ShareRecipients::<Test>::contains_key(dropped_id));
+		assert!(!SharedKeys::<Test>::contains_key(2, dropped_id));
+		let stats = Shadow::account_stats(&1);
+		assert_eq!((stats.current, stats.lifetime_deleted), (100, 50));
+		assert_eq!(TotalItems::<Test>::get(), 103);
+		let bytes = items.iter().map(Shadow::stored_size).sum::<u32>();
+		assert_eq!(AccountBytes::<Test>::get(1), bytes);
+
+		// Accounts within the cap are untouched
+		assert_eq!(Shadow::items_of(&2).len(), 3);
+		assert_eq!(
+			System::events()
+				.iter()
+				.filter(|record| matches!(
+					record.event,
+					RuntimeEvent::Shadow(Event::MigrationTruncated { .. })
+				))
+				.count(),
+			1
+		);
+	});
+}
+
+#[test]
+fn calls_succeed_under_utility_batch() {
+	new_test_ext().execute_with(|| {
+		let submit = |cid: &[u8]| {
+			RuntimeCall::Shadow(crate::Call::submit_shadow_item {
+				cid: cid.to_vec(),
+				encrypted_key: b"encrypted_key".to_vec(),
+				source: Source::GitHub,
+				metadata: b"metadata".to_vec(),
+				expires_at: None,
+			})
+		};
+		let calls = vec![
+			RuntimeCall::Shadow(crate::Call::grant_consent {
+				message_hash: b"test_consent".to_vec(),
+				duration: None,
+				scope: vec![],
+				delegate: None,
+				signature: consent_signature(1, b"test_consent", None),
+				signer: 1,
+			}),
+			submit(b"QmBatch1"),
+			submit(b"QmBatch2"),
+		];
+
+		assert_ok!(Utility::batch(RuntimeOrigin::signed(1), calls));
+		System::assert_has_event(pallet_utility::Event::BatchCompleted.into());
+
+		assert!(ConsentRecords::<Test>::contains_key(1, 0));
+		let items = Shadow::items_of(&1);
+		assert_eq!(items.len(), 2);
+		assert_eq!(items[0].cid.to_vec(), b"QmBatch1".to_vec());
+		assert_eq!(items[1].cid.to_vec(), b"QmBatch2".to_vec());
+		assert_ne!(items[0].id, items[1].id);
+	});
+}
+
+#[test]
+fn expired_consent_is_rejected_under_reject_policy() {
+	new_test_ext().execute_with(|| {
+		ExpiredPolicy::set(ExpiredConsentPolicy::Reject);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(10)));
+		System::set_block_number(12);
+
+		// The expired record is surfaced, even to the auto-grant flow
+		assert_noop!(
+			Shadow::submit_and_grant(
+				RuntimeOrigin::signed(1),
+				b"test_consent".to_vec(),
+				Some(10),
+				consent_signature(1, b"test_consent", Some(10)),
+				b"QmTest123".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec()
+			),
+			Error::<Test>::ConsentExpired
+		);
+		assert!(Shadow::items_of(&1).is_empty());
+	});
+}
+
+#[test]
+fn expired_consent_is_regranted_under_treat_as_none_policy() {
+	new_test_ext().execute_with(|| {
+		ExpiredPolicy::set(ExpiredConsentPolicy::TreatAsNone);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(10)));
+		System::set_block_number(12);
+
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest123".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			),
+			Error::<Test>::NoConsent
+		);
+
+		// The auto-grant flow records a fresh consent alongside the expired one and submits
+		assert_ok!(Shadow::submit_and_grant(
+			RuntimeOrigin::signed(1),
+			b"test_consent".to_vec(),
+			Some(10),
+			consent_signature(1, b"test_consent", Some(10)),
+			b"QmTest123".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec()
+		));
+		assert_eq!(ConsentRecords::<Test>::get(1, 1).unwrap().expires_at, Some(22));
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+	});
+}
+
+#[test]
+fn prune_empty_accounts_removes_emptied_entries() {
+	new_test_ext().execute_with(|| {
+		for who in [1, 2] {
+			assert_ok!(grant_consent_as(who, b"test_consent".to_vec(), None));
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(who),
+				b"QmTest123".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+
+		// Emptying account 1 leaves a stored empty vector and its records behind
+		let item_id = Shadow::items_of(&1)[0].id;
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), item_id));
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Shadow::set_metadata_limit_for_account(RuntimeOrigin::root(), 1, Some(64)));
+		assert!(ShadowItems::<Test>::contains_key(1));
+		assert!(AccountStats::<Test>::contains_key(1));
+		assert!(SubmissionRate::<Test>::contains_key(1));
+
+		assert_noop!(
+			Shadow::prune_empty_accounts(RuntimeOrigin::signed(1), 10),
+			DispatchError::BadOrigin
+		);
+		// A submission window still open is kept so the rate limit holds
+		assert_ok!(Shadow::prune_empty_accounts(RuntimeOrigin::root(), 10));
+		System::assert_last_event(Event::EmptyAccountsPruned { count: 1 }.into());
+
+		assert!(!ShadowItems::<Test>::contains_key(1));
+		assert!(!AccountStats::<Test>::contains_key(1));
+		assert!(!AccountBytes::<Test>::contains_key(1));
+		assert!(!ItemCounter::<Test>::contains_key(1));
+		assert!(!AccountMetadataLimit::<Test>::contains_key(1));
+		assert!(SubmissionRate::<Test>::contains_key(1));
+		assert_eq!(Shadow::items_of(&2).len(), 1);
+		assert!(AccountStats::<Test>::contains_key(2));
+		assert!(ItemCounter::<Test>::contains_key(2));
+		// Every account was examined, so the next call starts over
+		assert_eq!(PruneCursor::<Test>::get(), None);
+
+		// Pruned statistics alone are cleared once the window ends
+		AccountStats::<Test>::insert(1, Stats::default());
+		System::set_block_number(1 + <Test as Config>::RateLimitWindow::get());
+		assert_ok!(Shadow::prune_empty_accounts(RuntimeOrigin::root(), 10));
+		assert!(!SubmissionRate::<Test>::contains_key(1));
+		assert!(!AccountStats::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn prune_empty_accounts_resumes_from_cursor() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=3u64 {
+			ShadowItems::<Test>::insert(who, BoundedVec::default());
+			AccountStats::<Test>::insert(who, Stats::default());
+		}
+
+		assert_ok!(Shadow::prune_empty_accounts(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::EmptyAccountsPruned { count: 2 }.into());
+		assert!(PruneCursor::<Test>::get().is_some());
+
+		assert_ok!(Shadow::prune_empty_accounts(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::EmptyAccountsPruned { count: 1 }.into());
+		assert_eq!(ShadowItems::<Test>::iter_keys().count(), 0);
+		assert_eq!(AccountStats::<Test>::iter_keys().count(), 0);
+		assert_eq!(PruneCursor::<Test>::get(), None);
+	});
+}
+
+#[test]
+fn revoked_consent_is_distinguished_from_never_granted() {
+	new_test_ext().execute_with(|| {
+		let submit = |who: u64| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(who),
+				b"QmTest123".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			)
+		};
+
+		// Never granted
+		assert_noop!(submit(1), Error::<Test>::NoConsent);
+
+		// Granted, then revoked at block 5
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		System::set_block_number(5);
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 0));
+		assert_eq!(RevokedConsents::<Test>::get(1), Some(5));
+		assert_noop!(submit(1), Error::<Test>::ConsentRevoked);
+
+		// A fresh grant clears the marker
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_eq!(RevokedConsents::<Test>::get(1), None);
+		assert_ok!(submit(1));
+	});
+}
+
+#[test]
+fn grant_consent_verifies_each_signature_scheme() {
+	use crate::signature_scheme::{ED25519, SR25519};
+	use polkadot_sdk::sp_core::{ed25519, sr25519, Pair};
+
+	new_test_ext().execute_with(|| {
+		let message = b"test_consent".to_vec();
+		let sr_pair = sr25519::Pair::from_seed(&[1; 32]);
+		let ed_pair = ed25519::Pair::from_seed(&[2; 32]);
+		SignerKeys::set(vec![(1, sr_pair.public().0), (2, ed_pair.public().0)]);
+		let payload = |who| {
+			let action = ConsentAuthorization::Grant {
+				message_hash: message.clone(),
+				duration: None,
+				scope: Default::default(),
+				delegate: None,
+			};
+			Shadow::consent_payload(&who, action, 1)
+		};
+		let grant = |who, scheme, signature: Vec<u8>| {
+			Shadow::grant_consent(
+				RuntimeOrigin::signed(who),
+				message.clone(),
+				None,
+				vec![],
+				None,
+				ConsentSignature { valid_until: 1, scheme, signature },
+				who,
+			)
+		};
+
+		let sr_signature = sr_pair.sign(&payload(1)).0.to_vec();
+		let ed_signature = ed_pair.sign(&payload(2)).0.to_vec();
+
+		// A signature checked against the wrong scheme or account is rejected
+		assert_noop!(
+			grant(1, ED25519, sr_signature.clone()),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_noop!(
+			grant(2, SR25519, ed_signature.clone()),
+			Error::<Test>::InvalidConsentSignature
+		);
+		assert_noop!(
+			grant(3, SR25519, sr_signature.clone()),
+			Error::<Test>::InvalidConsentSignature
+		);
+
+		// The scheme a grant was verified with is recorded on it
+		assert_ok!(grant(1, SR25519, sr_signature));
+		assert_eq!(ConsentRecords::<Test>::get(1, 0).unwrap().signature_scheme, Some(SR25519));
+		assert_ok!(grant(2, ED25519, ed_signature));
+		assert_eq!(ConsentRecords::<Test>::get(2, 0).unwrap().signature_scheme, Some(ED25519));
+	});
+}
+
+#[test]
+fn grant_consent_rejects_unknown_scheme() {
+	new_test_ext().execute_with(|| {
+		let mut signature = consent_signature(1, b"test_consent", None);
+		signature.scheme = 3;
+		assert_noop!(
+			Shadow::grant_consent(
+				RuntimeOrigin::signed(1),
+				b"test_consent".to_vec(),
+				None,
+				vec![],
+				None,
+				signature,
+				1
+			),
+			Error::<Test>::UnsupportedSignatureScheme
+		);
+	});
+}
+
+#[test]
+fn migrate_v6_to_v7_marks_consents_unsigned() {
+	use crate::migrations::{
+		v3::{self, ConsentRecordV3},
+		v7::{self, MigrateV6ToV7},
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(6).put::<Shadow>();
+		v3::ConsentRecords::<Test>::insert(
+			1,
+			ConsentRecordV3::<Test> {
+				granted_at: 1,
+				expires_at: Some(10),
+				message_hash: b"test_consent".to_vec().try_into().unwrap(),
+				scope: Default::default(),
+				needs_resign: true,
+			},
+		);
+
+		MigrateV6ToV7::<Test>::on_runtime_upgrade();
+
+		let record = v7::ConsentRecords::<Test>::get(1).unwrap();
+		assert_eq!(record.expires_at, Some(10));
+		assert!(record.needs_resign);
+		assert_eq!(record.signature_scheme, None);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(7));
+	});
+}
+
+#[test]
+fn migrate_v8_to_v9_marks_consents_not_cosigned() {
+	use crate::{
+		migrations::{
+			v7::{self, ConsentRecordV7},
+			v9::{self, MigrateV8ToV9},
+		},
+		signature_scheme::SR25519,
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(8).put::<Shadow>();
+		v7::ConsentRecords::<Test>::insert(
+			1,
+			ConsentRecordV7::<Test> {
+				granted_at: 1,
+				expires_at: Some(10),
+				message_hash: b"test_consent".to_vec().try_into().unwrap(),
+				scope: Default::default(),
+				needs_resign: false,
+				signature_scheme: Some(SR25519),
+			},
+		);
+
+		MigrateV8ToV9::<Test>::on_runtime_upgrade();
+
+		let record = v9::ConsentRecords::<Test>::get(1).unwrap();
+		assert_eq!(record.expires_at, Some(10));
+		assert_eq!(record.signature_scheme, Some(SR25519));
+		assert!(!record.cosigned);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(9));
+	});
+}
+
+#[test]
+fn grant_consent_cosigned_requires_compliance_signature() {
+	use crate::signature_scheme::SR25519;
+	use polkadot_sdk::sp_core::{sr25519, Pair};
+
+	new_test_ext().execute_with(|| {
+		let message = b"test_consent".to_vec();
+		let user = sr25519::Pair::from_seed(&[1; 32]);
+		let compliance = sr25519::Pair::from_seed(&[9; 32]);
+		SignerKeys::set(vec![(1, user.public().0), (99, compliance.public().0)]);
+		let sign = |pair: &sr25519::Pair, who, action| ConsentSignature {
+			valid_until: 1,
+			scheme: SR25519,
+			signature: pair.sign(&Shadow::consent_payload(&who, action, 1)).0.to_vec(),
+		};
+		let grant = ConsentAuthorization::Grant {
+			message_hash: message.clone(),
+			duration: None,
+			scope: Default::default(),
+			delegate: None,
+		};
+		let cosign = ConsentAuthorization::Cosign { message_hash: message.clone() };
+		let cosigned = |user_signature, admin_signature| {
+			Shadow::grant_consent_cosigned(
+				RuntimeOrigin::signed(1),
+				message.clone(),
+				None,
+				user_signature,
+				admin_signature,
+			)
+		};
+		let user_signature = sign(&user, 1, grant.clone());
+		let admin_signature = sign(&compliance, 1, cosign.clone());
+
+		// The user signing in place of the compliance account is rejected
+		assert_noop!(
+			cosigned(user_signature.clone(), sign(&user, 1, cosign.clone())),
+			Error::<Test>::MissingCosignature
+		);
+		// The compliance signature does not stand in for the user's
+		assert_noop!(
+			cosigned(sign(&compliance, 1, grant.clone()), admin_signature.clone()),
+			Error::<Test>::InvalidConsentSignature
+		);
+		// The co-signature is bound to the account and is not a grant signature
+		assert_noop!(
+			cosigned(user_signature.clone(), sign(&compliance, 2, cosign.clone())),
+			Error::<Test>::MissingCosignature
+		);
+		assert_noop!(
+			cosigned(user_signature.clone(), sign(&compliance, 1, grant.clone())),
+			Error::<Test>::MissingCosignature
+		);
+
+		assert_ok!(cosigned(user_signature, admin_signature.clone()));
+		let record = ConsentRecords::<Test>::get(1, 0).unwrap();
+		assert!(record.cosigned);
+		assert_eq!(record.signature_scheme, Some(SR25519));
+		assert_eq!(ConsentNonce::<Test>::get(1), 1);
+
+		// The grant used up the nonce, so the co-signature cannot be replayed
+		assert_noop!(
+			cosigned(sign(&user, 1, grant.clone()), admin_signature),
+			Error::<Test>::MissingCosignature
+		);
+
+		// Without a configured compliance account no grant can be co-signed
+		ComplianceAccount::set(None);
+		assert_noop!(
+			cosigned(sign(&user, 1, grant), sign(&compliance, 1, cosign)),
+			Error::<Test>::MissingCosignature
+		);
+	});
+}
+
+#[test]
+fn submit_requires_cosigned_consent_when_configured() {
+	use polkadot_sdk::sp_core::{sr25519, Pair};
+
+	new_test_ext().execute_with(|| {
+		RequireCosignedConsent::set(true);
+		let message = b"test_consent".to_vec();
+		let compliance = sr25519::Pair::from_seed(&[9; 32]);
+		SignerKeys::set(vec![(99, compliance.public().0)]);
+		let submit = || {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest123".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			)
+		};
+
+		assert_ok!(grant_consent_as(1, message.clone(), None));
+		assert_noop!(submit(), Error::<Test>::MissingCosignature);
+		assert!(!Shadow::submit_readiness(&1).consent_valid);
+
+		assert_ok!(Shadow::grant_consent_cosigned(
+			RuntimeOrigin::signed(1),
+			message.clone(),
+			None,
+			consent_signature(1, &message, None),
+			sign_consent_with(
+				&compliance,
+				1,
+				ConsentAuthorization::Cosign { message_hash: message.clone() }
+			)
+		));
+		assert!(Shadow::submit_readiness(&1).consent_valid);
+		assert_ok!(submit());
+	});
+}
+
+#[test]
+fn migrate_v7_to_v8_types_sources_and_drops_unknown() {
+	use crate::migrations::{
+		v5::ShadowItemV5,
+		v6::{self, VersionedShadowItemV6},
+		v8::MigrateV7ToV8,
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		StorageVersion::new(7).put::<Shadow>();
+		let old_item = |id: u8, source: u8| {
+			VersionedShadowItemV6::V1(ShadowItemV5::<Test> {
+				id: <Test as frame_system::Config>::Hash::repeat_byte(id),
+				cid: b"QmTest123".to_vec().try_into().unwrap(),
+				encrypted_key: b"encrypted_key_123".to_vec().try_into().unwrap(),
+				timestamp: 1,
+				source,
+				metadata: b"test metadata".to_vec().try_into().unwrap(),
+				pinned: false,
+				encrypted_metadata: None,
+			})
+		};
+		v6::ShadowItems::<Test>::insert(
+			1,
+			BoundedVec::truncate_from(vec![old_item(1, 0), old_item(2, 9), old_item(3, 1)]),
+		);
+		v6::ShadowItems::<Test>::insert(2, BoundedVec::truncate_from(vec![old_item(4, 200)]));
+
+		MigrateV7ToV8::<Test>::on_runtime_upgrade();
+
+		let items = Shadow::items_of(&1);
+		assert_eq!(items.len(), 2);
+		assert_eq!(items[0].source, Source::GitHub);
+		assert_eq!(items[1].source, Source::Twitter);
+		assert!(!ShadowItems::<Test>::contains_key(2));
+		System::assert_has_event(
+			Event::ShadowItemRemoved {
+				who: 1,
+				item_id: <Test as frame_system::Config>::Hash::repeat_byte(2),
+				reason: RemovalReason::UnknownSource,
+			}
+			.into(),
+		);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(8));
+	});
+}
+
+#[test]
+fn has_cid_reports_stored_cids() {
+	new_test_ext().execute_with(|| {
+		assert!(!Shadow::has_cid(&1, b"QmTest123"));
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest123".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+
+		assert!(Shadow::has_cid(&1, b"QmTest123"));
+		// Only exact matches count, and only for the owning account
+		assert!(!Shadow::has_cid(&1, b"QmTest12"));
+		assert!(!Shadow::has_cid(&1, b"QmOther"));
+		assert!(!Shadow::has_cid(&2, b"QmTest123"));
+	});
+}
+
+#[test]
+fn update_shadow_item_replaces_fields_in_place() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmOld".to_vec(),
+			b"old_key".to_vec(),
+			Source::Twitter,
+			b"old metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+
+		System::set_block_number(5);
+		assert_ok!(Shadow::update_shadow_item(
+			RuntimeOrigin::signed(1),
+			item_id,
+			b"QmNew".to_vec(),
+			b"new_key".to_vec(),
+			b"new metadata".to_vec()
+		));
+		System::assert_last_event(
+			Event::ShadowItemUpdated { who: 1, item_id, cid: b"QmNew".to_vec() }.into(),
+		);
+
+		let items = Shadow::items_of(&1);
+		assert_eq!(items.len(), 1);
+		assert_eq!(items[0].id, item_id);
+		assert_eq!(items[0].cid.to_vec(), b"QmNew".to_vec());
+		assert_eq!(items[0].encrypted_key.to_vec(), b"new_key".to_vec());
+		assert_eq!(items[0].metadata.to_vec(), b"new metadata".to_vec());
+		assert_eq!(items[0].source, Source::Twitter);
+		assert_eq!(items[0].timestamp, 5);
+	});
+}
+
+#[test]
+fn update_shadow_item_rejects_unknown_id_and_long_fields() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmOld".to_vec(),
+			b"old_key".to_vec(),
+			Source::GitHub,
+			b"old metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+		let update = |id, cid: Vec<u8>, key: Vec<u8>, metadata: Vec<u8>| {
+			Shadow::update_shadow_item(RuntimeOrigin::signed(1), id, cid, key, metadata)
+		};
+
+		assert_noop!(
+			update(<Test as frame_system::Config>::Hash::repeat_byte(9), b"QmNew".to_vec(), b"key".to_vec(), vec![]),
+			Error::<Test>::ItemNotFound
+		);
+		assert_noop!(
+			update(item_id, vec![b'Q'; 101], b"key".to_vec(), vec![]),
+			Error::<Test>::CidTooLong
+		);
+		assert_noop!(
+			update(item_id, b"QmNew".to_vec(), vec![b'k'; 513], vec![]),
+			Error::<Test>::KeyTooLong
+		);
+		assert_noop!(
+			update(item_id, b"QmNew".to_vec(), b"key".to_vec(), vec![b'm'; 257]),
+			Error::<Test>::MetadataTooLong
+		);
+	});
+}
+
+#[test]
+fn on_finalize_folds_block_submissions_into_total() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let submit = |i: u32| {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).as_bytes().to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		};
+
+		(0..3).for_each(submit);
+		assert_eq!(SubmissionsThisBlock::<Test>::get(), 3);
+		assert_eq!(TotalSubmissions::<Test>::get(), 0);
+		Shadow::on_finalize(1);
+
+		System::set_block_number(2);
+		Shadow::on_initialize(2);
+		assert_eq!(SubmissionsThisBlock::<Test>::get(), 0);
+		assert_eq!(TotalSubmissions::<Test>::get(), 3);
+
+		(3..5).for_each(submit);
+		Shadow::on_finalize(2);
+		assert_eq!(SubmissionsThisBlock::<Test>::get(), 0);
+		assert_eq!(TotalSubmissions::<Test>::get(), 5);
+	});
+}
+
+#[test]
+fn tags_can_be_added_and_removed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest123".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+		let tags = |who| {
+			Shadow::items_of(&who)[0].tags.iter().map(|tag| tag.to_vec()).collect::<Vec<_>>()
+		};
+
+		assert_ok!(Shadow::add_tag(RuntimeOrigin::signed(1), item_id, b"work".to_vec()));
+		System::assert_last_event(
+			Event::ItemTagged { who: 1, item_id, tag: b"work".to_vec() }.into(),
+		);
+		assert_ok!(Shadow::add_tag(RuntimeOrigin::signed(1), item_id, b"rust".to_vec()));
+		// Re-adding an existing tag does not use up a slot
+		assert_ok!(Shadow::add_tag(RuntimeOrigin::signed(1), item_id, b"rust".to_vec()));
+		assert_eq!(tags(1), vec![b"work".to_vec(), b"rust".to_vec()]);
+
+		// `MaxTagsPerItem` is 2 in the mock
+		assert_noop!(
+			Shadow::add_tag(RuntimeOrigin::signed(1), item_id, b"extra".to_vec()),
+			Error::<Test>::TooManyTags
+		);
+		assert_noop!(
+			Shadow::add_tag(RuntimeOrigin::signed(1), item_id, vec![b't'; 17]),
+			Error::<Test>::TagTooLong
+		);
+
+		assert_ok!(Shadow::remove_tag(RuntimeOrigin::signed(1), item_id, b"work".to_vec()));
+		System::assert_last_event(
+			Event::ItemUntagged { who: 1, item_id, tag: b"work".to_vec() }.into(),
+		);
+		assert_eq!(tags(1), vec![b"rust".to_vec()]);
+		assert_ok!(Shadow::add_tag(RuntimeOrigin::signed(1), item_id, b"extra".to_vec()));
+		assert_eq!(tags(1), vec![b"rust".to_vec(), b"extra".to_vec()]);
+	});
+}
+
+#[test]
+fn tagging_unknown_items_fails() {
+	new_test_ext().execute_with(|| {
+		let item_id = <Test as frame_system::Config>::Hash::repeat_byte(7);
+		assert_noop!(
+			Shadow::add_tag(RuntimeOrigin::signed(1), item_id, b"work".to_vec()),
+			Error::<Test>::ItemNotFound
+		);
+		assert_noop!(
+			Shadow::remove_tag(RuntimeOrigin::signed(1), item_id, b"work".to_vec()),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
+
+#[test]
+fn tag_index_stays_consistent_with_item_tags() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(3, "QmTag")
+		));
+		let ids: Vec<_> = Shadow::items_of(&1).iter().map(|item| item.id).collect();
+		for id in &ids {
+			assert_ok!(Shadow::add_tag(RuntimeOrigin::signed(1), *id, b"work".to_vec()));
+		}
+		assert_ok!(Shadow::add_tag(RuntimeOrigin::signed(1), ids[0], b"rust".to_vec()));
+		assert_eq!(Shadow::items_with_tag(&1, b"work".to_vec()), ids);
+		assert_eq!(Shadow::items_with_tag(&1, b"rust".to_vec()), vec![ids[0]]);
+		// The index is per account
+		assert!(Shadow::items_with_tag(&2, b"work".to_vec()).is_empty());
+
+		// `MaxItemsPerTag` is 3 in the mock
+		let metadata = ItemMetadata::<Test> {
+			title: Default::default(),
+			tags: vec![b"work".to_vec().try_into().unwrap()].try_into().unwrap(),
+			created_at: 0,
+		};
+		assert_noop!(
+			Shadow::submit_structured_item(
+				RuntimeOrigin::signed(1),
+				b"QmStructured".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				metadata
+			),
+			Error::<Test>::TooManyItemsWithTag
+		);
+
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), ids[0]));
+		assert_eq!(Shadow::items_with_tag(&1, b"work".to_vec()), ids[1..].to_vec());
+		assert!(Shadow::items_with_tag(&1, b"rust".to_vec()).is_empty());
+		let rust = BoundedTag::<Test>::truncate_from(b"rust".to_vec());
+		assert!(!TagIndex::<Test>::contains_key(1, rust));
+
+		assert_ok!(Shadow::remove_tag(RuntimeOrigin::signed(1), ids[1], b"work".to_vec()));
+		assert_eq!(Shadow::items_with_tag(&1, b"work".to_vec()), vec![ids[2]]);
+
+		// Structured submissions index their tags too
+		let metadata = ItemMetadata::<Test> {
+			title: Default::default(),
+			tags: vec![b"work".to_vec().try_into().unwrap()].try_into().unwrap(),
+			created_at: 0,
+		};
+		assert_ok!(Shadow::submit_structured_item(
+			RuntimeOrigin::signed(1),
+			b"QmStructured".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			metadata
+		));
+		let structured_id = Shadow::items_of(&1)[2].id;
+		assert_eq!(Shadow::items_with_tag(&1, b"work".to_vec()), vec![ids[2], structured_id]);
+
+		assert_ok!(Shadow::delete_all_shadow_items(RuntimeOrigin::signed(1)));
+		assert!(Shadow::items_with_tag(&1, b"work".to_vec()).is_empty());
+		assert_eq!(TagIndex::<Test>::iter_prefix(1).count(), 0);
+	});
+}
+
+fn batch_of(count: u32, prefix: &str) -> Vec<BatchItem> {
+	(0..count)
+		.map(|i| {
+			(
+				format!("{}{}", prefix, i).into_bytes(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+			)
+		})
+		.collect()
+}
+
+#[test]
+fn batch_submit_stores_every_item() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Shadow::submit_shadow_items_batch(RuntimeOrigin::signed(1), batch_of(2, "QmBatch")),
+			Error::<Test>::NoConsent
+		);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(MaxBatchSize::get(), "QmBatch")
+		));
+
+		let items = Shadow::items_of(&1);
+		assert_eq!(items.len() as u32, MaxBatchSize::get());
+		assert_eq!(items[0].cid.to_vec(), b"QmBatch0".to_vec());
+		let ids: BTreeSet<_> = items.iter().map(|item| item.id).collect();
+		assert_eq!(ids.len(), items.len());
+		System::assert_last_event(
+			Event::ShadowItemStored { who: 1, item_id: items[4].id, cid: b"QmBatch4".to_vec() }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn batch_submit_is_bounded_and_atomic() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_noop!(
+			Shadow::submit_shadow_items_batch(
+				RuntimeOrigin::signed(1),
+				batch_of(MaxBatchSize::get() + 1, "QmBatch")
+			),
+			Error::<Test>::BatchTooLarge
+		);
+
+		for i in 0..MaxItemsPerAccount::get() - 2 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).into_bytes(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+
+		// Three items do not fit in the two remaining slots, so none is stored
+		assert_noop!(
+			Shadow::submit_shadow_items_batch(RuntimeOrigin::signed(1), batch_of(3, "QmBatch")),
+			Error::<Test>::TooManyItems
+		);
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(2, "QmBatch")
+		));
+		assert_eq!(Shadow::items_of(&1).len() as u32, MaxItemsPerAccount::get());
+	});
+}
+
+#[test]
+fn account_stats_track_submissions_and_deletions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for i in 0..3 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).into_bytes(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		let item_id = Shadow::items_of(&1)[0].id;
+
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), item_id));
+		// Deleting an unknown item does not count
+		assert_noop!(
+			Shadow::delete_shadow_item(RuntimeOrigin::signed(1), item_id),
+			Error::<Test>::ItemNotFound
+		);
+
+		assert_eq!(
+			Shadow::account_stats(&1),
+			Stats { lifetime_submitted: 3, lifetime_deleted: 1, current: 2 }
+		);
+
+		assert_ok!(Shadow::delete_all_shadow_items(RuntimeOrigin::signed(1)));
+		assert_eq!(
+			Shadow::account_stats(&1),
+			Stats { lifetime_submitted: 3, lifetime_deleted: 3, current: 0 }
+		);
+	});
+}
+
+#[test]
+fn migrate_v9_to_v10_seeds_account_stats() {
+	use crate::migrations::v10::MigrateV9ToV10;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for i in 0..2 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				format!("QmTest{}", i).into_bytes(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		AccountStats::<Test>::remove(1);
+		StorageVersion::new(9).put::<Shadow>();
+
+		MigrateV9ToV10::<Test>::on_runtime_upgrade();
+
+		assert_eq!(
+			Shadow::account_stats(&1),
+			Stats { lifetime_submitted: 2, lifetime_deleted: 0, current: 2 }
+		);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(10));
+	});
+}
+
+#[test]
+fn reused_cid_gets_distinct_item_ids() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		for _ in 0..2 {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+
+		let items = Shadow::items_of(&1);
+		assert_eq!(ItemCounter::<Test>::get(1), 2);
+		assert_ne!(items[0].id, items[1].id);
+		let kept = items[1].id;
+
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), items[0].id));
+		let items = Shadow::items_of(&1);
+		assert_eq!(items.len(), 1);
+		assert_eq!(items[0].id, kept);
+	});
+}
+
+#[test]
+fn consent_receipt_identifies_the_grant() {
+	use sp_runtime::traits::Hash;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(100)));
+
+		let record = ConsentRecords::<Test>::get(1, 0).unwrap();
+		let expected = <Test as frame_system::Config>::Hashing::hash_of(&(
+			1u64,
+			b"test_consent".to_vec(),
+			1u64,
+			Some(101u64),
+		));
+		assert_eq!(record.receipt, expected);
+		assert_eq!(Shadow::consent_receipt(&1, b"test_consent", 1, Some(101)), expected);
+		System::assert_last_event(
+			Event::ConsentGranted {
+				who: 1,
+				consent_id: 0,
+				message_hash: b"test_consent".to_vec(),
+				granted_at: 1,
+				expires_at: Some(101),
+				receipt: expected,
+			}
+			.into(),
+		);
+
+		// The same grant by another account yields a different receipt
+		assert_ok!(grant_consent_as(2, b"test_consent".to_vec(), Some(100)));
+		assert_ne!(ConsentRecords::<Test>::get(2, 0).unwrap().receipt, expected);
+	});
+}
+
+#[test]
+fn migrate_v10_to_v11_adds_consent_receipts() {
+	use crate::migrations::{
+		v11::{self, MigrateV10ToV11},
+		v9::{self, ConsentRecordV9},
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(10).put::<Shadow>();
+		v9::ConsentRecords::<Test>::insert(
+			1,
+			ConsentRecordV9::<Test> {
+				granted_at: 3,
+				expires_at: None,
+				message_hash: b"test_consent".to_vec().try_into().unwrap(),
+				scope: Default::default(),
+				needs_resign: false,
+				signature_scheme: None,
+				cosigned: true,
+			},
+		);
+
+		MigrateV10ToV11::<Test>::on_runtime_upgrade();
+
+		let record = v11::ConsentRecords::<Test>::get(1).unwrap();
+		assert!(record.cosigned);
+		assert_eq!(record.receipt, Shadow::consent_receipt(&1, b"test_consent", 3, None));
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(11));
+	});
+}
+
+#[test]
+fn expired_consents_are_pruned_across_blocks() {
+	new_test_ext().execute_with(|| {
+		// Distinct messages, as each message indexes at most two accounts
+		for who in 1..=3u8 {
+			assert_ok!(grant_consent_as(who.into(), vec![b'c', who], Some(5)));
+		}
+		assert_ok!(grant_consent_as(4, b"test_consent".to_vec(), None));
+
+		// Nothing has expired yet
+		System::set_block_number(6);
+		Shadow::on_initialize(6);
+		Shadow::on_initialize(6);
+		assert_eq!(ConsentRecords::<Test>::iter_keys().count(), 4);
+
+		// Each block examines at most two records, so a full pass takes two blocks
+		for block in 7..=9 {
+			System::set_block_number(block);
+			Shadow::on_initialize(block);
+		}
+
+		assert!((1..=3).all(|who| ConsentRecords::<Test>::get(who, 0).is_none()));
+		assert!(ConsentRecords::<Test>::get(4, 0).is_some());
+		assert!(Shadow::accounts_with_message(vec![b'c', 1]).is_empty());
+		assert_eq!(Shadow::accounts_with_message(b"test_consent".to_vec()), vec![4]);
+		System::assert_has_event(Event::ConsentExpiredPruned { who: 1, consent_id: 0 }.into());
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			),
+			Error::<Test>::NoConsent
+		);
+	});
+}
+
+#[test]
+fn items_are_removed_once_their_expiry_block_passes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let submit = |cid: &[u8], expires_at| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				expires_at,
+			)
+		};
+		assert_ok!(submit(b"QmExpiring", Some(5)));
+		assert_ok!(submit(b"QmPinned", Some(5)));
+		assert_ok!(submit(b"QmPermanent", None));
+		let ids: Vec<_> = Shadow::items_of(&1).iter().map(|item| item.id).collect();
+		assert_eq!(Shadow::items_of(&1)[0].expires_at, Some(5));
+		assert_ok!(Shadow::pin_item(RuntimeOrigin::signed(1), ids[1]));
+
+		// The expiry block itself is still covered
+		System::set_block_number(5);
+		Shadow::on_initialize(5);
+		assert_eq!(Shadow::items_of(&1).len(), 3);
+
+		System::set_block_number(6);
+		Shadow::on_initialize(6);
+		let remaining: Vec<_> = Shadow::items_of(&1).iter().map(|item| item.id).collect();
+		assert_eq!(remaining, ids[1..].to_vec());
+		System::assert_has_event(Event::ShadowItemExpired { who: 1, item_id: ids[0] }.into());
+		assert_eq!(TotalItems::<Test>::get(), 2);
+		assert_eq!(Shadow::account_stats(&1).current, 2);
+
+		// An expiry must lie in the future
+		assert_noop!(submit(b"QmLate", Some(6)), Error::<Test>::InvalidItemExpiry);
+	});
+}
+
+#[test]
+fn concurrent_consents_are_managed_independently() {
+	new_test_ext().execute_with(|| {
+		let submit = |cid: &[u8]| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			)
+		};
+
+		assert_ok!(grant_consent_as(1, b"github_consent".to_vec(), None));
+		assert_ok!(grant_consent_as(1, b"twitter_consent".to_vec(), Some(10)));
+		System::assert_last_event(
+			Event::ConsentGranted {
+				who: 1,
+				consent_id: 1,
+				message_hash: b"twitter_consent".to_vec(),
+				granted_at: 1,
+				expires_at: Some(11),
+				receipt: ConsentRecords::<Test>::get(1, 1).unwrap().receipt,
+			}
+			.into(),
+		);
+		assert_eq!(
+			Shadow::list_consents(&1)
+				.into_iter()
+				.map(|(id, record)| (id, record.message_hash.to_vec()))
+				.collect::<Vec<_>>(),
+			vec![(0, b"github_consent".to_vec()), (1, b"twitter_consent".to_vec())]
+		);
+
+		// Amending one consent leaves the other untouched
+		assert_ok!(Shadow::amend_consent_scope(
+			RuntimeOrigin::signed(1),
+			1,
+			vec![],
+			vec![Source::Twitter, Source::GitLab, Source::Reddit, Source::StackOverflow]
+		));
+		assert!(ConsentRecords::<Test>::get(1, 0).unwrap().scope.is_empty());
+		assert_eq!(
+			ConsentRecords::<Test>::get(1, 1).unwrap().scope.into_iter().collect::<Vec<_>>(),
+			vec![Source::GitHub]
+		);
+
+		// Any remaining valid consent authorizes a submission
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 0));
+		assert_ok!(submit(b"QmTest1"));
+		assert_noop!(
+			Shadow::revoke_consent(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NoConsent
+		);
+
+		System::set_block_number(2);
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 1));
+		assert_noop!(submit(b"QmTest2"), Error::<Test>::ConsentRevoked);
+
+		// Ids are never reused
+		assert_ok!(grant_consent_as(1, b"github_consent".to_vec(), None));
+		assert!(ConsentRecords::<Test>::contains_key(1, 2));
+	});
+}
+
+#[test]
+fn consents_per_account_are_bounded() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..MaxConsentsPerAccount::get() {
+			assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		}
+		assert_noop!(
+			grant_consent_as(1, b"test_consent".to_vec(), None),
+			Error::<Test>::TooManyConsents
+		);
+
+		// Revoking one frees a slot
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 1));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_eq!(
+			Shadow::list_consents(&1).into_iter().map(|(id, _)| id).collect::<Vec<_>>(),
+			vec![0, 2, 3]
+		);
+	});
+}
+
+#[test]
+fn migrate_v11_to_v12_keys_consents_by_id() {
+	use crate::migrations::{
+		v11::{self, ConsentRecordV11},
+		v12::{self, MigrateV11ToV12},
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(11).put::<Shadow>();
+		for who in [1, 2] {
+			v11::ConsentRecords::<Test>::insert(
+				who,
+				ConsentRecordV11::<Test> {
+					granted_at: 3,
+					expires_at: Some(20),
+					message_hash: b"test_consent".to_vec().try_into().unwrap(),
+					scope: Default::default(),
+					needs_resign: false,
+					signature_scheme: None,
+					cosigned: false,
+					receipt: Shadow::consent_receipt(&who, b"test_consent", 3, Some(20)),
+				},
+			);
+		}
+
+		MigrateV11ToV12::<Test>::on_runtime_upgrade();
+
+		for who in [1, 2] {
+			let record = v12::ConsentRecords::<Test>::get(who, 0).unwrap();
+			assert_eq!(record.expires_at, Some(20));
+			assert_eq!(record.receipt, Shadow::consent_receipt(&who, b"test_consent", 3, Some(20)));
+			assert_eq!(NextConsentId::<Test>::get(who), 1);
+		}
+		// The pallet's own storage reads the migrated records back under the same keys
+		assert_eq!(ConsentRecords::<Test>::iter_keys().count(), 2);
+		assert!(ConsentRecords::<Test>::contains_key(1, 0));
+		assert!(ConsentRecords::<Test>::contains_key(2, 0));
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(12));
+
+		// New grants continue after the migrated record
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert!(ConsentRecords::<Test>::contains_key(1, 1));
+	});
+}
+
+#[test]
+fn opted_out_accounts_accept_no_submissions_until_opting_in() {
+	new_test_ext().execute_with(|| {
+		let submit = |cid: &[u8]| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			)
+		};
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(grant_consent_as(1, b"other_consent".to_vec(), None));
+		assert_ok!(Shadow::authorize_agent(RuntimeOrigin::signed(1), 20));
+		assert_ok!(submit(b"QmTest1"));
+
+		assert_ok!(Shadow::opt_out(RuntimeOrigin::signed(1)));
+		System::assert_has_event(
+			Event::ConsentRevoked { who: 1, consent_id: 1, reason: RevocationReason::OptedOut }
+				.into(),
+		);
+		System::assert_last_event(Event::OptedOut { who: 1 }.into());
+		assert!(Shadow::list_consents(&1).is_empty());
+		assert!(Shadow::accounts_with_message(b"test_consent".to_vec()).is_empty());
+		// Items are kept unless the runtime clears them on opt-out
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+
+		// Nothing can be submitted, even with a fresh consent or by an agent
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_noop!(submit(b"QmTest2"), Error::<Test>::AccountOptedOut);
+		assert_noop!(
+			Shadow::submit_shadow_item_for(
+				RuntimeOrigin::signed(20),
+				1,
+				b"QmTest2".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec()
+			),
+			Error::<Test>::AccountOptedOut
+		);
+		assert_noop!(
+			Shadow::submit_shadow_items_batch(RuntimeOrigin::signed(1), batch_of(2, "QmBatch")),
+			Error::<Test>::AccountOptedOut
+		);
+
+		assert_ok!(Shadow::opt_in(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::OptedIn { who: 1 }.into());
+		assert_ok!(submit(b"QmTest2"));
+		assert_eq!(Shadow::items_of(&1).len(), 2);
+	});
+}
+
+#[test]
+fn opt_out_clears_items_when_configured() {
+	new_test_ext().execute_with(|| {
+		ClearItemsOnOptOut::set(true);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(3, "QmBatch")
+		));
+
+		assert_ok!(Shadow::opt_out(RuntimeOrigin::signed(1)));
+		System::assert_has_event(Event::AllShadowItemsDeleted { who: 1, count: 3 }.into());
+		assert!(Shadow::items_of(&1).is_empty());
+		assert_eq!(Shadow::account_stats(&1).current, 0);
+
+		ClearItemsOnOptOut::set(false);
+	});
+}
+
+#[test]
+fn scoped_consent_only_covers_its_sources() {
+	new_test_ext().execute_with(|| {
+		let submit = |cid: &[u8], source: Source| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				source,
+				b"metadata".to_vec(),
+				None
+			)
+		};
+
+		assert_ok!(grant_scoped_consent_as(1, b"github_only".to_vec(), vec![Source::GitHub]));
+		assert_eq!(
+			ConsentRecords::<Test>::get(1, 0).unwrap().scope.into_iter().collect::<Vec<_>>(),
+			vec![Source::GitHub]
+		);
+
+		assert_ok!(submit(b"QmGitHub", Source::GitHub));
+		assert_noop!(submit(b"QmTwitter", Source::Twitter), Error::<Test>::SourceNotConsented);
+		assert_noop!(
+			Shadow::submit_shadow_items_batch(
+				RuntimeOrigin::signed(1),
+				vec![
+					(b"QmBatch0".to_vec(), b"key".to_vec(), Source::GitHub, vec![]),
+					(b"QmBatch1".to_vec(), b"key".to_vec(), Source::Twitter, vec![]),
+				]
+			),
+			Error::<Test>::SourceNotConsented
+		);
+
+		// The auto-grant flow does not widen a scoped consent
+		assert_noop!(
+			Shadow::submit_and_grant(
+				RuntimeOrigin::signed(1),
+				b"test_consent".to_vec(),
+				None,
+				consent_signature(1, b"test_consent", None),
+				b"QmTwitter".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::Twitter,
+				b"metadata".to_vec()
+			),
+			Error::<Test>::SourceNotConsented
+		);
+
+		// An unscoped consent covers every source
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(submit(b"QmTwitter", Source::Twitter));
+
+		// A scope cannot list more than `MaxScopeSources` sources
+		assert_noop!(
+			grant_scoped_consent_as(
+				1,
+				b"test_consent".to_vec(),
+				vec![
+					Source::GitHub,
+					Source::Twitter,
+					Source::GitLab,
+					Source::Reddit,
+					Source::StackOverflow
+				]
+			),
+			Error::<Test>::ScopeTooLarge
+		);
+	});
+}
+
+#[test]
+fn items_storage_key_matches_shadow_items_key() {
+	use polkadot_sdk::sp_io::hashing::{blake2_128, twox_128};
+
+	new_test_ext().execute_with(|| {
+		let key = Shadow::items_storage_key(&1);
+		assert_eq!(key, ShadowItems::<Test>::hashed_key_for(1));
+
+		// Pallet prefix, storage prefix, then the Blake2_128Concat-hashed account
+		let account = 1u64.encode();
+		let mut expected = twox_128(b"Shadow").to_vec();
+		expected.extend(twox_128(b"ShadowItems"));
+		expected.extend(blake2_128(&account));
+		expected.extend(account);
+		assert_eq!(key, expected);
+
+		// The key reads back the account's stored items
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest123".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		assert_eq!(
+			frame_support::storage::unhashed::get_raw(&key),
+			Some(ShadowItems::<Test>::get(1).encode())
+		);
+	});
+}
+
+#[test]
+fn item_deposits_are_reserved_and_released() {
+	new_test_ext().execute_with(|| {
+		DepositPerItem::set(10);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 100));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest1".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(3, "QmBatch")
+		));
+		assert_eq!(Balances::reserved_balance(1), 40);
+		let items = Shadow::items_of(&1);
+		assert!(items.iter().all(|item| item.deposit == 10));
+
+		// Each removal returns exactly the item's deposit
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), items[0].id));
+		assert_eq!(Balances::reserved_balance(1), 30);
+		assert_ok!(Shadow::force_delete_item(RuntimeOrigin::root(), 1, items[1].id));
+		assert_eq!(Balances::reserved_balance(1), 20);
+
+		// A later change to the deposit does not affect items already stored
+		DepositPerItem::set(5);
+		assert_ok!(Shadow::delete_all_shadow_items(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+
+		DepositPerItem::set(0);
+	});
+}
+
+#[test]
+fn submission_fails_without_deposit_balance() {
+	new_test_ext().execute_with(|| {
+		DepositPerItem::set(10);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 25));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+
+		assert_noop!(
+			Shadow::submit_shadow_items_batch(RuntimeOrigin::signed(1), batch_of(3, "QmBatch")),
+			Error::<Test>::InsufficientDepositBalance
+		);
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(2, "QmBatch")
+		));
+		assert_noop!(
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				b"QmTest".to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			),
+			Error::<Test>::InsufficientDepositBalance
+		);
+		assert_eq!(Balances::reserved_balance(1), 20);
+
+		DepositPerItem::set(0);
+	});
+}
+
+#[test]
+fn on_consent_revoked_fires_for_revocation_and_expiry() {
+	new_test_ext().execute_with(|| {
+		RevokedConsentCount::set(0);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(grant_consent_as(2, b"test_consent".to_vec(), Some(5)));
+
+		assert_ok!(Shadow::revoke_consent(RuntimeOrigin::signed(1), 0));
+		assert_eq!(RevokedConsentCount::get(), 1);
+
+		// Not yet expired, so pruning leaves it alone
+		System::set_block_number(6);
+		Shadow::on_initialize(6);
+		assert_eq!(RevokedConsentCount::get(), 1);
+
+		System::set_block_number(7);
+		Shadow::on_initialize(7);
+		assert!(ConsentRecords::<Test>::get(2, 0).is_none());
+		assert_eq!(RevokedConsentCount::get(), 2);
+	});
+}
+
+#[test]
+fn renew_consent_extends_expiry_and_keeps_grant() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), Some(10)));
+		let granted = ConsentRecords::<Test>::get(1, 0).unwrap();
+		assert_eq!(granted.expires_at, Some(11));
+
+		System::set_block_number(5);
+		assert_ok!(Shadow::renew_consent(RuntimeOrigin::signed(1), 0, 20));
+
+		// Extends from the old expiry, not from the current block
+		let renewed = ConsentRecords::<Test>::get(1, 0).unwrap();
+		assert_eq!(renewed.expires_at, Some(31));
+		assert_eq!(renewed.granted_at, granted.granted_at);
+		assert_eq!(renewed.receipt, granted.receipt);
+		System::assert_last_event(
+			Event::ConsentRenewed { who: 1, consent_id: 0, new_expires_at: 31 }.into(),
+		);
+		assert_eq!(
+			ConsentHistory::<Test>::get(1).last().map(|entry| entry.action),
+			Some(ConsentAction::Renewed)
+		);
+
+		// Still valid past the original expiry
+		System::set_block_number(20);
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+	});
+}
+
+#[test]
+fn renew_consent_rejects_missing_permanent_and_expired_consents() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Shadow::renew_consent(RuntimeOrigin::signed(1), 0, 5),
+			Error::<Test>::NoConsent
+		);
+
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_noop!(
+			Shadow::renew_consent(RuntimeOrigin::signed(1), 0, 5),
+			Error::<Test>::ConsentAlreadyPermanent
+		);
+
+		assert_ok!(grant_consent_as(2, b"test_consent".to_vec(), Some(5)));
+		System::set_block_number(7);
+		assert_noop!(
+			Shadow::renew_consent(RuntimeOrigin::signed(2), 0, 5),
+			Error::<Test>::ConsentExpired
+		);
+	});
+}
+
+#[test]
+fn replace_item_works_at_the_item_cap() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let submit = |cid: Vec<u8>| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid,
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			)
+		};
+		for i in 0..MaxItemsPerAccount::get() {
+			assert_ok!(submit(format!("QmTest{}", i).into_bytes()));
+		}
+		assert_noop!(submit(b"QmNew".to_vec()), Error::<Test>::TooManyItems);
+
+		let old_item_id = Shadow::items_of(&1)[3].id;
+		assert_ok!(Shadow::replace_item(
+			RuntimeOrigin::signed(1),
+			old_item_id,
+			b"QmNew".to_vec(),
+			b"new_key".to_vec(),
+			Source::Twitter,
+			b"new_metadata".to_vec(),
+		));
+
+		let items = Shadow::items_of(&1);
+		assert_eq!(items.len() as u32, MaxItemsPerAccount::get());
+		assert!(items.iter().all(|item| item.id != old_item_id));
+		let new_item = &items[3];
+		assert_eq!(new_item.cid.to_vec(), b"QmNew".to_vec());
+		assert_eq!(new_item.source, Source::Twitter);
+		System::assert_last_event(
+			Event::ShadowItemReplaced { who: 1, old_item_id, new_item_id: new_item.id }.into(),
+		);
+
+		let stats = Shadow::account_stats(&1);
+		assert_eq!(stats.current, MaxItemsPerAccount::get());
+		assert_eq!(stats.lifetime_deleted, 1);
+
+		assert_noop!(
+			Shadow::replace_item(
+				RuntimeOrigin::signed(1),
+				old_item_id,
+				b"QmOther".to_vec(),
+				b"new_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+			),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
+
+#[test]
+fn strict_cid_validation_requires_well_formed_cids() {
+	new_test_ext().execute_with(|| {
+		StrictCidValidation::set(true);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let submit = |cid: &[u8]| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			)
+		};
+
+		assert_ok!(submit(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"));
+		assert_ok!(submit(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"));
+
+		// '0' is outside the base58 alphabet
+		assert_noop!(
+			submit(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0"),
+			Error::<Test>::InvalidCid
+		);
+		// Digest cut short
+		assert_noop!(
+			submit(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55f"),
+			Error::<Test>::InvalidCid
+		);
+		assert_noop!(submit(b"not-a-cid"), Error::<Test>::InvalidCid);
+
+		StrictCidValidation::set(false);
+		assert_ok!(submit(b"not-a-cid"));
+	});
+}
+
+#[test]
+fn total_items_tracks_submits_and_deletes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(grant_consent_as(2, b"test_consent".to_vec(), None));
+
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		assert_ok!(Shadow::submit_shadow_items_batch(RuntimeOrigin::signed(1), batch_of(3, "QmA")));
+		assert_ok!(Shadow::submit_shadow_items_batch(RuntimeOrigin::signed(2), batch_of(2, "QmB")));
+		assert_eq!(TotalItems::<Test>::get(), 6);
+
+		let item_id = Shadow::items_of(&1)[0].id;
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), item_id));
+		assert_eq!(TotalItems::<Test>::get(), 5);
+
+		assert_ok!(Shadow::delete_all_shadow_items(RuntimeOrigin::signed(1)));
+		assert_eq!(Shadow::total_items(), 2);
+	});
+}
+
+#[test]
+fn migrate_v12_to_v13_seeds_total_items() {
+	use crate::migrations::v13::MigrateV12ToV13;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		for who in [1, 2] {
+			assert_ok!(grant_consent_as(who, b"test_consent".to_vec(), None));
+			assert_ok!(Shadow::submit_shadow_items_batch(
+				RuntimeOrigin::signed(who),
+				batch_of(who as u32 + 1, "QmTest")
+			));
+		}
+		TotalItems::<Test>::kill();
+		StorageVersion::new(12).put::<Shadow>();
+
+		MigrateV12ToV13::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Shadow::total_items(), 5);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(13));
+	});
+}
+
+#[test]
+fn migrate_v13_to_v14_seeds_item_index() {
+	use crate::migrations::v14::MigrateV13ToV14;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		for who in [1, 2] {
+			assert_ok!(grant_consent_as(who, b"test_consent".to_vec(), None));
+			assert_ok!(Shadow::submit_shadow_items_batch(
+				RuntimeOrigin::signed(who),
+				batch_of(who as u32 + 1, "QmTest")
+			));
+		}
+		let _ = ItemIndex::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(13).put::<Shadow>();
+
+		MigrateV13ToV14::<Test>::on_runtime_upgrade();
+
+		assert_item_index_consistent(1);
+		assert_item_index_consistent(2);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(14));
+
+		// Migrated positions are good enough to delete by
+		let first = Shadow::items_of(&2)[0].id;
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(2), first));
+		assert_item_index_consistent(2);
+	});
+}
+
+#[test]
+fn migrate_v14_to_v15_adds_no_consent_delegate() {
+	use crate::migrations::{
+		v11::ConsentRecordV11,
+		v12,
+		v15::{self, MigrateV14ToV15},
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(14).put::<Shadow>();
+		let receipt = Shadow::consent_receipt(&1, b"test_consent", 3, Some(20));
+		v12::ConsentRecords::<Test>::insert(
+			1,
+			2,
+			ConsentRecordV11::<Test> {
+				granted_at: 3,
+				expires_at: Some(20),
+				message_hash: b"test_consent".to_vec().try_into().unwrap(),
+				scope: Default::default(),
+				needs_resign: false,
+				signature_scheme: None,
+				cosigned: true,
+				receipt,
+			},
+		);
+
+		MigrateV14ToV15::<Test>::on_runtime_upgrade();
+
+		let record = v15::ConsentRecords::<Test>::get(1, 2).unwrap();
+		assert_eq!((record.expires_at, record.cosigned, record.receipt), (Some(20), true, receipt));
+		assert_eq!(record.delegate, None);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(15));
+	});
+}
+
+#[test]
+fn migrate_v15_to_v16_seeds_account_bytes() {
+	use crate::migrations::v16::MigrateV15ToV16;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		for who in [1, 2] {
+			assert_ok!(grant_consent_as(who, b"test_consent".to_vec(), None));
+			assert_ok!(Shadow::submit_shadow_items_batch(
+				RuntimeOrigin::signed(who),
+				batch_of(who as u32 + 1, "QmTest")
+			));
+		}
+		let expected: Vec<_> = [1, 2].map(AccountBytes::<Test>::get).to_vec();
+		let _ = AccountBytes::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(15).put::<Shadow>();
+
+		MigrateV15ToV16::<Test>::on_runtime_upgrade();
+
+		assert_eq!([1, 2].map(AccountBytes::<Test>::get).to_vec(), expected);
+		assert_eq!(Shadow::on_chain_storage_version(), StorageVersion::new(16));
+	});
+}
+
+#[test]
+fn access_item_logs_owner_and_shared_accesses() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].hash_id();
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item_id, 2, b"key".to_vec()));
+
+		// The owner reads its own item
+		assert_ok!(Shadow::access_item(RuntimeOrigin::signed(1), 1, item_id));
+		System::assert_last_event(Event::ItemAccessed { accessor: 1, owner: 1, item_id }.into());
+
+		// A recipient of a shared key reads it in a later block
+		System::set_block_number(2);
+		assert_ok!(Shadow::access_item(RuntimeOrigin::signed(2), 1, item_id));
+		System::assert_last_event(Event::ItemAccessed { accessor: 2, owner: 1, item_id }.into());
+		assert_eq!(AccessLog::<Test>::get(1, item_id).into_inner(), vec![(1, 1), (2, 2)]);
+
+		// The log is full, so the oldest access makes room for the next
+		System::set_block_number(3);
+		assert_ok!(Shadow::access_item(RuntimeOrigin::signed(1), 1, item_id));
+		assert_eq!(AccessLog::<Test>::get(1, item_id).into_inner(), vec![(2, 2), (1, 3)]);
+	});
+}
+
+#[test]
+fn access_item_rejects_unauthorized_accessors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].hash_id();
+
+		assert_noop!(
+			Shadow::access_item(RuntimeOrigin::signed(3), 1, item_id),
+			Error::<Test>::AccessDenied
+		);
+
+		// A revoked share no longer grants access
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item_id, 2, b"key".to_vec()));
+		assert_ok!(Shadow::revoke_share(RuntimeOrigin::signed(1), item_id, 2));
+		assert_noop!(
+			Shadow::access_item(RuntimeOrigin::signed(2), 1, item_id),
+			Error::<Test>::AccessDenied
+		);
+
+		// Items are looked up under the named owner
+		assert_noop!(
+			Shadow::access_item(RuntimeOrigin::signed(1), 2, item_id),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
+
+#[test]
+fn deleting_an_item_clears_its_shares_access_log_and_flags() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 20, 100));
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item_id, 2, b"key".to_vec()));
+		assert_ok!(Shadow::access_item(RuntimeOrigin::signed(2), 1, item_id));
+		assert_ok!(Shadow::flag_item(RuntimeOrigin::signed(20), 1, item_id, 3));
+		assert_eq!(Balances::reserved_balance(20), FlagDeposit::get());
+
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), item_id));
+
+		assert_eq!(Shadow::shared_key(&2, item_id), None);
+		assert!(ShareRecipients::<Test>::get(item_id).is_empty());
+		assert!(AccessLog::<Test>::get(1, item_id).is_empty());
+		assert!(Flags::<Test>::get(1, item_id).is_empty());
+		assert_eq!(Balances::reserved_balance(20), 0);
+	});
+}
+
+#[test]
+fn revoke_share_removes_recipient_access() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmTest".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item_id, 2, b"key_2".to_vec()));
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item_id, 3, b"key_3".to_vec()));
+		assert_eq!(Shadow::shared_key(&2, item_id), Some(b"key_2".to_vec()));
+
+		// Only the owner can revoke
+		assert_noop!(
+			Shadow::revoke_share(RuntimeOrigin::signed(2), item_id, 2),
+			Error::<Test>::ItemNotFound
+		);
+
+		assert_ok!(Shadow::revoke_share(RuntimeOrigin::signed(1), item_id, 2));
+		System::assert_last_event(Event::ItemShareRevoked { who: 1, item_id, recipient: 2 }.into());
+		assert_eq!(Shadow::shared_key(&2, item_id), None);
+		assert_eq!(Shadow::shared_key(&3, item_id), Some(b"key_3".to_vec()));
+		assert_eq!(ShareRecipients::<Test>::get(item_id).into_inner(), vec![3]);
+
+		assert_noop!(
+			Shadow::revoke_share(RuntimeOrigin::signed(1), item_id, 2),
+			Error::<Test>::ShareNotFound
+		);
+
+		// The last revocation clears the recipient list
+		assert_ok!(Shadow::revoke_share(RuntimeOrigin::signed(1), item_id, 3));
+		assert!(!ShareRecipients::<Test>::contains_key(item_id));
+	});
+}
+
+#[test]
+fn structured_metadata_round_trips() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let metadata = ItemMetadata::<Test> {
+			title: b"Fix off-by-one in pager".to_vec().try_into().unwrap(),
+			tags: vec![b"rust".to_vec().try_into().unwrap(), b"bugfix".to_vec().try_into().unwrap()]
+				.try_into()
+				.unwrap(),
+			created_at: 1_700_000_000_000,
+		};
+
+		assert_ok!(Shadow::submit_structured_item(
+			RuntimeOrigin::signed(1),
+			b"QmStructured".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			metadata.clone()
+		));
+		let item = Shadow::items_of(&1)[0].clone();
+		assert_eq!(item.metadata.to_vec(), metadata.encode());
+		assert_eq!(Shadow::decode_metadata(&item), Some(metadata));
+
+		// Opaque metadata is stored untouched and does not decode as structured
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmOpaque".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None
+		));
+		let item = Shadow::items_of(&1)[1].clone();
+		assert_eq!(item.metadata.to_vec(), b"metadata".to_vec());
+		assert_eq!(Shadow::decode_metadata(&item), None);
+	});
+}
+
+#[test]
+fn transfer_item_ownership_moves_item_deposit_and_tags() {
+	new_test_ext().execute_with(|| {
+		DepositPerItem::set(10);
+		for who in [1, 2] {
+			assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), who, 100));
+			assert_ok!(grant_consent_as(who, b"test_consent".to_vec(), None));
+		}
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(2, "QmMove")
+		));
+		let item = Shadow::items_of(&1)[0].clone();
+		assert_ok!(Shadow::add_tag(RuntimeOrigin::signed(1), item.id, b"work".to_vec()));
+		assert_ok!(Shadow::share_item(RuntimeOrigin::signed(1), item.id, 3, b"key".to_vec()));
+		assert_ok!(Shadow::access_item(RuntimeOrigin::signed(3), 1, item.id));
+
+		// Offering the item moves nothing until the recipient accepts
+		assert_ok!(Shadow::transfer_item_ownership(RuntimeOrigin::signed(1), item.id, 2));
+		System::assert_last_event(
+			Event::ItemTransferOffered { from: 1, to: 2, item_id: item.id }.into(),
+		);
+		assert_eq!(Shadow::items_of(&1).len(), 2);
+		assert_noop!(
+			Shadow::accept_item_transfer(RuntimeOrigin::signed(3), 1, item.id),
+			Error::<Test>::NoPendingTransfer
+		);
+
+		assert_ok!(Shadow::accept_item_transfer(RuntimeOrigin::signed(2), 1, item.id));
+		System::assert_last_event(
+			Event::ItemOwnershipTransferred { from: 1, to: 2, item_id: item.id }.into(),
+		);
+		assert!(!PendingTransfers::<Test>::contains_key(1, item.id));
+		assert_eq!(Shadow::items_of(&1).len(), 1);
+		let moved = Shadow::items_of(&2)[0].clone();
+		assert_eq!((moved.id, moved.cid), (item.id, item.cid));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert!(Shadow::items_with_tag(&1, b"work".to_vec()).is_empty());
+		assert_eq!(Shadow::items_with_tag(&2, b"work".to_vec()), vec![item.id]);
+		assert_eq!(Shadow::account_stats(&1).current, 1);
+		assert_eq!(Shadow::account_stats(&2).current, 1);
+		assert_eq!(TotalItems::<Test>::get(), 2);
+
+		// Its access log moves with it and its shared keys stay valid
+		assert!(AccessLog::<Test>::get(1, item.id).is_empty());
+		assert_eq!(AccessLog::<Test>::get(2, item.id).into_inner(), vec![(3, 1)]);
+		assert_eq!(Shadow::shared_key(&3, item.id), Some(b"key".to_vec()));
+		assert_ok!(Shadow::access_item(RuntimeOrigin::signed(3), 2, item.id));
+		assert_ok!(Shadow::revoke_share(RuntimeOrigin::signed(2), item.id, 3));
+
+		// The item is no longer the sender's to move, and the offer is used up
+		assert_noop!(
+			Shadow::transfer_item_ownership(RuntimeOrigin::signed(1), item.id, 2),
+			Error::<Test>::ItemNotFound
+		);
+		assert_noop!(
+			Shadow::accept_item_transfer(RuntimeOrigin::signed(2), 1, item.id),
+			Error::<Test>::NoPendingTransfer
+		);
+
+		DepositPerItem::set(0);
+	});
+}
+
+#[test]
+fn transfer_item_ownership_rejects_full_or_unconsented_recipients() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(1, "QmMove")
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+		let accept = |who| Shadow::accept_item_transfer(RuntimeOrigin::signed(who), 1, item_id);
+
+		assert_ok!(Shadow::transfer_item_ownership(RuntimeOrigin::signed(1), item_id, 2));
+		assert_noop!(accept(2), Error::<Test>::NoConsent);
+
+		assert_ok!(grant_consent_as(2, b"test_consent".to_vec(), None));
+		for i in 0..MaxItemsPerAccount::get() {
+			assert_ok!(Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(2),
+				format!("QmFull{}", i).into_bytes(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None
+			));
+		}
+		assert_noop!(accept(2), Error::<Test>::TooManyItems);
+
+		// The item must fit in the recipient's byte budget
+		RequireRecipientConsent::set(false);
+		assert_ok!(Shadow::transfer_item_ownership(RuntimeOrigin::signed(1), item_id, 3));
+		MaxBytesPerAccount::set(Shadow::stored_size(&Shadow::items_of(&1)[0]) - 1);
+		assert_noop!(accept(3), Error::<Test>::AccountStorageFull);
+		MaxBytesPerAccount::set(u32::MAX);
+
+		// Without the consent requirement any account with room can receive the item
+		assert_ok!(accept(3));
+		assert_eq!(Shadow::items_of(&3)[0].id, item_id);
+		RequireRecipientConsent::set(true);
+	});
+}
+
+#[test]
+fn item_transfer_offers_can_be_cancelled_or_replaced() {
+	new_test_ext().execute_with(|| {
+		RequireRecipientConsent::set(false);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(1, "QmMove")
+		));
+		let item_id = Shadow::items_of(&1)[0].id;
+
+		// Only the owner can offer an item
+		assert_noop!(
+			Shadow::transfer_item_ownership(RuntimeOrigin::signed(2), item_id, 3),
+			Error::<Test>::ItemNotFound
+		);
+
+		// A new offer replaces the previous one
+		assert_ok!(Shadow::transfer_item_ownership(RuntimeOrigin::signed(1), item_id, 2));
+		assert_ok!(Shadow::transfer_item_ownership(RuntimeOrigin::signed(1), item_id, 3));
+		assert_noop!(
+			Shadow::accept_item_transfer(RuntimeOrigin::signed(2), 1, item_id),
+			Error::<Test>::NoPendingTransfer
+		);
+
+		assert_ok!(Shadow::cancel_item_transfer(RuntimeOrigin::signed(1), item_id));
+		System::assert_last_event(Event::ItemTransferCancelled { from: 1, item_id }.into());
+		assert_noop!(
+			Shadow::accept_item_transfer(RuntimeOrigin::signed(3), 1, item_id),
+			Error::<Test>::NoPendingTransfer
+		);
+		assert_noop!(
+			Shadow::cancel_item_transfer(RuntimeOrigin::signed(1), item_id),
+			Error::<Test>::NoPendingTransfer
+		);
+
+		// Deleting the item withdraws its offer
+		assert_ok!(Shadow::transfer_item_ownership(RuntimeOrigin::signed(1), item_id, 3));
+		assert_ok!(Shadow::delete_shadow_item(RuntimeOrigin::signed(1), item_id));
+		assert!(!PendingTransfers::<Test>::contains_key(1, item_id));
+		RequireRecipientConsent::set(true);
+	});
+}
+
+#[test]
+fn submissions_are_rate_limited_per_window() {
+	new_test_ext().execute_with(|| {
+		MaxSubmissionsPerWindow::set(3);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		let submit = |cid: &[u8]| {
+			Shadow::submit_shadow_item(
+				RuntimeOrigin::signed(1),
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+				None,
+			)
+		};
+
+		// A batch counts each of its items
+		assert_ok!(submit(b"QmRate0"));
+		assert_noop!(
+			Shadow::submit_shadow_items_batch(RuntimeOrigin::signed(1), batch_of(3, "QmBatch")),
+			Error::<Test>::RateLimited
+		);
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(2, "QmBatch")
+		));
+		assert_noop!(submit(b"QmRate1"), Error::<Test>::RateLimited);
+
+		// Still the same window until `RateLimitWindow` blocks have passed
+		System::set_block_number(10);
+		assert_noop!(submit(b"QmRate1"), Error::<Test>::RateLimited);
+		System::set_block_number(11);
+		assert_ok!(submit(b"QmRate1"));
+		assert_eq!(SubmissionRate::<Test>::get(1), (11, 1));
+		assert_eq!(Shadow::items_of(&1).len(), 4);
+
+		MaxSubmissionsPerWindow::set(u32::MAX);
+	});
+}
+
+#[test]
+fn replace_item_counts_as_a_submission() {
+	new_test_ext().execute_with(|| {
+		MaxSubmissionsPerWindow::set(2);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_item(
+			RuntimeOrigin::signed(1),
+			b"QmRate0".to_vec(),
+			b"encrypted_key".to_vec(),
+			Source::GitHub,
+			b"metadata".to_vec(),
+			None,
+		));
+		let replace = |cid: &[u8]| {
+			let old_item_id = Shadow::items_of(&1)[0].id;
+			Shadow::replace_item(
+				RuntimeOrigin::signed(1),
+				old_item_id,
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+			)
+		};
+
+		assert_ok!(replace(b"QmRate1"));
+		assert_eq!(SubmissionRate::<Test>::get(1), (1, 2));
+		assert_eq!(SubmissionsThisBlock::<Test>::get(), 2);
+		assert_noop!(replace(b"QmRate2"), Error::<Test>::RateLimited);
+
+		MaxSubmissionsPerWindow::set(u32::MAX);
+	});
+}
+
+#[test]
+fn trusted_submitters_bypass_the_rate_limit() {
+	new_test_ext().execute_with(|| {
+		MaxSubmissionsPerWindow::set(1);
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::authorize_agent(RuntimeOrigin::signed(1), 2));
+		let submit_for = |cid: &[u8]| {
+			Shadow::submit_shadow_item_for(
+				RuntimeOrigin::signed(2),
+				1,
+				cid.to_vec(),
+				b"encrypted_key".to_vec(),
+				Source::GitHub,
+				b"metadata".to_vec(),
+			)
+		};
+
+		// An agent's submissions count against the owner's window
+		assert_ok!(submit_for(b"QmAgent0"));
+		assert_noop!(submit_for(b"QmAgent1"), Error::<Test>::RateLimited);
+
+		// Once the agent is trusted its submissions are neither limited nor counted
+		assert_ok!(Shadow::add_trusted(RuntimeOrigin::root(), 2));
+		assert_ok!(submit_for(b"QmAgent1"));
+		assert_ok!(submit_for(b"QmAgent2"));
+		assert_eq!(SubmissionRate::<Test>::get(1), (1, 1));
+		assert_eq!(Shadow::items_of(&1).len(), 3);
+
+		MaxSubmissionsPerWindow::set(u32::MAX);
+	});
+}
+
+#[test]
+fn try_state_catches_injected_over_counts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(grant_consent_as(1, b"test_consent".to_vec(), None));
+		assert_ok!(Shadow::submit_shadow_items_batch(
+			RuntimeOrigin::signed(1),
+			batch_of(3, "QmTest")
+		));
+		assert_ok!(Shadow::do_try_state());
+
+		// A counter out of step with the stored items
+		TotalItems::<Test>::put(4);
+		assert_eq!(
+			Shadow::do_try_state(),
+			Err("TotalItems does not match the number of items stored".into())
+		);
+		TotalItems::<Test>::put(3);
+		assert_ok!(Shadow::do_try_state());
+
+		// An account holding more items than it may
+		let item = ShadowItems::<Test>::get(1)[0].clone();
+		frame_support::storage::unhashed::put(
+			&ShadowItems::<Test>::hashed_key_for(2),
+			&vec![item; MaxItemsPerAccount::get() as usize + 1],
+		);
+		assert_eq!(
+			Shadow::do_try_state(),
+			Err("an account holds more than MaxItemsPerAccount items".into())
+		);
+	});
+}
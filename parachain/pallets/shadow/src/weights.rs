@@ -29,54 +29,808 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for pallet_shadow.
 pub trait WeightInfo {
-	fn submit_shadow_item() -> Weight;
-	fn delete_shadow_item() -> Weight;
+	fn submit_shadow_item(m: u32, ) -> Weight;
+	fn delete_shadow_item(n: u32, ) -> Weight;
 	fn grant_consent() -> Weight;
 	fn revoke_consent() -> Weight;
+	fn set_metadata_limit_for_account() -> Weight;
+	fn authorize_session() -> Weight;
+	fn submit_shadow_item_for(m: u32, ) -> Weight;
+	fn pin_item() -> Weight;
+	fn unpin_item() -> Weight;
+	fn delete_all_shadow_items(n: u32, ) -> Weight;
+	fn authorize_agent() -> Weight;
+	fn revoke_all_agents() -> Weight;
+	fn share_item() -> Weight;
+	fn amend_consent_scope() -> Weight;
+	fn register_consent_template() -> Weight;
+	fn update_cid() -> Weight;
+	fn transfer_consent() -> Weight;
+	fn set_source_meta_template() -> Weight;
+	fn report_dead_cid() -> Weight;
+	fn force_delete_item() -> Weight;
+	fn submit_shadow_item_with_encrypted_metadata(m: u32, e: u32, ) -> Weight;
+	fn add_trusted() -> Weight;
+	fn remove_trusted() -> Weight;
+	fn flag_item() -> Weight;
+	fn resolve_flags(n: u32, ) -> Weight;
+	fn reserve_slots() -> Weight;
+	fn release_slots() -> Weight;
+	fn prune_empty_accounts(n: u32, ) -> Weight;
+	fn verify_consent_signature() -> Weight;
+	fn update_shadow_item(m: u32, ) -> Weight;
+	fn on_finalize(n: u32, ) -> Weight;
+	fn add_tag() -> Weight;
+	fn remove_tag() -> Weight;
+	fn submit_shadow_items_batch(n: u32, ) -> Weight;
+	fn prune_expired_consents(n: u32, ) -> Weight;
+	fn opt_out(n: u32, ) -> Weight;
+	fn opt_in() -> Weight;
+	fn renew_consent() -> Weight;
+	fn replace_item(m: u32, ) -> Weight;
+	fn revoke_share() -> Weight;
+	fn expire_items(n: u32, ) -> Weight;
+	fn transfer_item_ownership() -> Weight;
+	fn delete_items_by_source(n: u32, ) -> Weight;
+	fn access_item() -> Weight;
+	fn register_source() -> Weight;
+	fn accept_item_transfer() -> Weight;
+	fn cancel_item_transfer() -> Weight;
+	fn resign_consent() -> Weight;
+	fn unregister_source() -> Weight;
+	fn clear_reaped_item() -> Weight;
 }
 
 /// Weights for pallet_shadow using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: polkadot_sdk::frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
-	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
 	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
-	fn submit_shadow_item() -> Weight {
+	/// Storage: `Shadow::AccountBytes` (r:1 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SourceMetaTemplate` (r:1 w:0)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	fn submit_shadow_item(m: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `6`
 		//  Estimated: `12306`
 		// Minimum execution time: 21_000_000 picoseconds.
 		Weight::from_parts(22_000_000, 12306)
+			// Standard Error: 41
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:33 w:33)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:8 w:8)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:2)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 100]`.
+	fn delete_shadow_item(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + n * (898 ±0)`
+		//  Estimated: `12306`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(14_788_000, 12306)
+			// Standard Error: 1_954
+			.saturating_add(Weight::from_parts(402_600, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(47_u64))
+			.saturating_add(T::DbWeight::get().writes(68_u64))
+			.saturating_add(Weight::from_parts(0, 898).saturating_mul(n.into()))
+	}
+	/// Storage: `System::Account` (r:1 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentTemplates` (r:1 w:0)
+	/// Proof: `Shadow::ConsentTemplates` (`max_values`: None, `max_size`: Some(81), added: 2556, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:2 w:2)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentHistory` (r:1 w:1)
+	/// Proof: `Shadow::ConsentHistory` (`max_values`: None, `max_size`: Some(2707), added: 5182, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::RevokedConsents` (r:0 w:1)
+	/// Proof: `Shadow::RevokedConsents` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentNonce` (r:1 w:1)
+	/// Proof: `Shadow::ConsentNonce` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn grant_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321`
+		//  Estimated: `22490`
+		// Minimum execution time: 78_000_000 picoseconds.
+		Weight::from_parts(80_000_000, 22490)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:1 w:1)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentHistory` (r:1 w:1)
+	/// Proof: `Shadow::ConsentHistory` (`max_values`: None, `max_size`: Some(2707), added: 5182, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::RevokedConsents` (r:0 w:1)
+	/// Proof: `Shadow::RevokedConsents` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn revoke_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321`
+		//  Estimated: `11740`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 11740)
 			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Shadow::AccountMetadataLimit` (r:0 w:1)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn set_metadata_limit_for_account() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::Sessions` (r:0 w:1)
+	/// Proof: `Shadow::Sessions` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	fn authorize_session() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Shadow::AuthorizedAgents` (r:1 w:0)
+	/// Proof: `Shadow::AuthorizedAgents` (`max_values`: None, `max_size`: Some(305), added: 2780, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Sessions` (r:1 w:0)
+	/// Proof: `Shadow::Sessions` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:1 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SourceMetaTemplate` (r:1 w:0)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:0)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	fn submit_shadow_item_for(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 12306)
+			// Standard Error: 41
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(15_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn pin_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
 	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
-	fn delete_shadow_item() -> Weight {
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn unpin_item() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `6`
 		//  Estimated: `12306`
 		// Minimum execution time: 15_000_000 picoseconds.
 		Weight::from_parts(16_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3201 w:3201)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:800 w:800)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:100)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:100 w:100)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:1600)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:100)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:100 w:100)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:100)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:100)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 100]`.
+	fn delete_all_shadow_items(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + n * (898 ±0)`
+		//  Estimated: `1493`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_512_000, 1493)
+			// Standard Error: 2_108
+			.saturating_add(Weight::from_parts(391_200, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().reads((42_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((62_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 898).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::AuthorizedAgents` (r:1 w:1)
+	/// Proof: `Shadow::AuthorizedAgents` (`max_values`: None, `max_size`: Some(305), added: 2780, mode: `MaxEncodedLen`)
+	fn authorize_agent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `3770`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 3770)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: `Shadow::ConsentRecords` (r:0 w:1)
-	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
-	fn grant_consent() -> Weight {
+	/// Storage: `Shadow::AuthorizedAgents` (r:1 w:1)
+	/// Proof: `Shadow::AuthorizedAgents` (`max_values`: None, `max_size`: Some(305), added: 2780, mode: `MaxEncodedLen`)
+	fn revoke_all_agents() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `3770`
+		// Minimum execution time: 10_000_000 picoseconds.
+		Weight::from_parts(11_000_000, 3770)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:0)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:1 w:1)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	fn share_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `11316`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 11316)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	fn amend_consent_scope() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `148`
+		//  Estimated: `3543`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 3543)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ConsentTemplates` (r:0 w:1)
+	/// Proof: `Shadow::ConsentTemplates` (`max_values`: None, `max_size`: Some(81), added: 2556, mode: `MaxEncodedLen`)
+	fn register_consent_template() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn update_cid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:2 w:2)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:1 w:1)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	fn transfer_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321`
+		//  Estimated: `11740`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 11740)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Shadow::SourceMetaTemplate` (r:0 w:1)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	fn set_source_meta_template() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:1 w:2)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:33 w:33)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:8 w:8)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:2)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn report_dead_cid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(48_u64))
+			.saturating_add(T::DbWeight::get().writes(69_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:33 w:33)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:8 w:8)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:2)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn force_delete_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(47_u64))
+			.saturating_add(T::DbWeight::get().writes(68_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:1 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SourceMetaTemplate` (r:1 w:0)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	/// The range of component `e` is `[0, 256]`.
+	fn submit_shadow_item_with_encrypted_metadata(m: u32, e: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 12306)
+			// Standard Error: 41
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			// Standard Error: 160
+			.saturating_add(Weight::from_parts(1_204, 0).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: `Shadow::TrustedSubmitters` (r:0 w:1)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn add_trusted() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::TrustedSubmitters` (r:0 w:1)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn remove_trusted() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:0)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn flag_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `300`
+		//  Estimated: `12306`
+		// Minimum execution time: 30_000_000 picoseconds.
+		Weight::from_parts(31_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:32 w:32)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 32]`.
+	fn resolve_flags(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `200`
+		//  Estimated: `4620`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 4620)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(9_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:0)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn reserve_slots() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
 		// Minimum execution time: 9_000_000 picoseconds.
-		Weight::from_parts(10_000_000, 0)
+		Weight::from_parts(10_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: `Shadow::ConsentRecords` (r:0 w:1)
-	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
-	fn revoke_consent() -> Weight {
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn release_slots() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `3517`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 3517)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::PruneCursor` (r:1 w:1)
+	/// Proof: `Shadow::PruneCursor` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1001 w:1000)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1000 w:1000)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1000 w:1000)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:0 w:1000)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1000)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:0 w:1000)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 1000]`.
+	fn prune_empty_accounts(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3533`
+		// Minimum execution time: 6_000_000 picoseconds.
+		Weight::from_parts(5_000_000, 3533)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(9_600_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 11316).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+	fn verify_consent_signature() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 47_000_000 picoseconds.
+		Weight::from_parts(48_000_000, 0)
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	fn update_shadow_item(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 20_000_000 picoseconds.
+		Weight::from_parts(21_000_000, 12306)
+			// Standard Error: 41
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ConsentCheckCache` (r:0 w:1)
+	/// Proof: `Shadow::ConsentCheckCache` (`max_values`: Some(1), `max_size`: Some(514), added: 1009, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingItemsStored` (r:1 w:1)
+	/// Proof: `Shadow::PendingItemsStored` (`max_values`: Some(1), `max_size`: Some(2305), added: 2800, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionsThisBlock` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionsThisBlock` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalSubmissions` (r:1 w:1)
+	/// Proof: `Shadow::TotalSubmissions` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 64]`.
+	fn on_finalize(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `40`
+		//  Estimated: `1489`
+		// Minimum execution time: 4_000_000 picoseconds.
+		Weight::from_parts(4_500_000, 1489)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(1_100_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:1 w:1)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	fn add_tag() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:1 w:1)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	fn remove_tag() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:1 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:20)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 20]`.
+	fn submit_shadow_items_batch(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 12306)
+			// Standard Error: 2_310
+			.saturating_add(Weight::from_parts(3_412_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `Shadow::ConsentPruneCursor` (r:1 w:1)
+	/// Proof: `Shadow::ConsentPruneCursor` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:51 w:50)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:50 w:50)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentCheckCache` (r:1 w:1)
+	/// Proof: `Shadow::ConsentCheckCache` (`max_values`: Some(1), `max_size`: Some(514), added: 1009, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 50]`.
+	fn prune_expired_consents(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `990`
+		// Minimum execution time: 3_000_000 picoseconds.
+		Weight::from_parts(3_412_000, 990)
+			// Standard Error: 4_120
+			.saturating_add(Weight::from_parts(11_870_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10750).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:4 w:4)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:4 w:4)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentHistory` (r:1 w:1)
+	/// Proof: `Shadow::ConsentHistory` (`max_values`: None, `max_size`: Some(2707), added: 5182, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:0 w:1)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::RevokedConsents` (r:0 w:1)
+	/// Proof: `Shadow::RevokedConsents` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:800 w:800)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:100)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 100]`.
+	fn opt_out(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321 + n * (898 ±0)`
+		//  Estimated: `11740`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(39_214_000, 11740)
+			// Standard Error: 2_310
+			.saturating_add(Weight::from_parts(388_400, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(16_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((9_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 898).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::OptedOut` (r:0 w:1)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn opt_in() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
@@ -84,49 +838,1056 @@ impl<T: polkadot_sdk::frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(9_000_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentHistory` (r:1 w:1)
+	/// Proof: `Shadow::ConsentHistory` (`max_values`: None, `max_size`: Some(2707), added: 5182, mode: `MaxEncodedLen`)
+	fn renew_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `6172`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 6172)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SourceMetaTemplate` (r:1 w:0)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:33 w:33)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:2)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionsThisBlock` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionsThisBlock` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	fn replace_item(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321`
+		//  Estimated: `12306`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 12306)
+			// Standard Error: 44
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(47_u64))
+			.saturating_add(T::DbWeight::get().writes(63_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:0)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:1 w:1)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	fn revoke_share() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1326`
+		//  Estimated: `12306`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ItemExpiryCursor` (r:1 w:1)
+	/// Proof: `Shadow::ItemExpiryCursor` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:11 w:10)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:10)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:32010 w:32010)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:10 w:10)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1000)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1000 w:1000)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16000)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1000)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1000 w:1000)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1000)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1000)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 10]`.
+	fn expire_items(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + n * (8874 ±0)`
+		//  Estimated: `990`
+		// Minimum execution time: 3_000_000 picoseconds.
+		Weight::from_parts(3_208_000, 990)
+			// Standard Error: 5_210
+			.saturating_add(Weight::from_parts(41_380_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((3403_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((5404_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 11316).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::ItemIndex` (r:1 w:0)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn transfer_item_ownership() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142`
+		//  Estimated: `3565`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 3565)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3201 w:3201)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:800 w:800)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:100)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:100 w:100)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:1600)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:100)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:100 w:100)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:100)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:100)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 100]`.
+	fn delete_items_by_source(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + n * (898 ±0)`
+		//  Estimated: `1493`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(15_604_000, 1493)
+			// Standard Error: 2_377
+			.saturating_add(Weight::from_parts(428_900, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().reads((42_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((62_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 898).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::ItemIndex` (r:1 w:0)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:1 w:0)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(612), added: 3087, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:1 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	fn access_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1502`
+		//  Estimated: `4846`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 4846)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::RegisteredSources` (r:0 w:1)
+	/// Proof: `Shadow::RegisteredSources` (`max_values`: None, `max_size`: Some(51), added: 2526, mode: `MaxEncodedLen`)
+	fn register_source() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::PendingTransfers` (r:1 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:2 w:2)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:1 w:2)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentCheckCache` (r:1 w:1)
+	/// Proof: `Shadow::ConsentCheckCache` (`max_values`: Some(1), `max_size`: Some(514), added: 1009, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:4 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:16 w:16)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:0)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:2 w:2)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:2)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:3)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:1 w:2)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	fn accept_item_transfer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `19361`
+		//  Estimated: `25003`
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(37_000_000, 25003)
+			.saturating_add(T::DbWeight::get().reads(34_u64))
+			.saturating_add(T::DbWeight::get().writes(33_u64))
+	}
+	/// Storage: `Shadow::PendingTransfers` (r:1 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn cancel_item_transfer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142`
+		//  Estimated: `3593`
+		// Minimum execution time: 10_000_000 picoseconds.
+		Weight::from_parts(11_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentNonce` (r:1 w:1)
+	/// Proof: `Shadow::ConsentNonce` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn resign_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `298`
+		//  Estimated: `3544`
+		// Minimum execution time: 59_000_000 picoseconds.
+		Weight::from_parts(61_000_000, 3544)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::RegisteredSources` (r:1 w:1)
+	/// Proof: `Shadow::RegisteredSources` (`max_values`: None, `max_size`: Some(51), added: 2526, mode: `MaxEncodedLen`)
+	fn unregister_source() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `131`
+		//  Estimated: `3516`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 3516)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ReapedItems` (r:1 w:1)
+	/// Proof: `Shadow::ReapedItems` (`max_values`: None, `max_size`: Some(398), added: 2873, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:8 w:8)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:32 w:32)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn clear_reaped_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 12306)
+			.saturating_add(T::DbWeight::get().reads(43_u64))
+			.saturating_add(T::DbWeight::get().writes(62_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
-	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
 	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
-	fn submit_shadow_item() -> Weight {
+	/// Storage: `Shadow::AccountBytes` (r:1 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SourceMetaTemplate` (r:1 w:0)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	fn submit_shadow_item(m: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `6`
 		//  Estimated: `12306`
 		// Minimum execution time: 21_000_000 picoseconds.
 		Weight::from_parts(22_000_000, 12306)
+			// Standard Error: 41
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:33 w:33)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:8 w:8)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:2)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 100]`.
+	fn delete_shadow_item(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + n * (898 ±0)`
+		//  Estimated: `12306`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(14_788_000, 12306)
+			// Standard Error: 1_954
+			.saturating_add(Weight::from_parts(402_600, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(47_u64))
+			.saturating_add(RocksDbWeight::get().writes(68_u64))
+			.saturating_add(Weight::from_parts(0, 898).saturating_mul(n.into()))
+	}
+	/// Storage: `System::Account` (r:1 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentTemplates` (r:1 w:0)
+	/// Proof: `Shadow::ConsentTemplates` (`max_values`: None, `max_size`: Some(81), added: 2556, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:2 w:2)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentHistory` (r:1 w:1)
+	/// Proof: `Shadow::ConsentHistory` (`max_values`: None, `max_size`: Some(2707), added: 5182, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::RevokedConsents` (r:0 w:1)
+	/// Proof: `Shadow::RevokedConsents` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentNonce` (r:1 w:1)
+	/// Proof: `Shadow::ConsentNonce` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn grant_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321`
+		//  Estimated: `22490`
+		// Minimum execution time: 78_000_000 picoseconds.
+		Weight::from_parts(80_000_000, 22490)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:1 w:1)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentHistory` (r:1 w:1)
+	/// Proof: `Shadow::ConsentHistory` (`max_values`: None, `max_size`: Some(2707), added: 5182, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::RevokedConsents` (r:0 w:1)
+	/// Proof: `Shadow::RevokedConsents` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn revoke_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321`
+		//  Estimated: `11740`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 11740)
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Shadow::AccountMetadataLimit` (r:0 w:1)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn set_metadata_limit_for_account() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::Sessions` (r:0 w:1)
+	/// Proof: `Shadow::Sessions` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	fn authorize_session() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Shadow::AuthorizedAgents` (r:1 w:0)
+	/// Proof: `Shadow::AuthorizedAgents` (`max_values`: None, `max_size`: Some(305), added: 2780, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Sessions` (r:1 w:0)
+	/// Proof: `Shadow::Sessions` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:1 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SourceMetaTemplate` (r:1 w:0)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:0)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	fn submit_shadow_item_for(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 12306)
+			// Standard Error: 41
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(15_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn pin_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
 	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
-	fn delete_shadow_item() -> Weight {
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn unpin_item() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `6`
 		//  Estimated: `12306`
 		// Minimum execution time: 15_000_000 picoseconds.
 		Weight::from_parts(16_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3201 w:3201)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:800 w:800)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:100)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:100 w:100)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:1600)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:100)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:100 w:100)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:100)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:100)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 100]`.
+	fn delete_all_shadow_items(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + n * (898 ±0)`
+		//  Estimated: `1493`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_512_000, 1493)
+			// Standard Error: 2_108
+			.saturating_add(Weight::from_parts(391_200, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((42_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((62_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 898).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::AuthorizedAgents` (r:1 w:1)
+	/// Proof: `Shadow::AuthorizedAgents` (`max_values`: None, `max_size`: Some(305), added: 2780, mode: `MaxEncodedLen`)
+	fn authorize_agent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `3770`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 3770)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-	/// Storage: `Shadow::ConsentRecords` (r:0 w:1)
-	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
-	fn grant_consent() -> Weight {
+	/// Storage: `Shadow::AuthorizedAgents` (r:1 w:1)
+	/// Proof: `Shadow::AuthorizedAgents` (`max_values`: None, `max_size`: Some(305), added: 2780, mode: `MaxEncodedLen`)
+	fn revoke_all_agents() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `3770`
+		// Minimum execution time: 10_000_000 picoseconds.
+		Weight::from_parts(11_000_000, 3770)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:0)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:1 w:1)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	fn share_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `11316`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 11316)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	fn amend_consent_scope() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `148`
+		//  Estimated: `3543`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 3543)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ConsentTemplates` (r:0 w:1)
+	/// Proof: `Shadow::ConsentTemplates` (`max_values`: None, `max_size`: Some(81), added: 2556, mode: `MaxEncodedLen`)
+	fn register_consent_template() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn update_cid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:2 w:2)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:1 w:1)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	fn transfer_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321`
+		//  Estimated: `11740`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 11740)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Shadow::SourceMetaTemplate` (r:0 w:1)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	fn set_source_meta_template() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:1 w:2)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:33 w:33)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:8 w:8)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:2)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn report_dead_cid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(48_u64))
+			.saturating_add(RocksDbWeight::get().writes(69_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:33 w:33)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:8 w:8)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:2)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn force_delete_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(47_u64))
+			.saturating_add(RocksDbWeight::get().writes(68_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:1 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SourceMetaTemplate` (r:1 w:0)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	/// The range of component `e` is `[0, 256]`.
+	fn submit_shadow_item_with_encrypted_metadata(m: u32, e: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 12306)
+			// Standard Error: 41
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			// Standard Error: 160
+			.saturating_add(Weight::from_parts(1_204, 0).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: `Shadow::TrustedSubmitters` (r:0 w:1)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn add_trusted() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::TrustedSubmitters` (r:0 w:1)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn remove_trusted() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:0)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn flag_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `300`
+		//  Estimated: `12306`
+		// Minimum execution time: 30_000_000 picoseconds.
+		Weight::from_parts(31_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:32 w:32)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 32]`.
+	fn resolve_flags(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `200`
+		//  Estimated: `4620`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 4620)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(9_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:0)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn reserve_slots() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
 		// Minimum execution time: 9_000_000 picoseconds.
-		Weight::from_parts(10_000_000, 0)
+		Weight::from_parts(10_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-	/// Storage: `Shadow::ConsentRecords` (r:0 w:1)
-	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
-	fn revoke_consent() -> Weight {
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn release_slots() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `3517`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 3517)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::PruneCursor` (r:1 w:1)
+	/// Proof: `Shadow::PruneCursor` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1001 w:1000)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1000 w:1000)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1000 w:1000)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:0 w:1000)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1000)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:0 w:1000)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 1000]`.
+	fn prune_empty_accounts(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3533`
+		// Minimum execution time: 6_000_000 picoseconds.
+		Weight::from_parts(5_000_000, 3533)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(9_600_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 11316).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+	fn verify_consent_signature() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 47_000_000 picoseconds.
+		Weight::from_parts(48_000_000, 0)
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	fn update_shadow_item(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 20_000_000 picoseconds.
+		Weight::from_parts(21_000_000, 12306)
+			// Standard Error: 41
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ConsentCheckCache` (r:0 w:1)
+	/// Proof: `Shadow::ConsentCheckCache` (`max_values`: Some(1), `max_size`: Some(514), added: 1009, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingItemsStored` (r:1 w:1)
+	/// Proof: `Shadow::PendingItemsStored` (`max_values`: Some(1), `max_size`: Some(2305), added: 2800, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionsThisBlock` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionsThisBlock` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalSubmissions` (r:1 w:1)
+	/// Proof: `Shadow::TotalSubmissions` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 64]`.
+	fn on_finalize(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `40`
+		//  Estimated: `1489`
+		// Minimum execution time: 4_000_000 picoseconds.
+		Weight::from_parts(4_500_000, 1489)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(1_100_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:1 w:1)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	fn add_tag() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:1 w:1)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	fn remove_tag() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:1 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:1)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:20)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 20]`.
+	fn submit_shadow_items_batch(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 12306)
+			// Standard Error: 2_310
+			.saturating_add(Weight::from_parts(3_412_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `Shadow::ConsentPruneCursor` (r:1 w:1)
+	/// Proof: `Shadow::ConsentPruneCursor` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:51 w:50)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:50 w:50)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentCheckCache` (r:1 w:1)
+	/// Proof: `Shadow::ConsentCheckCache` (`max_values`: Some(1), `max_size`: Some(514), added: 1009, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 50]`.
+	fn prune_expired_consents(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `990`
+		// Minimum execution time: 3_000_000 picoseconds.
+		Weight::from_parts(3_412_000, 990)
+			// Standard Error: 4_120
+			.saturating_add(Weight::from_parts(11_870_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10750).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:4 w:4)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentByMessage` (r:4 w:4)
+	/// Proof: `Shadow::ConsentByMessage` (`max_values`: None, `max_size`: Some(8275), added: 10750, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentHistory` (r:1 w:1)
+	/// Proof: `Shadow::ConsentHistory` (`max_values`: None, `max_size`: Some(2707), added: 5182, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:0 w:1)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::RevokedConsents` (r:0 w:1)
+	/// Proof: `Shadow::RevokedConsents` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:800 w:800)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:100)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 100]`.
+	fn opt_out(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321 + n * (898 ±0)`
+		//  Estimated: `11740`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(39_214_000, 11740)
+			// Standard Error: 2_310
+			.saturating_add(Weight::from_parts(388_400, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((9_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 898).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::OptedOut` (r:0 w:1)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	fn opt_in() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
@@ -134,4 +1895,303 @@ impl WeightInfo for () {
 		Weight::from_parts(9_000_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentHistory` (r:1 w:1)
+	/// Proof: `Shadow::ConsentHistory` (`max_values`: None, `max_size`: Some(2707), added: 5182, mode: `MaxEncodedLen`)
+	fn renew_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `6172`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 6172)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemCounter` (r:1 w:1)
+	/// Proof: `Shadow::ItemCounter` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountMetadataLimit` (r:1 w:0)
+	/// Proof: `Shadow::AccountMetadataLimit` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SourceMetaTemplate` (r:1 w:0)
+	/// Proof: `Shadow::SourceMetaTemplate` (`max_values`: None, `max_size`: Some(1044), added: 3519, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:33 w:33)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:2)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TrustedSubmitters` (r:1 w:0)
+	/// Proof: `Shadow::TrustedSubmitters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionRate` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionRate` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SubmissionsThisBlock` (r:1 w:1)
+	/// Proof: `Shadow::SubmissionsThisBlock` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// The range of component `m` is `[0, 1024]`.
+	fn replace_item(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `8321`
+		//  Estimated: `12306`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 12306)
+			// Standard Error: 44
+			.saturating_add(Weight::from_parts(1_187, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(47_u64))
+			.saturating_add(RocksDbWeight::get().writes(63_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:0)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:1 w:1)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	fn revoke_share() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1326`
+		//  Estimated: `12306`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::ItemExpiryCursor` (r:1 w:1)
+	/// Proof: `Shadow::ItemExpiryCursor` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:11 w:10)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:10)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:32010 w:32010)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:10 w:10)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1000)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1000 w:1000)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16000)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1000)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1000 w:1000)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1000)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1000)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 10]`.
+	fn expire_items(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + n * (8874 ±0)`
+		//  Estimated: `990`
+		// Minimum execution time: 3_000_000 picoseconds.
+		Weight::from_parts(3_208_000, 990)
+			// Standard Error: 5_210
+			.saturating_add(Weight::from_parts(41_380_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((3403_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((5404_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 11316).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::ItemIndex` (r:1 w:0)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn transfer_item_ownership() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142`
+		//  Estimated: `3565`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 3565)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ShadowItems` (r:1 w:1)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:0 w:1)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:1 w:1)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:3201 w:3201)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TotalItems` (r:1 w:1)
+	/// Proof: `Shadow::TotalItems` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:800 w:800)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:100)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:100 w:100)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:1600)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:100)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:100 w:100)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:100)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:100)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 100]`.
+	fn delete_items_by_source(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + n * (898 ±0)`
+		//  Estimated: `1493`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(15_604_000, 1493)
+			// Standard Error: 2_377
+			.saturating_add(Weight::from_parts(428_900, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((42_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((62_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 898).saturating_mul(n.into()))
+	}
+	/// Storage: `Shadow::ItemIndex` (r:1 w:0)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:1 w:0)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(612), added: 3087, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:1 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	fn access_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1502`
+		//  Estimated: `4846`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 4846)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::RegisteredSources` (r:0 w:1)
+	/// Proof: `Shadow::RegisteredSources` (`max_values`: None, `max_size`: Some(51), added: 2526, mode: `MaxEncodedLen`)
+	fn register_source() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::PendingTransfers` (r:1 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShadowItems` (r:2 w:2)
+	/// Proof: `Shadow::ShadowItems` (`max_values`: None, `max_size`: Some(8841), added: 11316, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountBytes` (r:1 w:2)
+	/// Proof: `Shadow::AccountBytes` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::OptedOut` (r:1 w:0)
+	/// Proof: `Shadow::OptedOut` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentCheckCache` (r:1 w:1)
+	/// Proof: `Shadow::ConsentCheckCache` (`max_values`: Some(1), `max_size`: Some(514), added: 1009, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentRecords` (r:4 w:0)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:16 w:16)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ReservedSlots` (r:1 w:0)
+	/// Proof: `Shadow::ReservedSlots` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccountStats` (r:2 w:2)
+	/// Proof: `Shadow::AccountStats` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:2)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:1 w:3)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:1 w:2)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	fn accept_item_transfer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `19361`
+		//  Estimated: `25003`
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(37_000_000, 25003)
+			.saturating_add(RocksDbWeight::get().reads(34_u64))
+			.saturating_add(RocksDbWeight::get().writes(33_u64))
+	}
+	/// Storage: `Shadow::PendingTransfers` (r:1 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn cancel_item_transfer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142`
+		//  Estimated: `3593`
+		// Minimum execution time: 10_000_000 picoseconds.
+		Weight::from_parts(11_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ConsentRecords` (r:1 w:1)
+	/// Proof: `Shadow::ConsentRecords` (`max_values`: None, `max_size`: Some(79), added: 2554, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ConsentNonce` (r:1 w:1)
+	/// Proof: `Shadow::ConsentNonce` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn resign_consent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `298`
+		//  Estimated: `3544`
+		// Minimum execution time: 59_000_000 picoseconds.
+		Weight::from_parts(61_000_000, 3544)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Shadow::RegisteredSources` (r:1 w:1)
+	/// Proof: `Shadow::RegisteredSources` (`max_values`: None, `max_size`: Some(51), added: 2526, mode: `MaxEncodedLen`)
+	fn unregister_source() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `131`
+		//  Estimated: `3516`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 3516)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Shadow::ReapedItems` (r:1 w:1)
+	/// Proof: `Shadow::ReapedItems` (`max_values`: None, `max_size`: Some(398), added: 2873, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ItemIndex` (r:0 w:1)
+	/// Proof: `Shadow::ItemIndex` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::TagIndex` (r:8 w:8)
+	/// Proof: `Shadow::TagIndex` (`max_values`: None, `max_size`: Some(32101), added: 34576, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::ShareRecipients` (r:1 w:1)
+	/// Proof: `Shadow::ShareRecipients` (`max_values`: None, `max_size`: Some(306), added: 2781, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::SharedKeys` (r:0 w:16)
+	/// Proof: `Shadow::SharedKeys` (`max_values`: None, `max_size`: Some(614), added: 3089, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::AccessLog` (r:0 w:1)
+	/// Proof: `Shadow::AccessLog` (`max_values`: None, `max_size`: Some(1381), added: 3856, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::Flags` (r:1 w:1)
+	/// Proof: `Shadow::Flags` (`max_values`: None, `max_size`: Some(2145), added: 4620, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:32 w:32)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::DeadCidReports` (r:0 w:1)
+	/// Proof: `Shadow::DeadCidReports` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `Shadow::PendingTransfers` (r:0 w:1)
+	/// Proof: `Shadow::PendingTransfers` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn clear_reaped_item() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `12306`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 12306)
+			.saturating_add(RocksDbWeight::get().reads(43_u64))
+			.saturating_add(RocksDbWeight::get().writes(62_u64))
+	}
 }
\ No newline at end of file
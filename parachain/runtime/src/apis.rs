@@ -25,6 +25,7 @@
 
 // External crates imports
 use alloc::vec::Vec;
+use codec::Encode;
 
 use polkadot_sdk::*;
 
@@ -45,9 +46,9 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Balance, Block, ConsensusHook, Executive, InherentDataExt, Nonce, ParachainSystem,
-	Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment,
-	SLOT_DURATION, VERSION,
+	AccountId, Balance, Block, BlockNumber, ConsensusHook, Executive, Hash, InherentDataExt,
+	Nonce, ParachainSystem, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, Shadow,
+	System, TransactionPayment, SIGNED_EXTRINSIC_OVERHEAD, SLOT_DURATION, VERSION,
 };
 
 // we move some impls outside so we can easily use them with `docify`.
@@ -225,6 +226,121 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_shadow::runtime_api::ShadowApi<
+		Block,
+		AccountId,
+		Hash,
+		pallet_shadow::ShadowItem<Runtime>,
+		BlockNumber,
+		pallet_shadow::ConsentAudit<Runtime>,
+		Balance,
+		pallet_shadow::ConsentRecord<Runtime, BlockNumber>,
+	> for Runtime {
+		fn shadow_items(account: AccountId) -> Vec<pallet_shadow::ShadowItem<Runtime>> {
+			Shadow::items_of(&account).into_inner()
+		}
+
+		fn consent_status(account: AccountId) -> pallet_shadow::ConsentStatus<BlockNumber> {
+			Shadow::consent_status(&account)
+		}
+
+		fn items_diff(
+			account: AccountId,
+			known_ids: Vec<Hash>,
+		) -> Result<
+			(Vec<pallet_shadow::ShadowItem<Runtime>>, Vec<Hash>),
+			sp_runtime::DispatchError,
+		> {
+			Shadow::items_diff(&account, known_ids)
+		}
+
+		fn submit_readiness(account: AccountId) -> pallet_shadow::SubmitReadiness<BlockNumber> {
+			Shadow::submit_readiness(&account)
+		}
+
+		fn account_storage_bytes(account: AccountId) -> u64 {
+			Shadow::account_storage_bytes(&account)
+		}
+
+		fn item_ids_paged(account: AccountId, after: Option<Hash>, limit: u32) -> Vec<Hash> {
+			Shadow::item_ids_paged(&account, after, limit)
+		}
+
+		fn has_cid(account: AccountId, cid: Vec<u8>) -> bool {
+			Shadow::has_cid(&account, &cid)
+		}
+
+		fn items_by_cid_prefix(
+			account: AccountId,
+			prefix: Vec<u8>,
+			limit: u32,
+		) -> Vec<pallet_shadow::ShadowItem<Runtime>> {
+			Shadow::items_by_cid_prefix(&account, &prefix, limit)
+		}
+
+		fn items_by_source(
+			account: AccountId,
+			source: pallet_shadow::Source,
+			offset: u32,
+			limit: u32,
+		) -> Vec<pallet_shadow::ShadowItem<Runtime>> {
+			Shadow::items_by_source(&account, source, offset, limit)
+		}
+
+		fn oldest_item(account: AccountId) -> Option<(Hash, u64)> {
+			Shadow::oldest_item(&account)
+		}
+
+		fn source_breakdown(account: AccountId) -> Vec<(pallet_shadow::Source, u32)> {
+			Shadow::source_breakdown(&account)
+		}
+
+		fn accounts_with_message(message_hash: Vec<u8>) -> Vec<AccountId> {
+			Shadow::accounts_with_message(message_hash)
+		}
+
+		fn total_items() -> u32 {
+			Shadow::total_items()
+		}
+
+		fn consent_count() -> u32 {
+			Shadow::consent_count()
+		}
+
+		fn consent_audit(account: AccountId) -> pallet_shadow::ConsentAudit<Runtime> {
+			Shadow::consent_audit(&account)
+		}
+
+		fn estimate_submit_fee(
+			cid_len: u32,
+			key_len: u32,
+			metadata_len: u32,
+			source: pallet_shadow::Source,
+		) -> Balance {
+			let call = RuntimeCall::Shadow(Shadow::submit_call(cid_len, key_len, metadata_len, source));
+			let len = (call.encoded_size() as u32).saturating_add(SIGNED_EXTRINSIC_OVERHEAD);
+			TransactionPayment::query_call_info(call, len).partial_fee
+		}
+
+		fn account_stats(account: AccountId) -> pallet_shadow::Stats {
+			Shadow::account_stats(&account)
+		}
+
+		fn list_consents(
+			account: AccountId,
+		) -> Vec<(u32, pallet_shadow::ConsentRecord<Runtime, BlockNumber>)> {
+			Shadow::list_consents(&account)
+		}
+
+		fn shared_key(recipient: AccountId, item_id: Hash) -> Option<Vec<u8>> {
+			Shadow::shared_key(&recipient, item_id)
+		}
+
+		fn items_storage_key(account: AccountId) -> Vec<u8> {
+			Shadow::items_storage_key(&account)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
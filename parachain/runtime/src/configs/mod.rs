@@ -62,8 +62,9 @@ use super::{
 	AccountId, Aura, Balance, Balances, Block, BlockNumber, CollatorSelection, ConsensusHook, Hash,
 	MessageQueue, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent,
 	RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, Session, SessionKeys,
-	System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, EXISTENTIAL_DEPOSIT, HOURS,
-	MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
+	Shadow, System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, EXISTENTIAL_DEPOSIT,
+	HOURS, MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, MILLI_UNIT, MINUTES, NORMAL_DISPATCH_RATIO,
+	SLOT_DURATION, VERSION,
 };
 use xcm_config::{RelayLocation, XcmOriginToTransactDispatchOrigin};
 
@@ -127,6 +128,8 @@ impl frame_system::Config for Runtime {
 	/// The action to take on a Runtime Upgrade
 	type OnSetCode = cumulus_pallet_parachain_system::ParachainSetCode<Self>;
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	/// Clean up Shadow consent and items when an account is reaped.
+	type OnKilledAccount = Shadow;
 }
 
 /// Configure the palelt weight reclaim tx.
@@ -318,11 +321,51 @@ impl pallet_collator_selection::Config for Runtime {
 
 parameter_types! {
 	pub const MaxItemsPerAccount: u32 = 100;
+	pub const MaxBatchSize: u32 = 20;
+	pub const MaxSubmissionsPerWindow: u32 = 50;
+	pub const RateLimitWindow: BlockNumber = MINUTES;
+	pub const MaxConsentPrunedPerBlock: u32 = 50;
+	pub const MaxAccountsExpiredPerBlock: u32 = 10;
+	pub const MaxConsentsPerAccount: u32 = 4;
 	pub const MaxCidLength: u32 = 64;        // For IPFS CIDs (typically 46 chars for CIDv1)
 	pub const MaxKeyLength: u32 = 512;       // For encrypted symmetric keys
-	pub const MaxSourceLength: u32 = 32;     // For source names like "GitHub", "Twitter"
 	pub const MaxMetadataLength: u32 = 256;
+	pub const MaxMetadataLengthHard: u32 = 1024; // Upper bound for premium overrides
 	pub const MaxMessageHashLength: u32 = 64;
+	pub const MaxSourceNameLen: u32 = 32;
+	pub const MaxSessionDuration: BlockNumber = 2 * HOURS;
+	pub const MaxConsentCacheEntries: u32 = 64;
+	pub const MaxAgents: u32 = 8;
+	pub const MaxSharesPerItem: u32 = 16;
+	pub const MaxAccessLogEntries: u32 = 32;
+	pub const MaxScopeSources: u32 = 8;
+	pub const MaxTagsPerItem: u32 = 8;
+	pub const MaxTagLength: u32 = 32;
+	pub const MaxItemsPerTag: u32 = 1000;
+	pub const AggregateEvents: bool = false;
+	pub const MaxAggregatedAccounts: u32 = 64;
+	pub const EnforceTemplates: bool = false;
+	pub const MaxAccountsPerMessage: u32 = 256;
+	pub const ConsentExpiryInclusive: bool = true;
+	pub const DeadCidThreshold: u32 = 3;
+	pub const MaxEncMetaLength: u32 = 256;
+	pub const MaxConsentHistory: u32 = 32;
+	pub const FlagDeposit: Balance = 100 * MILLI_UNIT;
+	pub const FlagReviewThreshold: u32 = 5;
+	pub const MaxFlagsPerItem: u32 = 32;
+	pub const NormalizeMessageHash: bool = false;
+	pub const ExpiredPolicy: pallet_shadow::ExpiredConsentPolicy =
+		pallet_shadow::ExpiredConsentPolicy::Reject;
+	pub const MinBalanceForConsent: Balance = 0;
+	pub const MaxItemTotalBytes: u32 =
+		MaxCidLength::get() + MaxKeyLength::get() + MaxMetadataLengthHard::get();
+	pub const MaxBytesPerAccount: u32 = 64 * 1024;
+	pub const ComplianceKey: Option<AccountId> = None;
+	pub const RequireCosignedConsent: bool = false;
+	pub const RequireRecipientConsent: bool = true;
+	pub const ClearItemsOnOptOut: bool = true;
+	pub const DepositPerItem: Balance = MILLI_UNIT;
+	pub const StrictCidValidation: bool = true;
 }
 
 /// Configure the Shadow pallet for storing Web2 activity data with encryption.
@@ -331,9 +374,69 @@ impl pallet_shadow::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_shadow::weights::SubstrateWeight<Runtime>;
 	type MaxItemsPerAccount = MaxItemsPerAccount;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxSubmissionsPerWindow = MaxSubmissionsPerWindow;
+	type RateLimitWindow = RateLimitWindow;
+	type MaxConsentPrunedPerBlock = MaxConsentPrunedPerBlock;
+	type MaxAccountsExpiredPerBlock = MaxAccountsExpiredPerBlock;
+	type MaxConsentsPerAccount = MaxConsentsPerAccount;
+	type ClearItemsOnOptOut = ClearItemsOnOptOut;
+	type DepositPerItem = DepositPerItem;
+	type OnConsentRevoked = ();
 	type MaxCidLength = MaxCidLength;
 	type MaxKeyLength = MaxKeyLength;
-	type MaxSourceLength = MaxSourceLength;
 	type MaxMetadataLength = MaxMetadataLength;
+	type MaxMetadataLengthHard = MaxMetadataLengthHard;
 	type MaxMessageHashLength = MaxMessageHashLength;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type RegisterOrigin = EnsureRoot<AccountId>;
+	type MaxSourceNameLen = MaxSourceNameLen;
+	type MaxSessionDuration = MaxSessionDuration;
+	type MaxConsentCacheEntries = MaxConsentCacheEntries;
+	type MaxAgents = MaxAgents;
+	type Currency = Balances;
+	type MinBalanceForConsent = MinBalanceForConsent;
+	type CidValidator = ();
+	type CidLimitForSource = ();
+	type StrictCidValidation = StrictCidValidation;
+	type MaxSharesPerItem = MaxSharesPerItem;
+	type MaxAccessLogEntries = MaxAccessLogEntries;
+	type MaxScopeSources = MaxScopeSources;
+	type MaxTagsPerItem = MaxTagsPerItem;
+	type MaxTagLength = MaxTagLength;
+	type MaxItemsPerTag = MaxItemsPerTag;
+	type AggregateEvents = AggregateEvents;
+	type MaxAggregatedAccounts = MaxAggregatedAccounts;
+	type EnforceTemplates = EnforceTemplates;
+	type MaxAccountsPerMessage = MaxAccountsPerMessage;
+	type ConsentExpiryInclusive = ConsentExpiryInclusive;
+	// No oracle set is configured yet, so dead-CID reporting is disabled
+	type ReporterOrigin = frame_system::EnsureNever<AccountId>;
+	type DeadCidThreshold = DeadCidThreshold;
+	type MaxEncMetaLength = MaxEncMetaLength;
+	type MaxConsentHistory = MaxConsentHistory;
+	type FlagDeposit = FlagDeposit;
+	type FlagReviewThreshold = FlagReviewThreshold;
+	type MaxFlagsPerItem = MaxFlagsPerItem;
+	type NormalizeMessageHash = NormalizeMessageHash;
+	type ExpiredConsentPolicy = ExpiredPolicy;
+	type AccountPublicKey = sp_runtime::traits::ConvertInto;
+	type MaxItemTotalBytes = MaxItemTotalBytes;
+	type MaxBytesPerAccount = MaxBytesPerAccount;
+	type ComplianceKey = ComplianceKey;
+	type RequireCosignedConsent = RequireCosignedConsent;
+	type RequireRecipientConsent = RequireRecipientConsent;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ShadowBenchmarkHelper;
+}
+
+/// Accounts are their sr25519 public keys or hashed ECDSA keys, so a benchmark key is its own
+/// account.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ShadowBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_shadow::BenchmarkHelper<AccountId> for ShadowBenchmarkHelper {
+	fn account_for_key(public: [u8; 32]) -> AccountId {
+		AccountId::from(public)
+	}
 }
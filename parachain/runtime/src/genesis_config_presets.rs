@@ -1,6 +1,7 @@
 use crate::{
 	AccountId, BalancesConfig, CollatorSelectionConfig, ParachainInfoConfig, PolkadotXcmConfig,
-	RuntimeGenesisConfig, SessionConfig, SessionKeys, SudoConfig, EXISTENTIAL_DEPOSIT,
+	RuntimeGenesisConfig, SessionConfig, SessionKeys, ShadowConfig, SudoConfig,
+	EXISTENTIAL_DEPOSIT,
 };
 
 use alloc::{vec, vec::Vec};
@@ -19,6 +20,10 @@ const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
 /// Parachain id used for genesis config presets of parachain template.
 #[docify::export_content]
 pub const PARACHAIN_ID: u32 = 1000;
+/// Preset id for a development chain with the shadow pallet disabled.
+pub const DISABLED_RUNTIME_PRESET: &str = "disabled";
+/// CID of the item seeded for Alice on development chains.
+const DEV_SHADOW_ITEM_CID: &[u8] = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
 
 /// Generate the session keys from individual elements.
 ///
@@ -32,7 +37,26 @@ fn testnet_genesis(
 	endowed_accounts: Vec<AccountId>,
 	root: AccountId,
 	id: ParaId,
+	shadow_enabled: bool,
+	shadow_seed: bool,
 ) -> Value {
+	// A permanent consent and one item for Alice, so a fresh chain has data to query
+	let alice = Sr25519Keyring::Alice.to_account_id();
+	let (shadow_consents, shadow_items) = if shadow_seed {
+		(
+			vec![(alice.clone(), 0, None, b"dev_consent".to_vec())],
+			vec![(
+				alice,
+				DEV_SHADOW_ITEM_CID.to_vec(),
+				b"dev_encrypted_key".to_vec(),
+				pallet_shadow::Source::GitHub,
+				b"dev item".to_vec(),
+			)],
+		)
+	} else {
+		(vec![], vec![])
+	};
+
 	build_struct_json_patch!(RuntimeGenesisConfig {
 		balances: BalancesConfig {
 			balances: endowed_accounts
@@ -60,6 +84,11 @@ fn testnet_genesis(
 		},
 		polkadot_xcm: PolkadotXcmConfig { safe_xcm_version: Some(SAFE_XCM_VERSION) },
 		sudo: SudoConfig { key: Some(root) },
+		shadow: ShadowConfig {
+			enabled: shadow_enabled,
+			consents: shadow_consents,
+			items: shadow_items,
+		},
 	})
 }
 
@@ -73,10 +102,12 @@ fn local_testnet_genesis() -> Value {
 		Sr25519Keyring::well_known().map(|k| k.to_account_id()).collect(),
 		Sr25519Keyring::Alice.to_account_id(),
 		PARACHAIN_ID.into(),
+		true,
+		false,
 	)
 }
 
-fn development_config_genesis() -> Value {
+fn development_config_genesis(shadow_enabled: bool) -> Value {
 	testnet_genesis(
 		// initial collators.
 		vec![
@@ -86,6 +117,8 @@ fn development_config_genesis() -> Value {
 		Sr25519Keyring::well_known().map(|k| k.to_account_id()).collect(),
 		Sr25519Keyring::Alice.to_account_id(),
 		PARACHAIN_ID.into(),
+		shadow_enabled,
+		true,
 	)
 }
 
@@ -93,7 +126,8 @@ fn development_config_genesis() -> Value {
 pub fn get_preset(id: &PresetId) -> Option<vec::Vec<u8>> {
 	let patch = match id.as_ref() {
 		sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => local_testnet_genesis(),
-		sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(),
+		sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(true),
+		DISABLED_RUNTIME_PRESET => development_config_genesis(false),
 		_ => return None,
 	};
 	Some(
@@ -108,5 +142,6 @@ pub fn preset_names() -> Vec<PresetId> {
 	vec![
 		PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
 		PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+		PresetId::from(DISABLED_RUNTIME_PRESET),
 	]
 }
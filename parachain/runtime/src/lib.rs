@@ -98,7 +98,26 @@ pub type UncheckedExtrinsic =
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+	pallet_shadow::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_shadow::migrations::v2::MigrateV1ToV2<Runtime>,
+	pallet_shadow::migrations::v3::MigrateV2ToV3<Runtime>,
+	pallet_shadow::migrations::v4::MigrateV3ToV4<Runtime>,
+	pallet_shadow::migrations::v5::MigrateV4ToV5<Runtime>,
+	pallet_shadow::migrations::v6::MigrateV5ToV6<Runtime>,
+	pallet_shadow::migrations::v7::MigrateV6ToV7<Runtime>,
+	pallet_shadow::migrations::v8::MigrateV7ToV8<Runtime>,
+	pallet_shadow::migrations::v9::MigrateV8ToV9<Runtime>,
+	pallet_shadow::migrations::v10::MigrateV9ToV10<Runtime>,
+	pallet_shadow::migrations::v11::MigrateV10ToV11<Runtime>,
+	pallet_shadow::migrations::v12::MigrateV11ToV12<Runtime>,
+	pallet_shadow::migrations::v13::MigrateV12ToV13<Runtime>,
+	pallet_shadow::migrations::v14::MigrateV13ToV14<Runtime>,
+	pallet_shadow::migrations::v15::MigrateV14ToV15<Runtime>,
+	pallet_shadow::migrations::v16::MigrateV15ToV16<Runtime>,
+	pallet_shadow::migrations::v17::MigrateV16ToV17<Runtime>,
+	pallet_shadow::migrations::v18::MigrateV17ToV18<Runtime>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -206,6 +225,10 @@ pub const MICRO_UNIT: Balance = 1_000_000;
 /// The existential deposit. Set to 1/10 of the Connected Relay Chain.
 pub const EXISTENTIAL_DEPOSIT: Balance = MILLI_UNIT;
 
+/// Upper bound on the bytes a signed extrinsic adds to its call: the length prefix, version,
+/// address, signature and the encoded `TxExtension`.
+pub const SIGNED_EXTRINSIC_OVERHEAD: u32 = 112;
+
 /// We assume that ~5% of the block weight is consumed by `on_initialize` handlers. This is
 /// used to limit the maximal weight of a single extrinsic.
 const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_percent(5);